@@ -0,0 +1,2 @@
+-- Add a per-list flag to hide completed items in the TUI
+ALTER TABLE todo_lists ADD COLUMN hide_done BOOLEAN NOT NULL DEFAULT FALSE;
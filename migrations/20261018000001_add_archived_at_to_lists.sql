@@ -0,0 +1,4 @@
+-- Allow lists to be archived instead of deleted
+ALTER TABLE todo_lists ADD COLUMN archived_at TEXT;
+
+CREATE INDEX idx_todo_lists_archived_at ON todo_lists(archived_at);
@@ -0,0 +1,6 @@
+-- Soft deletes: deleted lists and items stay in the trash until purged
+ALTER TABLE todo_lists ADD COLUMN deleted_at TEXT;
+ALTER TABLE todo_items ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX idx_todo_lists_deleted_at ON todo_lists(deleted_at);
+CREATE INDEX idx_todo_items_deleted_at ON todo_items(deleted_at);
@@ -0,0 +1,2 @@
+-- Let each list choose how its items are sorted (manual keeps the ordering field)
+ALTER TABLE todo_lists ADD COLUMN sort_mode TEXT NOT NULL DEFAULT 'manual';
@@ -0,0 +1,6 @@
+-- Kanban board: each list has its own columns (comma separated) and each item a status
+ALTER TABLE todo_lists ADD COLUMN statuses TEXT NOT NULL DEFAULT 'todo,doing,done';
+ALTER TABLE todo_items ADD COLUMN status TEXT NOT NULL DEFAULT 'todo';
+
+-- Completed items belong in the final column
+UPDATE todo_items SET status = 'done' WHERE is_done = TRUE;
@@ -0,0 +1,16 @@
+-- Tags of an item, comma separated
+ALTER TABLE todo_items ADD COLUMN tags TEXT NOT NULL DEFAULT '';
+
+-- Saved filters, shown as virtual lists of the items matching all of their criteria
+CREATE TABLE smart_lists (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    ordering INTEGER NOT NULL DEFAULT 0,
+    min_priority TEXT CHECK (min_priority IN ('high', 'medium', 'low')),
+    due_within_days INTEGER,
+    is_done BOOLEAN,
+    tag TEXT,
+    text TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
@@ -0,0 +1,22 @@
+-- Orderings must be unique: lists among all lists, items within their list.
+-- Separate writers used to be able to hand out the same ordering twice, so
+-- renumber existing orderings first, keeping their order and breaking ties by id.
+-- Running this again on a repaired database leaves it unchanged.
+CREATE TEMP TABLE list_orderings AS
+    SELECT id, ROW_NUMBER() OVER (ORDER BY ordering, id) AS ordering
+    FROM todo_lists;
+UPDATE todo_lists
+SET ordering = (SELECT o.ordering FROM list_orderings o WHERE o.id = todo_lists.id);
+DROP TABLE list_orderings;
+
+CREATE TEMP TABLE item_orderings AS
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY list_id ORDER BY ordering, id) AS ordering
+    FROM todo_items;
+UPDATE todo_items
+SET ordering = (SELECT o.ordering FROM item_orderings o WHERE o.id = todo_items.id);
+DROP TABLE item_orderings;
+
+DROP INDEX IF EXISTS idx_todo_lists_ordering;
+DROP INDEX IF EXISTS idx_todo_items_list_ordering;
+CREATE UNIQUE INDEX idx_todo_lists_ordering ON todo_lists(ordering);
+CREATE UNIQUE INDEX idx_todo_items_list_ordering ON todo_items(list_id, ordering);
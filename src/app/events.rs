@@ -1,5 +1,6 @@
 use crate::app::state::{App, CurrentScreen};
-use crate::ui::components::{ItemsComponent, ListsComponent};
+use crate::db::quick_add::QuickAdd;
+use crate::ui::components::{ItemsComponent, ListsComponent, StatusMessage};
 use crate::ui::cursor::CursorState;
 use arboard::Clipboard;
 #[cfg(target_os = "linux")]
@@ -13,6 +14,9 @@ impl EventHandler {
     pub async fn handle_main_screen_key(app: &mut App, key: KeyEvent) {
         match (key.code, key.modifiers) {
             (KeyCode::Char('q'), KeyModifiers::NONE) => app.exit = true, // Quit application
+            (KeyCode::Char('?'), KeyModifiers::SHIFT | KeyModifiers::NONE) => {
+                app.enter_item_keys_screen() // Show what the keys of the items panel do
+            }
             (KeyCode::Char('s'), KeyModifiers::NONE) => app.lists_component.select_next(), // Navigate down in lists
             (KeyCode::Char('w'), KeyModifiers::NONE) => app.lists_component.select_previous(), // Navigate up in lists
             (KeyCode::Char('A'), KeyModifiers::SHIFT) => app.enter_add_list_screen(), // Add new list
@@ -28,28 +32,128 @@ impl EventHandler {
                     app.enter_modify_item_screen(&selected_list.clone())
                 }
             } // Modify existing item
+            (KeyCode::Char('R'), KeyModifiers::SHIFT) => {
+                if let Err(e) =
+                    ListsComponent::archive_selected_list(&mut app.lists_component, &app.pool).await
+                {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to archive list: {}",
+                        e
+                    )));
+                }
+            } // Archive selected list
+            (KeyCode::Char('V'), KeyModifiers::SHIFT) => {
+                if let Err(e) = app.enter_archive_screen().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to open archive: {}",
+                        e
+                    )));
+                }
+            } // View archived lists
+            (KeyCode::Char('Z'), KeyModifiers::SHIFT) => {
+                if let Err(e) = app.enter_trash_screen().await {
+                    app.status = Some(StatusMessage::error(format!("Failed to open trash: {}", e)));
+                }
+            } // View deleted lists and items
+            (KeyCode::Char('g'), KeyModifiers::NONE) => {
+                if let Err(e) = app.enter_agenda_screen().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to open agenda: {}",
+                        e
+                    )));
+                }
+            } // View agenda of due items
+            (KeyCode::Char('k'), KeyModifiers::NONE) => {
+                if let Err(e) = app.enter_board_screen().await {
+                    app.status = Some(StatusMessage::error(format!("Failed to open board: {}", e)));
+                }
+            } // View selected list as a board
+            (KeyCode::Char('/'), KeyModifiers::NONE) => app.enter_filter_screen(), // Filter items with a query
+            (KeyCode::Char('@'), KeyModifiers::SHIFT | KeyModifiers::NONE) => {
+                app.enter_due_date_screen() // Set due date of selected item
+            }
             (KeyCode::Char('D'), KeyModifiers::SHIFT) => {
                 if let Err(e) =
                     ListsComponent::delete_selected_list_static(&mut app.lists_component, &app.pool)
                         .await
                 {
-                    // Log error but don't crash the application
-                    eprintln!("Failed to delete list: {}", e);
+                    // Show the error but don't crash the application
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to delete list: {}",
+                        e
+                    )));
                 }
             }
             (KeyCode::Char('d'), KeyModifiers::NONE) => {
-                if let Some(selected_list) = app.lists_component.get_selected_list_mut()
-                    && let Err(e) =
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut() {
+                    if let Err(e) =
                         ItemsComponent::delete_selected_item(selected_list, &app.pool).await
-                {
-                    eprintln!("Failed to delete item: {}", e);
+                    {
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to delete item: {}",
+                            e
+                        )));
+                    } else if let Err(e) = app.lists_component.sync_items(&app.pool).await {
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to refresh items: {}",
+                            e
+                        )));
+                    }
                 }
             }
             (KeyCode::Enter, KeyModifiers::NONE) => {
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut() {
+                    if let Err(e) = ItemsComponent::toggle_item_done(selected_list, &app.pool).await
+                    {
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to toggle item: {}",
+                            e
+                        )));
+                    } else if let Err(e) = app.lists_component.sync_items(&app.pool).await {
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to refresh items: {}",
+                            e
+                        )));
+                    }
+                }
+            }
+            // Hide or show completed items
+            (KeyCode::Char('h'), KeyModifiers::NONE) => {
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut()
+                    && let Err(e) = ItemsComponent::toggle_hide_done(selected_list, &app.pool).await
+                {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to toggle completed items: {}",
+                        e
+                    )));
+                }
+            }
+            // Cycle through the sort modes of the items
+            (KeyCode::Char('o'), KeyModifiers::NONE) => {
                 if let Some(selected_list) = app.lists_component.get_selected_list_mut()
-                    && let Err(e) = ItemsComponent::toggle_item_done(selected_list, &app.pool).await
+                    && let Err(e) = ItemsComponent::cycle_sort_mode(selected_list, &app.pool).await
                 {
-                    eprintln!("Failed to toggle item: {}", e);
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to change sort mode: {}",
+                        e
+                    )));
+                }
+            }
+            // Clear completed items
+            (KeyCode::Char('X'), KeyModifiers::SHIFT) => {
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut() {
+                    if let Err(e) = ItemsComponent::clear_done_items(selected_list, &app.pool).await
+                    {
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to clear completed items: {}",
+                            e
+                        )));
+                    } else if let Err(e) = app.lists_component.sync_items(&app.pool).await {
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to refresh items: {}",
+                            e
+                        )));
+                    }
                 }
             }
             (KeyCode::Char('s'), KeyModifiers::ALT) => {
@@ -58,7 +162,10 @@ impl EventHandler {
                     ListsComponent::move_selected_list_down(&mut app.lists_component, &app.pool)
                         .await
                 {
-                    eprintln!("Failed to move list down: {}", e);
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to move list down: {}",
+                        e
+                    )));
                 }
             }
             (KeyCode::Char('w'), KeyModifiers::ALT) => {
@@ -66,7 +173,10 @@ impl EventHandler {
                 if let Err(e) =
                     ListsComponent::move_selected_list_up(&mut app.lists_component, &app.pool).await
                 {
-                    eprintln!("Failed to move list up: {}", e);
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to move list up: {}",
+                        e
+                    )));
                 }
             }
             (KeyCode::Up, KeyModifiers::ALT) => {
@@ -75,7 +185,10 @@ impl EventHandler {
                     && let Err(e) =
                         ItemsComponent::move_selected_item_up(selected_list, &app.pool).await
                 {
-                    eprintln!("Failed to move item up: {}", e);
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to move item up: {}",
+                        e
+                    )));
                 }
             }
             (KeyCode::Down, KeyModifiers::ALT) => {
@@ -84,7 +197,70 @@ impl EventHandler {
                     && let Err(e) =
                         ItemsComponent::move_selected_item_down(selected_list, &app.pool).await
                 {
-                    eprintln!("Failed to move item down: {}", e);
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to move item down: {}",
+                        e
+                    )));
+                }
+            }
+            (KeyCode::Char('t'), KeyModifiers::ALT) => {
+                // Alt+t: Move selected item to the top
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut()
+                    && let Err(e) =
+                        ItemsComponent::move_selected_item_to_top(selected_list, &app.pool).await
+                {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to move item to the top: {}",
+                        e
+                    )));
+                }
+            }
+            (KeyCode::Char('b'), KeyModifiers::ALT) => {
+                // Alt+b: Move selected item to the bottom
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut()
+                    && let Err(e) =
+                        ItemsComponent::move_selected_item_to_bottom(selected_list, &app.pool).await
+                {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to move item to the bottom: {}",
+                        e
+                    )));
+                }
+            }
+            (KeyCode::Char('T'), modifiers)
+                if modifiers == KeyModifiers::ALT | KeyModifiers::SHIFT =>
+            {
+                // Alt+T: Move selected list to the top
+                if let Err(e) =
+                    ListsComponent::move_selected_list_to_top(&mut app.lists_component, &app.pool)
+                        .await
+                {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to move list to the top: {}",
+                        e
+                    )));
+                }
+            }
+            (KeyCode::Char('B'), modifiers)
+                if modifiers == KeyModifiers::ALT | KeyModifiers::SHIFT =>
+            {
+                // Alt+B: Move selected list to the bottom
+                if let Err(e) = ListsComponent::move_selected_list_to_bottom(
+                    &mut app.lists_component,
+                    &app.pool,
+                )
+                .await
+                {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to move list to the bottom: {}",
+                        e
+                    )));
+                }
+            }
+            // Grab the selected item to move it with the cursor
+            (KeyCode::Char(' '), KeyModifiers::NONE) => {
+                if let Err(e) = app.enter_grab_screen().await {
+                    app.status = Some(StatusMessage::error(format!("Failed to grab item: {}", e)));
                 }
             }
             (KeyCode::Down, KeyModifiers::NONE) => {
@@ -152,6 +328,13 @@ impl EventHandler {
         }
     }
 
+    /// Handle key press from user in the item keys pop-up
+    pub async fn handle_item_keys_screen_key(app: &mut App, key: KeyEvent) {
+        if let KeyCode::Esc | KeyCode::Char('?') = key.code {
+            app.exit_item_keys_screen();
+        }
+    }
+
     /// Handle key press from user in add list screen
     pub async fn handle_add_or_modify_list_screen_key(app: &mut App, key: KeyEvent) {
         match (key.code, key.modifiers) {
@@ -177,7 +360,10 @@ impl EventHandler {
                         )
                         .await
                         {
-                            eprintln!("Failed to update list: {}", e);
+                            app.status = Some(StatusMessage::error(format!(
+                                "Failed to update list: {}",
+                                e
+                            )));
                         } else {
                             app.current_screen = CurrentScreen::Main;
                             app.input_state.clear();
@@ -186,7 +372,10 @@ impl EventHandler {
                         ListsComponent::create_list(&mut app.lists_component, list_name, &app.pool)
                             .await
                     {
-                        eprintln!("Failed to create list: {}", e);
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to create list: {}",
+                            e
+                        )));
                     } else {
                         app.lists_component.select_last();
                         app.current_screen = CurrentScreen::Main;
@@ -213,33 +402,302 @@ impl EventHandler {
             }
             (KeyCode::Enter, KeyModifiers::NONE) => {
                 let item_name = app.input_state.get_text().to_string();
-                if !item_name.trim().is_empty()
-                    && let Some(selected_list) = app.lists_component.get_selected_list_mut()
-                {
-                    if app.input_state.is_modifying {
+                if item_name.trim().is_empty() {
+                    return;
+                }
+
+                // New items are typed in the quick-add syntax, and need a name besides the tokens
+                let quick_add = if app.input_state.is_modifying {
+                    None
+                } else {
+                    let quick_add = QuickAdd::parse(&item_name);
+                    if quick_add.name.is_empty() {
+                        return;
+                    }
+                    Some(quick_add)
+                };
+
+                // The list named with ^list only gets selected once the item is in it
+                let target = match &quick_add {
+                    Some(QuickAdd {
+                        list: Some(list_name),
+                        ..
+                    }) => match app.lists_component.position_of_list_by_name(list_name) {
+                        Some(i) => Some(i),
+                        None => {
+                            app.status = Some(StatusMessage::error(format!(
+                                "Failed to create item: no list named '{}'",
+                                list_name
+                            )));
+                            return;
+                        }
+                    },
+                    _ => app.lists_component.selected(),
+                };
+
+                if let Some(i) = target {
+                    let selected_list = &mut app.lists_component.lists[i];
+                    if let Some(quick_add) = quick_add {
                         if let Err(e) =
-                            ItemsComponent::update_item(selected_list, item_name, &app.pool).await
+                            ItemsComponent::create_item(selected_list, &quick_add, &app.pool).await
                         {
-                            eprintln!("Failed to update item: {}", e);
+                            app.status = Some(StatusMessage::error(format!(
+                                "Failed to create item: {}",
+                                e
+                            )));
                         } else {
+                            ItemsComponent::select_last_item(selected_list);
+                            app.lists_component.list_state.select(Some(i));
                             app.current_screen = CurrentScreen::Main;
                             app.input_state.clear();
                         }
                     } else if let Err(e) =
-                        ItemsComponent::create_item(selected_list, item_name, &app.pool).await
+                        ItemsComponent::update_item(selected_list, item_name, &app.pool).await
                     {
-                        eprintln!("Failed to create item: {}", e);
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to update item: {}",
+                            e
+                        )));
                     } else {
-                        ItemsComponent::select_last_item(selected_list);
                         app.current_screen = CurrentScreen::Main;
                         app.input_state.clear();
                     }
+
+                    // Show the change in the smart lists too
+                    if let Err(e) = app.lists_component.sync_items(&app.pool).await {
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to refresh items: {}",
+                            e
+                        )));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle key press from user in the filter screen
+    pub async fn handle_filter_screen_key(app: &mut App, key: KeyEvent) {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE) => app.exit_filter_screen(),
+            (KeyCode::Backspace, KeyModifiers::NONE) => app.input_state.remove_char_before_cursor(),
+            (KeyCode::Delete, KeyModifiers::NONE) => app.input_state.delete_char_after_cursor(),
+            (KeyCode::Left, KeyModifiers::NONE) => app.input_state.move_cursor_left(),
+            (KeyCode::Right, KeyModifiers::NONE) => app.input_state.move_cursor_right(),
+            (KeyCode::Char('a'), KeyModifiers::CONTROL) => app.input_state.move_cursor_to_start(),
+            (KeyCode::Char('e'), KeyModifiers::CONTROL) => app.input_state.move_cursor_to_end(),
+            (KeyCode::Char(value), KeyModifiers::SHIFT | KeyModifiers::NONE) => {
+                app.input_state.add_char(value)
+            }
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                // Invalid queries keep the pop-up open, which shows what is wrong;
+                // anything failing after that is reported once it has closed
+                if let Err(e) = app.apply_filter().await
+                    && app.current_screen != CurrentScreen::Filter
+                {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to filter items: {}",
+                        e
+                    )));
                 }
             }
             _ => {}
         }
     }
 
+    /// Handle key press from user in the due date screen
+    pub async fn handle_due_date_screen_key(app: &mut App, key: KeyEvent) {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, KeyModifiers::NONE) => app.exit_due_date_screen(),
+            (KeyCode::Backspace, KeyModifiers::NONE) => app.input_state.remove_char_before_cursor(),
+            (KeyCode::Delete, KeyModifiers::NONE) => app.input_state.delete_char_after_cursor(),
+            (KeyCode::Left, KeyModifiers::NONE) => app.input_state.move_cursor_left(),
+            (KeyCode::Right, KeyModifiers::NONE) => app.input_state.move_cursor_right(),
+            (KeyCode::Char('a'), KeyModifiers::CONTROL) => app.input_state.move_cursor_to_start(),
+            (KeyCode::Char('e'), KeyModifiers::CONTROL) => app.input_state.move_cursor_to_end(),
+            (KeyCode::Char(value), KeyModifiers::SHIFT | KeyModifiers::NONE) => {
+                app.input_state.add_char(value)
+            }
+            (KeyCode::Enter, KeyModifiers::NONE) => {
+                // Invalid dates keep the pop-up open, which shows what is wrong;
+                // anything failing after that is reported once it has closed
+                if let Err(e) = app.apply_due_date().await
+                    && app.current_screen != CurrentScreen::DueDate
+                {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to set due date: {}",
+                        e
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle key press from user while an item is grabbed
+    pub async fn handle_grab_screen_key(app: &mut App, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                if let Err(e) = app.cancel_grab().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to refresh items: {}",
+                        e
+                    )));
+                }
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                if let Err(e) = app.drop_grabbed_item().await {
+                    app.status = Some(StatusMessage::error(format!("Failed to move item: {}", e)));
+                }
+            }
+            KeyCode::Up => {
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut() {
+                    ItemsComponent::carry_grabbed_item_up(selected_list);
+                }
+            }
+            KeyCode::Down => {
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut() {
+                    ItemsComponent::carry_grabbed_item_down(selected_list);
+                }
+            }
+            KeyCode::Char('t') => {
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut() {
+                    ItemsComponent::carry_grabbed_item_to_top(selected_list);
+                }
+            }
+            KeyCode::Char('b') => {
+                if let Some(selected_list) = app.lists_component.get_selected_list_mut() {
+                    ItemsComponent::carry_grabbed_item_to_bottom(selected_list);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle key press from user in the archive view
+    pub async fn handle_archive_screen_key(app: &mut App, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => app.exit_archive_screen(),
+            KeyCode::Up => app.archive_component.select_previous(),
+            KeyCode::Down => app.archive_component.select_next(),
+            KeyCode::Enter => {
+                if let Err(e) = app.restore_selected_archived_list().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to restore list: {}",
+                        e
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle key press from user in the trash view
+    pub async fn handle_trash_screen_key(app: &mut App, key: KeyEvent) {
+        // Purging can't be undone, so it waits for a yes
+        if app.trash_component.pending_purge.is_some() {
+            if key.code == KeyCode::Char('y') {
+                if let Err(e) = app.trash_component.confirm_purge(&app.pool).await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to purge from trash: {}",
+                        e
+                    )));
+                }
+            } else {
+                app.trash_component.cancel_purge();
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc => app.exit_trash_screen(),
+            KeyCode::Up => app.trash_component.select_previous(),
+            KeyCode::Down => app.trash_component.select_next(),
+            KeyCode::Enter => {
+                if let Err(e) = app.restore_selected_trash_entry().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to restore from trash: {}",
+                        e
+                    )));
+                }
+            }
+            KeyCode::Char('d') => app.trash_component.request_purge_selected(),
+            KeyCode::Char('E') => app.trash_component.request_empty(),
+            _ => {}
+        }
+    }
+
+    /// Handle key press from user in the agenda view
+    pub async fn handle_agenda_screen_key(app: &mut App, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                if let Err(e) = app.exit_agenda_screen().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to refresh items: {}",
+                        e
+                    )));
+                }
+            }
+            KeyCode::Up => app.agenda_component.select_previous(),
+            KeyCode::Down => app.agenda_component.select_next(),
+            KeyCode::Char(' ') => {
+                if let Err(e) = app.toggle_selected_agenda_item_done().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to toggle item: {}",
+                        e
+                    )));
+                }
+            }
+            KeyCode::Enter => {
+                if let Err(e) = app.jump_to_selected_agenda_item().await {
+                    app.status = Some(StatusMessage::error(format!("Failed to open list: {}", e)));
+                }
+            }
+            KeyCode::Char('a') => {
+                if let Err(e) = app.toggle_agenda_all_dbs().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to load agenda: {}",
+                        e
+                    )));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handle key press from user in the board view
+    pub async fn handle_board_screen_key(app: &mut App, key: KeyEvent) {
+        match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => {
+                if let Err(e) = app.exit_board_screen().await {
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to refresh items: {}",
+                        e
+                    )));
+                }
+            }
+            (KeyCode::Left, KeyModifiers::ALT) => {
+                if let Err(e) = app.board_component.move_selected_card_left(&app.pool).await {
+                    app.status = Some(StatusMessage::error(format!("Failed to move card: {}", e)));
+                }
+            }
+            (KeyCode::Right, KeyModifiers::ALT) => {
+                if let Err(e) = app
+                    .board_component
+                    .move_selected_card_right(&app.pool)
+                    .await
+                {
+                    app.status = Some(StatusMessage::error(format!("Failed to move card: {}", e)));
+                }
+            }
+            (KeyCode::Left, _) => app.board_component.select_previous_column(),
+            (KeyCode::Right, _) => app.board_component.select_next_column(),
+            (KeyCode::Up, _) => app.board_component.select_previous_card(),
+            (KeyCode::Down, _) => app.board_component.select_next_card(),
+            _ => {}
+        }
+    }
+
     /// Handle change of db
     pub async fn handle_change_db_screen_key(app: &mut App, key: KeyEvent) {
         match key.code {
@@ -248,24 +706,31 @@ impl EventHandler {
             KeyCode::Down => app.select_next_db(),
             KeyCode::Enter => {
                 if let Err(e) = app.switch_to_selected_db().await {
-                    eprintln!("Failed to switch database: {}", e);
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to switch database: {}",
+                        e
+                    )));
                 }
             }
             KeyCode::Char('A') => app.enter_add_db_screen(),
             KeyCode::Char('S') => {
                 // Set selected database as default
                 if let Err(e) = app.set_selected_db_as_default().await {
-                    eprintln!("Failed to set database as default: {}", e);
+                    app.status = Some(StatusMessage::error(format!(
+                        "Failed to set database as default: {}",
+                        e
+                    )));
                 }
             }
             _ => {}
         }
     }
 
-    /// Handle key press from user in add database screen
-    pub async fn handle_add_db_screen_key(app: &mut App, key: KeyEvent) {
+    /// Handle key press from user in add or register database screen
+    pub async fn handle_add_or_register_db_screen_key(app: &mut App, key: KeyEvent) {
         match (key.code, key.modifiers) {
             (KeyCode::Esc, KeyModifiers::NONE) => app.exit_add_db_without_saving(),
+            (KeyCode::Tab, KeyModifiers::NONE) => app.toggle_add_or_register_db_screen(),
             (KeyCode::Backspace, KeyModifiers::NONE) => app.input_state.remove_char_before_cursor(),
             (KeyCode::Delete, KeyModifiers::NONE) => app.input_state.delete_char_after_cursor(),
             (KeyCode::Char(value), KeyModifiers::SHIFT | KeyModifiers::NONE) => {
@@ -276,10 +741,19 @@ impl EventHandler {
             (KeyCode::Char('a'), KeyModifiers::CONTROL) => app.input_state.move_cursor_to_start(),
             (KeyCode::Char('e'), KeyModifiers::CONTROL) => app.input_state.move_cursor_to_end(),
             (KeyCode::Enter, KeyModifiers::NONE) => {
-                let db_name = app.input_state.get_text().to_string();
-                if !db_name.trim().is_empty() {
-                    if let Err(e) = app.create_new_database(db_name, false).await {
-                        eprintln!("Failed to create database: {}", e);
+                let input = app.input_state.get_text().to_string();
+                if !input.trim().is_empty() {
+                    let result = if app.current_screen == CurrentScreen::RegisterDB {
+                        app.register_existing_database(input, None).await
+                    } else {
+                        app.create_new_database(input, false).await
+                    };
+
+                    if let Err(e) = result {
+                        app.status = Some(StatusMessage::error(format!(
+                            "Failed to add database: {}",
+                            e
+                        )));
                     } else {
                         app.current_screen = CurrentScreen::ChangeDB;
                         app.input_state.clear();
@@ -1,5 +1,9 @@
 pub mod events;
+pub mod session;
+pub mod startup;
 pub mod state;
+pub mod tasks;
 
 pub use events::EventHandler;
+pub use startup::{StartupError, StartupOutcome, StartupScreen};
 pub use state::{App, CurrentScreen};
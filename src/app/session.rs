@@ -0,0 +1,56 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE: &str = "state.toml";
+
+/// Selected list and item to restore when reopening a database
+#[derive(Deserialize, Serialize, Clone, Default, Debug, PartialEq)]
+pub struct DbSelection {
+    pub list_id: Option<i64>,
+    pub item_id: Option<i64>,
+}
+
+/// State of the TUI remembered across launches
+#[derive(Deserialize, Serialize, Clone, Default, Debug, PartialEq)]
+pub struct SessionState {
+    /// Name of the database that was active when the TUI was closed
+    pub last_db: Option<String>,
+    /// Last selection in each database, keyed by connection string
+    #[serde(default)]
+    pub selections: BTreeMap<String, DbSelection>,
+}
+
+impl SessionState {
+    /// Path of the state file in the standard data directory
+    pub fn default_path() -> Result<PathBuf> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not find data directory"))?
+            .join("judo");
+        Ok(data_dir.join(STATE_FILE))
+    }
+
+    /// Read the state file, starting afresh if there is none
+    pub fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Write the state file, creating its directory if needed
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| "Failed to create data directory")?;
+        }
+
+        let content =
+            toml::to_string_pretty(&self).with_context(|| "Failed to serialize state file")?;
+        fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
@@ -0,0 +1,334 @@
+use crate::app::App;
+use crate::db::config::Config;
+use crate::ui::layout::AppLayout;
+use anyhow::Result;
+use crossterm::event::{EventStream, KeyCode, KeyEvent};
+use futures::StreamExt;
+use ratatui::DefaultTerminal;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{
+    Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph,
+    StatefulWidget, Widget, Wrap,
+};
+use std::fmt;
+use std::path::PathBuf;
+
+/// Part of the startup that failed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StartupStage {
+    /// Locating or reading judo.toml, or picking the database in it
+    Config,
+    /// Opening the database or reading its lists
+    Database,
+}
+
+/// Why the app could not start, with what was read before it failed
+#[derive(Debug)]
+pub struct StartupError {
+    pub stage: StartupStage,
+    pub error: anyhow::Error,
+    /// Path of judo.toml, if it could be located
+    pub config_path: Option<PathBuf>,
+    /// Config as read from judo.toml, if it could be read
+    pub config: Option<Config>,
+    /// Name of the database that failed to open
+    pub db_name: Option<String>,
+}
+
+impl StartupError {
+    pub fn config(
+        error: anyhow::Error,
+        config_path: Option<PathBuf>,
+        config: Option<Config>,
+    ) -> Self {
+        Self {
+            stage: StartupStage::Config,
+            error,
+            config_path,
+            config,
+            db_name: None,
+        }
+    }
+
+    pub fn database(
+        error: anyhow::Error,
+        config_path: PathBuf,
+        config: Config,
+        db_name: String,
+    ) -> Self {
+        Self {
+            stage: StartupStage::Database,
+            error,
+            config_path: Some(config_path),
+            config: Some(config),
+            db_name: Some(db_name),
+        }
+    }
+
+    /// One sentence on what went wrong
+    pub fn headline(&self) -> String {
+        match (self.stage, &self.config_path, &self.db_name) {
+            (StartupStage::Database, _, Some(db_name)) => {
+                format!("Judo could not open the database '{}'", db_name)
+            }
+            (_, Some(config_path), _) => format!(
+                "Judo could not use the configuration in {}",
+                config_path.display()
+            ),
+            _ => "Judo could not locate its configuration".to_string(),
+        }
+    }
+
+    /// Exit code for commands that can't offer a way out
+    pub fn exit_code(&self) -> i32 {
+        match self.stage {
+            StartupStage::Config => exitcode::CONFIG,
+            StartupStage::Database => exitcode::DATAERR,
+        }
+    }
+}
+
+impl fmt::Display for StartupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:#}", self.headline(), self.error)
+    }
+}
+
+impl std::error::Error for StartupError {}
+
+/// Way out offered by the startup error screen
+#[derive(Debug, Clone, PartialEq)]
+pub enum StartupChoice {
+    /// Open another database of the config for this session
+    OpenDb(String),
+    /// Set judo.toml aside and start from a default one
+    RecreateConfig,
+    Exit,
+}
+
+impl StartupChoice {
+    fn label(&self) -> String {
+        match self {
+            Self::OpenDb(name) => format!("Open database '{}' instead", name),
+            Self::RecreateConfig => "Recreate judo.toml, keeping a backup of the old one".into(),
+            Self::Exit => "Exit".into(),
+        }
+    }
+}
+
+/// How the startup error screen was left
+pub enum StartupOutcome {
+    Started(Box<App>),
+    Exit,
+}
+
+/// Screen explaining why the app could not start, and offering ways out
+pub struct StartupScreen {
+    pub error: StartupError,
+    pub choices: Vec<StartupChoice>,
+    pub selected: usize,
+}
+
+impl StartupScreen {
+    pub fn new(error: StartupError) -> Self {
+        let mut choices = Vec::new();
+
+        // Any other database the config knows of
+        if let Some(config) = &error.config {
+            choices.extend(
+                config
+                    .dbs
+                    .iter()
+                    .filter(|db| Some(&db.name) != error.db_name.as_ref())
+                    .map(|db| StartupChoice::OpenDb(db.name.clone())),
+            );
+        }
+        if error.config_path.is_some() {
+            choices.push(StartupChoice::RecreateConfig);
+        }
+        choices.push(StartupChoice::Exit);
+
+        Self {
+            error,
+            choices,
+            selected: 0,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(self.choices.len() - 1);
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Handle a key, returning how the screen was left if it was
+    pub async fn handle_key_event(&mut self, key: KeyEvent) -> Option<StartupOutcome> {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.select_next(),
+            KeyCode::Esc | KeyCode::Char('q') => return Some(StartupOutcome::Exit),
+            KeyCode::Enter => return self.choose().await,
+            _ => {}
+        }
+        None
+    }
+
+    /// Go with the selected choice
+    ///
+    /// If that fails too, the screen explains the new problem instead.
+    pub async fn choose(&mut self) -> Option<StartupOutcome> {
+        let result = match self.choices[self.selected].clone() {
+            StartupChoice::Exit => return Some(StartupOutcome::Exit),
+            StartupChoice::OpenDb(name) => self.open_db(name).await,
+            StartupChoice::RecreateConfig => self.recreate_config().await,
+        };
+
+        match result {
+            Ok(app) => Some(StartupOutcome::Started(Box::new(app))),
+            Err(error) => {
+                *self = Self::new(error);
+                None
+            }
+        }
+    }
+
+    async fn open_db(&self, name: String) -> Result<App, StartupError> {
+        let (Some(config_path), Some(global_config)) =
+            (&self.error.config_path, &self.error.config)
+        else {
+            unreachable!("Databases are only offered once the config was read");
+        };
+
+        let mut config = global_config.clone();
+        config.use_db(name.clone()).map_err(|e| {
+            StartupError::config(e, Some(config_path.clone()), Some(global_config.clone()))
+        })?;
+        App::open(config_path.clone(), config).await.map_err(|e| {
+            StartupError::database(e, config_path.clone(), global_config.clone(), name)
+        })
+    }
+
+    async fn recreate_config(&self) -> Result<App, StartupError> {
+        let Some(config_path) = &self.error.config_path else {
+            unreachable!("Recreating the config is only offered once it was located");
+        };
+
+        let config = Config::recreate(config_path)
+            .map_err(|e| StartupError::config(e, Some(config_path.clone()), None))?;
+        let db_name = config.default.clone();
+        App::open(config_path.clone(), config.clone())
+            .await
+            .map_err(|e| StartupError::database(e, config_path.clone(), config, db_name))
+    }
+
+    /// Show the screen until the user picks a way out that works, or leaves
+    pub async fn run(mut self, terminal: &mut DefaultTerminal) -> Result<StartupOutcome> {
+        let mut events = EventStream::new();
+        loop {
+            terminal.draw(|frame| frame.render_widget(&self, frame.area()))?;
+
+            match events.next().await {
+                Some(event) => {
+                    if let Some(key) = event?.as_key_press_event()
+                        && let Some(outcome) = self.handle_key_event(key).await
+                    {
+                        return Ok(outcome);
+                    }
+                }
+                // The terminal is gone
+                None => return Ok(StartupOutcome::Exit),
+            }
+        }
+    }
+}
+
+impl Widget for &StartupScreen {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // The config may be what failed to load, so stick to the default theme
+        let config = Config::default();
+        let hl = config.highlight();
+
+        AppLayout::render_background(config.clone(), area, buf);
+
+        let command_hints = Line::from(vec![
+            Span::raw(" "),
+            Span::styled(" ↑↓ ", Style::default()),
+            Span::styled("[Enter]", Style::default().fg(hl)),
+            Span::styled(" [Esc]", Style::default().fg(hl)),
+            Span::raw(" "),
+        ]);
+
+        // Explanation first, the ways out below it
+        let causes: Vec<Line> = self
+            .error
+            .error
+            .chain()
+            .map(|cause| Line::from(format!("  {}", cause)))
+            .collect();
+        let explanation_height = causes.len() as u16 + 4;
+        let choices_height = self.choices.len() as u16 + 4;
+
+        let [popup_area] = Layout::horizontal([Constraint::Percentage(70)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [popup_area] =
+            Layout::vertical([Constraint::Length(explanation_height + choices_height)])
+                .flex(Flex::Center)
+                .areas(popup_area);
+        let [explanation_area, choices_area] = Layout::vertical([
+            Constraint::Length(explanation_height),
+            Constraint::Length(choices_height),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+        Block::default()
+            .style(config.colours.popup_style())
+            .render(popup_area, buf);
+
+        let mut text = Text::from(Line::styled(self.error.headline(), Style::default().fg(hl)));
+        text.push_line(Line::raw(""));
+        text.extend(causes);
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::new()
+                    .padding(Padding::horizontal(1))
+                    .title("  S T A R T U P   E R R O R  ")
+                    .title_style(config.colours.title_style())
+                    .borders(Borders::ALL)
+                    .border_style(config.colours.border_style())
+                    .border_type(BorderType::Rounded),
+            )
+            .render(explanation_area, buf);
+
+        let items: Vec<ListItem> = self
+            .choices
+            .iter()
+            .map(|choice| ListItem::from(choice.label()))
+            .collect();
+        let mut list_state = ListState::default();
+        list_state.select(Some(self.selected));
+
+        let list = List::new(items)
+            .block(
+                Block::new()
+                    .padding(Padding::new(1, 1, 1, 1))
+                    .title(" What now? ")
+                    .title_style(config.colours.title_style())
+                    .title_bottom(command_hints)
+                    .borders(Borders::ALL)
+                    .border_style(config.colours.border_style())
+                    .border_type(BorderType::Rounded),
+            )
+            .highlight_symbol(" ▸ ")
+            .highlight_style(config.colours.selected_style())
+            .highlight_spacing(ratatui::widgets::HighlightSpacing::Always);
+        StatefulWidget::render(list, choices_area, buf, &mut list_state);
+    }
+}
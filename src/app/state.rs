@@ -1,20 +1,38 @@
 use crate::app::events::EventHandler;
-use crate::db::config::{Config, DBConfig};
-use crate::db::connections::init_db;
-use crate::db::models::{TodoList, UIList};
+use crate::app::session::{DbSelection, SessionState};
+use crate::app::startup::StartupError;
+use crate::app::tasks::{BackgroundTasks, TaskResult};
+use crate::db::config::{Config, DBConfig, SqliteSettings, resolve_path};
+use crate::db::connections::{
+    ChangeWatcher, PoolRegistry, init_db, init_db_with, open_existing_db,
+};
+use crate::db::dates::parse_due_date;
+use crate::db::models::{TodoList, TrashEntry, UIList};
+use crate::db::project::discover_project_db;
+use crate::db::query::Query;
 use crate::ui::components::{
-    AddDBPopUp, AddItemPopUp, AddListPopUp, ChangeDBPopUp, DBSelector, InputState, ItemsComponent,
-    ListsComponent, Logo, ModifyItemPopUp, ModifyListPopUp,
+    AddDBPopUp, AddItemPopUp, AddListPopUp, AgendaComponent, ArchiveComponent, BoardComponent,
+    ChangeDBPopUp, DBSelector, DueDatePopUp, FilterPopUp, InputState, ItemKeysPopUp,
+    ItemsComponent, ListsComponent, Logo, ModifyItemPopUp, ModifyListPopUp, RegisterDBPopUp,
+    StatusBar, StatusMessage, TrashComponent,
 };
 use crate::ui::cursor::CursorState;
 use crate::ui::layout::AppLayout;
 use anyhow::{Context, Result};
-use crossterm::event::{self, KeyEvent};
+use chrono::{Local, NaiveDate};
+use crossterm::event::{EventStream, KeyEvent};
+use futures::StreamExt;
 use ratatui::DefaultTerminal;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::widgets::Widget;
 use sqlx::SqlitePool;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::time::MissedTickBehavior;
+
+/// How often the TUI wakes up without input, e.g. to look for changes made elsewhere
+const TICK_INTERVAL: Duration = Duration::from_millis(500);
 
 /// Enum representing the different screens in the application
 #[derive(Debug, Clone, PartialEq)]
@@ -33,24 +51,66 @@ pub enum CurrentScreen {
     ChangeDB,
     /// Pop-up for adding a new database
     AddDB,
+    /// Pop-up for registering an existing database file
+    RegisterDB,
+    /// View of the archived lists
+    Archive,
+    /// View of the deleted lists and items
+    Trash,
+    /// View of the pending items grouped by due date
+    Agenda,
+    /// Kanban board of the selected list
+    Board,
+    /// Pop-up for filtering the items with a query
+    Filter,
+    /// Pop-up for setting the due date of the selected item
+    DueDate,
+    /// Selected item following the cursor until it is dropped
+    Grab,
+    /// Pop-up listing the keys of the items panel
+    ItemKeys,
 }
 
 /// Main application state
 pub struct App {
     /// Configuration of available databases
     pub config: Config,
+    /// Path of the judo.toml file the configuration was read from
+    pub config_path: PathBuf,
     /// Config of currently selected database
     pub current_db_config: DBConfig,
     /// Current active screen (Main, AddList, ModifyList, or AddItem)
     pub current_screen: CurrentScreen,
     /// Database connection pool
     pub pool: SqlitePool,
+    /// Pools of all the databases opened so far, the current one included
+    pub pools: PoolRegistry,
+    /// Watches the current database for changes made outside the TUI, once it runs
+    pub change_watcher: Option<ChangeWatcher>,
     /// Lists component for managing todo lists
     pub lists_component: ListsComponent,
+    /// Archived lists shown in the archive view
+    pub archive_component: ArchiveComponent,
+    /// Deleted lists and items shown in the trash view
+    pub trash_component: TrashComponent,
+    /// Pending items shown in the agenda view
+    pub agenda_component: AgendaComponent,
+    /// Kanban board of the selected list
+    pub board_component: BoardComponent,
     /// State of user-provided input
     pub input_state: InputState,
     /// Selected database index for DB selector
     pub selected_db_index: usize,
+    /// State remembered across TUI sessions
+    pub session_state: SessionState,
+    /// File the session is remembered in, if there is anywhere to keep it
+    pub session_path: Option<PathBuf>,
+    /// Work running in the background, such as purging old trash
+    pub tasks: BackgroundTasks,
+    /// Message shown at the bottom until it expires
+    pub status: Option<StatusMessage>,
+    /// Date the lists were loaded on, to notice when due dates roll over
+    pub today: NaiveDate,
     /// Flag to indicate if the application should exit
     pub exit: bool,
 }
@@ -59,18 +119,73 @@ impl App {
     /// Create new app instance
     ///
     /// Initializes the database connection, loads existing lists from the database,
-    /// and sets up the initial UI state.
-    pub async fn new() -> Self {
+    /// and sets up the initial UI state. A config path and a database name can be
+    /// given to override the standard judo.toml and its default database.
+    ///
+    /// With `restore_session`, the database and selection of the previous TUI
+    /// session are brought back; an explicitly requested database or list takes
+    /// their place by leaving it off.
+    ///
+    /// Failures say whether the config or the database is to blame, and carry
+    /// what was read so far, so that the caller can offer a way out.
+    pub async fn new(
+        config_path: Option<String>,
+        db_name: Option<String>,
+        restore_session: bool,
+    ) -> Result<Self, StartupError> {
         // Read the config (creates default if missing)
-        let config = Config::read().expect("Failed to read config file");
+        let config_path = match config_path {
+            Some(path) => resolve_path(&path),
+            None => Config::default_path(),
+        }
+        .map_err(|e| StartupError::config(e, None, None))?;
+        let global_config = Config::read(&config_path)
+            .with_context(|| format!("Failed to read {}", config_path.display()))
+            .map_err(|e| StartupError::config(e, Some(config_path.clone()), None))?;
+
+        let config = Self::apply_overrides(global_config.clone(), db_name).map_err(|e| {
+            StartupError::config(e, Some(config_path.clone()), Some(global_config.clone()))
+        })?;
+
+        let db_name = config.default.clone();
+        let mut app = Self::open(config_path.clone(), config)
+            .await
+            .map_err(|e| StartupError::database(e, config_path, global_config, db_name))?;
+        if restore_session {
+            app.resume_session().await;
+        }
+        Ok(app)
+    }
+
+    /// Point the config at the project database, or at the requested one
+    fn apply_overrides(mut config: Config, db_name: Option<String>) -> Result<Config> {
+        // A project-local database overrides the default for this session
+        let cwd = std::env::current_dir().with_context(|| "Failed to read current directory")?;
+        if let Some(project_db) =
+            discover_project_db(&cwd).with_context(|| "Failed to read project configuration")?
+        {
+            config.use_project_db(project_db);
+        }
+
+        // An explicitly requested database wins over everything else
+        if let Some(db_name) = db_name {
+            config.use_db(db_name)?;
+        }
+
+        // Make sure there is a database to open
+        config.get_default()?;
+        Ok(config)
+    }
 
+    /// Create an app on the default database of an already read config
+    pub async fn open(config_path: PathBuf, config: Config) -> Result<Self> {
         // Extract the default db and its connection string
-        let default_db_config = config
-            .get_default()
-            .expect("Couldn't fetch default database");
-        let pool = init_db(&default_db_config.connection_str)
+        let default_db_config = config.get_default()?;
+        let pool = init_db_with(&default_db_config.connection_str, &default_db_config.sqlite)
             .await
-            .expect("Failed to connect to database");
+            .with_context(|| format!("Failed to open database '{}'", default_db_config.name))?;
+        let pools = PoolRegistry::new();
+        pools.insert(&default_db_config, pool.clone());
 
         // Start from main screen
         let current_screen = CurrentScreen::Main;
@@ -80,32 +195,71 @@ impl App {
         lists_component
             .load_lists(&pool)
             .await
-            .expect("Failed to read lists");
+            .with_context(|| "Failed to read lists")?;
 
-        Self {
+        Ok(Self {
             config,
+            config_path,
             current_db_config: default_db_config,
             current_screen,
             pool,
+            pools,
+            change_watcher: None,
             lists_component,
+            archive_component: ArchiveComponent::new(),
+            trash_component: TrashComponent::new(),
+            agenda_component: AgendaComponent::new(),
+            board_component: BoardComponent::new(),
             input_state: InputState::new(),
             selected_db_index: 0,
+            session_state: SessionState::default(),
+            session_path: SessionState::default_path().ok(),
+            tasks: BackgroundTasks::new(),
+            status: None,
+            today: Local::now().date_naive(),
             exit: false,
-        }
+        })
     }
 
     /// Run the application
     ///
     /// Main event loop that handles terminal drawing and user input.
     /// Continues until the user exits the application.
-    pub async fn run(mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+    pub async fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        // Pick up what was committed between loading the lists and watching for changes
+        if let Err(e) = self.watch_current_db().await {
+            self.status = Some(StatusMessage::error(format!(
+                "Failed to watch for changes: {}",
+                e
+            )));
+        } else if let Err(e) = self.lists_component.reload(&self.pool).await {
+            self.status = Some(StatusMessage::error(format!(
+                "Failed to reload lists: {}",
+                e
+            )));
+        }
+
+        let mut events = EventStream::new();
+        let mut ticks = tokio::time::interval(TICK_INTERVAL);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
         while !self.exit {
             // Draw the current state of the application
-            terminal.draw(|frame| frame.render_widget(&mut self, frame.area()))?;
+            terminal.draw(|frame| frame.render_widget(&mut *self, frame.area()))?;
 
-            // Handle keyboard input based on current screen
-            if let Some(key) = event::read()?.as_key_press_event() {
-                self.handle_key_event(key).await;
+            // Wait for a key, a tick or a background task, whichever comes first
+            tokio::select! {
+                event = events.next() => match event {
+                    Some(event) => {
+                        if let Some(key) = event?.as_key_press_event() {
+                            self.handle_key_event(key).await;
+                        }
+                    }
+                    // The terminal is gone
+                    None => self.exit = true,
+                },
+                _ = ticks.tick() => self.on_tick().await,
+                result = self.tasks.next() => self.handle_task_result(result),
             }
         }
         Ok(())
@@ -136,6 +290,7 @@ impl App {
         let new_db_config = DBConfig {
             name: db_name.clone(),
             connection_str: connection_str.clone(),
+            sqlite: SqliteSettings::default(),
         };
 
         // Initialize the new database (this creates the file and runs migrations)
@@ -148,17 +303,66 @@ impl App {
 
         // Set as default if requested
         if set_as_default {
-            self.config.default = db_name.clone();
+            self.config.set_default(db_name.clone());
         }
 
         // Write updated config to file
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?
-            .join("judo");
-        let config_path = config_dir.join("judo.toml");
+        self.config
+            .write(&self.config_path)
+            .with_context(|| "Failed to save config")?;
+
+        // Update selected index to point to the new database
+        self.selected_db_index = self.config.dbs.len() - 1;
+
+        Ok(())
+    }
 
+    /// Register an existing SQLite file as a database
+    ///
+    /// The path may be relative or start with `~`. When no name is given,
+    /// the file stem is used (e.g. `./todo.db` becomes `todo`).
+    pub async fn register_existing_database(
+        &mut self,
+        path: String,
+        db_name: Option<String>,
+    ) -> Result<()> {
+        let path = resolve_path(&path)?;
+        if !path.is_file() {
+            anyhow::bail!("No database file found at {}", path.display());
+        }
+
+        // Derive the name from the file when not provided
+        let db_name = match db_name {
+            Some(name) => name,
+            None => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .ok_or_else(|| anyhow::anyhow!("Could not derive a name from the path"))?,
+        };
+
+        if self.config.has_db(&db_name) {
+            anyhow::bail!("A database called '{}' already exists", db_name);
+        }
+
+        // Create connection string (only SQLite is admissible)
+        let db_config = DBConfig {
+            name: db_name,
+            connection_str: format!("sqlite:{}", path.display()),
+            sqlite: SqliteSettings::default(),
+        };
+
+        // Check the schema and bring it up to date before registering it
+        let pool = open_existing_db(&db_config.connection_str, &db_config.sqlite)
+            .await
+            .with_context(|| format!("Failed to register database at {}", path.display()))?;
+        pool.close().await;
+
+        // Add to config
+        self.config.dbs.push(db_config);
+
+        // Write updated config to file
         self.config
-            .write(&config_path)
+            .write(&self.config_path)
             .with_context(|| "Failed to save config")?;
 
         // Update selected index to point to the new database
@@ -168,7 +372,7 @@ impl App {
     }
 
     /// Handle key events and delegate to appropriate handler
-    async fn handle_key_event(&mut self, key: KeyEvent) {
+    pub async fn handle_key_event(&mut self, key: KeyEvent) {
         match self.current_screen {
             CurrentScreen::Main => EventHandler::handle_main_screen_key(self, key).await,
             CurrentScreen::AddList | CurrentScreen::ModifyList => {
@@ -181,7 +385,17 @@ impl App {
                 EventHandler::handle_add_or_modify_item_screen_key(self, key).await
             }
             CurrentScreen::ChangeDB => EventHandler::handle_change_db_screen_key(self, key).await,
-            CurrentScreen::AddDB => EventHandler::handle_add_db_screen_key(self, key).await,
+            CurrentScreen::AddDB | CurrentScreen::RegisterDB => {
+                EventHandler::handle_add_or_register_db_screen_key(self, key).await
+            }
+            CurrentScreen::Archive => EventHandler::handle_archive_screen_key(self, key).await,
+            CurrentScreen::Trash => EventHandler::handle_trash_screen_key(self, key).await,
+            CurrentScreen::Agenda => EventHandler::handle_agenda_screen_key(self, key).await,
+            CurrentScreen::Board => EventHandler::handle_board_screen_key(self, key).await,
+            CurrentScreen::Filter => EventHandler::handle_filter_screen_key(self, key).await,
+            CurrentScreen::DueDate => EventHandler::handle_due_date_screen_key(self, key).await,
+            CurrentScreen::Grab => EventHandler::handle_grab_screen_key(self, key).await,
+            CurrentScreen::ItemKeys => EventHandler::handle_item_keys_screen_key(self, key).await,
         }
     }
 
@@ -203,7 +417,9 @@ impl App {
 
     /// Enter the "Add Item" screen by opening the corresponding pop-up
     pub fn enter_add_item_screen(&mut self) {
-        if self.lists_component.selected().is_some() {
+        if let Some(ui_list) = self.lists_component.get_selected_list()
+            && !ui_list.is_smart()
+        {
             self.input_state = InputState::default();
             self.current_screen = CurrentScreen::AddItem;
         }
@@ -237,6 +453,367 @@ impl App {
         self.input_state.clear();
     }
 
+    /// Enter the "Filter" screen, starting from the current filter
+    pub fn enter_filter_screen(&mut self) {
+        let current_input = self
+            .lists_component
+            .filter
+            .as_ref()
+            .map(Query::to_string)
+            .unwrap_or_default();
+        self.input_state = InputState {
+            cursor_pos: current_input.chars().count(),
+            current_input,
+            is_modifying: false,
+        };
+        self.current_screen = CurrentScreen::Filter;
+    }
+
+    /// Apply the query typed in the "Filter" screen, an empty one showing all items again
+    pub async fn apply_filter(&mut self) -> Result<()> {
+        let query = self
+            .input_state
+            .get_text()
+            .parse::<Query>()
+            .map_err(|e| anyhow::anyhow!(e))?;
+        self.current_screen = CurrentScreen::Main;
+        self.input_state.clear();
+        self.lists_component
+            .set_filter(Some(query), &self.pool)
+            .await
+    }
+
+    /// Exit the Filter screen, keeping the current filter
+    pub fn exit_filter_screen(&mut self) {
+        self.current_screen = CurrentScreen::Main;
+        self.input_state.clear();
+    }
+
+    /// Enter the "Due Date" screen for the selected item, starting from its current due date
+    pub fn enter_due_date_screen(&mut self) {
+        let Some(ui_list) = self.lists_component.get_selected_list() else {
+            return;
+        };
+        let Some(j) = ui_list.item_state.selected() else {
+            return;
+        };
+        let current_input = ui_list.items[j]
+            .item
+            .due_date
+            .map(|due| due.with_timezone(&Local).format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        self.input_state = InputState {
+            cursor_pos: current_input.chars().count(),
+            current_input,
+            is_modifying: false,
+        };
+        self.current_screen = CurrentScreen::DueDate;
+    }
+
+    /// Apply the date typed in the "Due Date" screen, an empty one removing the due date
+    pub async fn apply_due_date(&mut self) -> Result<()> {
+        let input = self.input_state.get_text().trim();
+        let due = if input.is_empty() {
+            None
+        } else {
+            Some(parse_due_date(input).map_err(|e| anyhow::anyhow!(e))?)
+        };
+        self.current_screen = CurrentScreen::Main;
+        self.input_state.clear();
+        if let Some(ui_list) = self.lists_component.get_selected_list_mut() {
+            ItemsComponent::set_selected_item_due_date(ui_list, due, &self.pool).await?;
+        }
+        self.lists_component.sync_items(&self.pool).await
+    }
+
+    /// Exit the Due Date screen, keeping the current due date
+    pub fn exit_due_date_screen(&mut self) {
+        self.current_screen = CurrentScreen::Main;
+        self.input_state.clear();
+    }
+
+    /// Enter the "Item Keys" screen, listing what the keys of the items panel do
+    pub fn enter_item_keys_screen(&mut self) {
+        self.current_screen = CurrentScreen::ItemKeys;
+    }
+
+    /// Exit the Item Keys screen
+    pub fn exit_item_keys_screen(&mut self) {
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    /// Grab the selected item so it follows the cursor until dropped
+    pub async fn enter_grab_screen(&mut self) -> Result<()> {
+        if let Some(ui_list) = self.lists_component.get_selected_list_mut()
+            && ItemsComponent::grab_selected_item(ui_list, &self.pool).await?
+        {
+            self.current_screen = CurrentScreen::Grab;
+        }
+        Ok(())
+    }
+
+    /// Drop the grabbed item where it was carried to
+    pub async fn drop_grabbed_item(&mut self) -> Result<()> {
+        self.current_screen = CurrentScreen::Main;
+        if let Some(ui_list) = self.lists_component.get_selected_list_mut() {
+            ItemsComponent::drop_grabbed_item(ui_list, &self.pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Put the grabbed item back where it was
+    pub async fn cancel_grab(&mut self) -> Result<()> {
+        self.current_screen = CurrentScreen::Main;
+        if let Some(ui_list) = self.lists_component.get_selected_list_mut() {
+            ItemsComponent::cancel_grab(ui_list, &self.pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Enter the archive view, listing the archived lists of the current database
+    pub async fn enter_archive_screen(&mut self) -> Result<()> {
+        self.archive_component
+            .load_lists(&self.pool)
+            .await
+            .with_context(|| "Failed to load archived lists")?;
+        self.current_screen = CurrentScreen::Archive;
+        Ok(())
+    }
+
+    /// Exit the archive view
+    pub fn exit_archive_screen(&mut self) {
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    /// Restore the archived list selected in the archive view
+    pub async fn restore_selected_archived_list(&mut self) -> Result<()> {
+        if let Some(id) = self
+            .archive_component
+            .restore_selected_list(&self.pool)
+            .await?
+        {
+            // Show the restored list among the active ones
+            self.lists_component.load_lists(&self.pool).await?;
+            self.lists_component.select_list_by_id(id);
+        }
+        Ok(())
+    }
+
+    /// Enter the trash view, listing the deleted lists and items of the current database
+    pub async fn enter_trash_screen(&mut self) -> Result<()> {
+        self.trash_component
+            .load_entries(&self.pool)
+            .await
+            .with_context(|| "Failed to load trash")?;
+        self.current_screen = CurrentScreen::Trash;
+        Ok(())
+    }
+
+    /// Exit the trash view
+    pub fn exit_trash_screen(&mut self) {
+        self.current_screen = CurrentScreen::Main;
+    }
+
+    /// Restore the list or item selected in the trash view
+    pub async fn restore_selected_trash_entry(&mut self) -> Result<()> {
+        let Some(entry) = self
+            .trash_component
+            .restore_selected_entry(&self.pool)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        // Show the restored list, or the list holding the restored item
+        self.lists_component.load_lists(&self.pool).await?;
+        match entry {
+            TrashEntry::List(list) => {
+                self.lists_component.select_list_by_id(list.id);
+            }
+            TrashEntry::Item { item, .. } => {
+                if self.lists_component.select_list_by_id(item.list_id)
+                    && let Some(ui_list) = self.lists_component.get_selected_list_mut()
+                    && let Some(j) = ui_list.items.iter().position(|i| i.item.id == item.id)
+                {
+                    ui_list.item_state.select(Some(j));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Enter the agenda view, listing the pending items with a due date
+    pub async fn enter_agenda_screen(&mut self) -> Result<()> {
+        self.reload_agenda().await?;
+        self.current_screen = CurrentScreen::Agenda;
+        Ok(())
+    }
+
+    /// Exit the agenda view, picking up the items completed from it
+    pub async fn exit_agenda_screen(&mut self) -> Result<()> {
+        self.current_screen = CurrentScreen::Main;
+        self.refresh_items().await
+    }
+
+    /// Switch the agenda between the current database and all databases
+    pub async fn toggle_agenda_all_dbs(&mut self) -> Result<()> {
+        self.agenda_component.all_dbs = !self.agenda_component.all_dbs;
+        self.agenda_component.selected = None;
+        self.reload_agenda().await
+    }
+
+    /// Toggle the completion of the item selected in the agenda
+    pub async fn toggle_selected_agenda_item_done(&mut self) -> Result<()> {
+        self.agenda_component
+            .toggle_selected_done(&self.pool, &self.current_db_config, &self.pools)
+            .await?;
+        self.reload_agenda().await
+    }
+
+    /// Leave the agenda for the list (and database) holding the selected item
+    pub async fn jump_to_selected_agenda_item(&mut self) -> Result<()> {
+        let Some(row) = self.agenda_component.selected_row().cloned() else {
+            return Ok(());
+        };
+
+        if row.db.connection_str != self.current_db_config.connection_str {
+            self.remember_selection();
+            self.open_db(row.db).await?;
+        } else {
+            self.refresh_items().await?;
+        }
+        self.current_screen = CurrentScreen::Main;
+
+        let item = row.entry.item;
+        if self.lists_component.select_list_by_id(item.list_id)
+            && let Some(ui_list) = self.lists_component.get_selected_list_mut()
+            && let Some(j) = ui_list.items.iter().position(|i| i.item.id == item.id)
+        {
+            ui_list.item_state.select(Some(j));
+        }
+        Ok(())
+    }
+
+    async fn reload_agenda(&mut self) -> Result<()> {
+        self.agenda_component
+            .load_entries(
+                &self.config,
+                &self.current_db_config,
+                &self.pool,
+                &self.pools,
+            )
+            .await
+            .with_context(|| "Failed to load agenda")?;
+
+        // The rest of the agenda is still shown
+        if !self.agenda_component.skipped_dbs.is_empty() {
+            self.status = Some(StatusMessage::error(format!(
+                "Could not read {}, left out of the agenda",
+                self.agenda_component.skipped_dbs.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Enter the board view of the selected list
+    pub async fn enter_board_screen(&mut self) -> Result<()> {
+        let Some(ui_list) = self.lists_component.get_selected_list() else {
+            return Ok(());
+        };
+        if ui_list.is_smart() {
+            return Ok(());
+        }
+        self.board_component
+            .load_list(ui_list.list.clone(), &self.pool)
+            .await
+            .with_context(|| "Failed to load board")?;
+        self.current_screen = CurrentScreen::Board;
+        Ok(())
+    }
+
+    /// Exit the board view, picking up the cards moved on it
+    pub async fn exit_board_screen(&mut self) -> Result<()> {
+        self.current_screen = CurrentScreen::Main;
+        self.refresh_items().await
+    }
+
+    /// Keep the TUI up to date while no key is pressed
+    pub async fn on_tick(&mut self) {
+        if let Some(status) = &self.status
+            && status.is_expired(Instant::now())
+        {
+            self.status = None;
+        }
+
+        if let Err(e) = self.refresh_if_changed().await {
+            self.status = Some(StatusMessage::error(format!(
+                "Failed to refresh lists: {}",
+                e
+            )));
+        }
+    }
+
+    /// Show the outcome of a background task
+    pub fn handle_task_result(&mut self, result: TaskResult) {
+        match result {
+            TaskResult::Done(Some(message)) => self.status = Some(StatusMessage::info(message)),
+            TaskResult::Done(None) => {}
+            TaskResult::Failed(e) => self.status = Some(StatusMessage::error(e.to_string())),
+        }
+    }
+
+    /// Watch the current database for changes made elsewhere and tidy its trash up
+    ///
+    /// Only the running TUI needs this; one-shot CLI commands go without.
+    async fn watch_current_db(&mut self) -> Result<()> {
+        self.change_watcher = Some(ChangeWatcher::new(&self.pool).await?);
+        self.purge_expired_trash();
+        Ok(())
+    }
+
+    /// Empty the trash of entries kept longer than configured, without waiting for it
+    fn purge_expired_trash(&self) {
+        let pool = self.pool.clone();
+        let retention_days = self.config.trash_retention_days;
+        self.tasks.spawn(async move {
+            let purged = TrashEntry::purge_expired(&pool, retention_days)
+                .await
+                .with_context(|| "Failed to purge expired trash")?;
+            Ok((purged > 0).then(|| format!("Purged {} expired entries from the trash", purged)))
+        });
+    }
+
+    /// Reload the lists if the database changed outside the TUI, e.g. through
+    /// the CLI, or if the day changed and due dates need a fresh look
+    ///
+    /// Only the main screen is refreshed, so nothing changes under an open
+    /// pop-up or a grabbed item; changes are picked up once back on it.
+    /// Returns whether the lists were reloaded.
+    pub async fn refresh_if_changed(&mut self) -> Result<bool> {
+        if self.current_screen != CurrentScreen::Main {
+            return Ok(false);
+        }
+        let changed = match self.change_watcher.as_mut() {
+            Some(change_watcher) => change_watcher.has_changed().await?,
+            None => false,
+        };
+        let today = Local::now().date_naive();
+        if !changed && today == self.today {
+            return Ok(false);
+        }
+        self.today = today;
+        self.lists_component
+            .reload(&self.pool)
+            .await
+            .with_context(|| "Failed to reload lists")?;
+        Ok(true)
+    }
+
+    /// Re-fetch the items of every list, keeping the selections
+    async fn refresh_items(&mut self) -> Result<()> {
+        self.lists_component.reload_items(&self.pool).await
+    }
+
     /// Enter the "Change DB" screen by opening the corresponding pop-up
     pub fn enter_change_db_screen(&mut self) {
         // Find the index of the current database in the config
@@ -259,6 +836,15 @@ impl App {
         self.current_screen = CurrentScreen::AddDB;
     }
 
+    /// Swap between adding a database by name and registering one by path
+    pub fn toggle_add_or_register_db_screen(&mut self) {
+        self.input_state.clear();
+        self.current_screen = match self.current_screen {
+            CurrentScreen::AddDB => CurrentScreen::RegisterDB,
+            _ => CurrentScreen::AddDB,
+        };
+    }
+
     /// Exit the Add DB screen without saving
     pub fn exit_add_db_without_saving(&mut self) {
         self.current_screen = CurrentScreen::ChangeDB;
@@ -287,22 +873,12 @@ impl App {
 
     /// Switch to the selected database
     pub async fn switch_to_selected_db(&mut self) -> Result<()> {
-        if let Some(selected_db) = self.config.dbs.get(self.selected_db_index) {
-            // Initialize connection to the new database
-            let new_pool = init_db(&selected_db.connection_str)
-                .await
-                .with_context(|| "Failed to connect to database")?;
+        if let Some(selected_db) = self.config.dbs.get(self.selected_db_index).cloned() {
+            // Keep track of where we were in the database we are leaving
+            self.remember_selection();
 
-            // Update app state
-            self.current_db_config = selected_db.clone();
-            self.pool = new_pool;
-
-            // Reload all lists from the new database
-            self.lists_component = ListsComponent::new();
-            self.lists_component
-                .load_lists(&self.pool)
-                .await
-                .with_context(|| "Failed to load lists")?;
+            self.open_db(selected_db).await?;
+            self.restore_selection();
 
             // Return to main screen
             self.current_screen = CurrentScreen::Main;
@@ -310,20 +886,142 @@ impl App {
         Ok(())
     }
 
+    /// Connect to a database and load its lists
+    async fn open_db(&mut self, db_config: DBConfig) -> Result<()> {
+        // Initialize connection to the new database
+        let new_pool = self
+            .pools
+            .get(&db_config)
+            .await
+            .with_context(|| "Failed to connect to database")?;
+
+        // Update app state
+        self.current_db_config = db_config;
+        self.pool = new_pool;
+        // Watch before loading, so that nothing committed meanwhile is missed
+        if self.change_watcher.is_some() {
+            self.watch_current_db().await?;
+        }
+
+        // Reload all lists from the new database
+        self.lists_component = ListsComponent::new();
+        self.lists_component
+            .load_lists(&self.pool)
+            .await
+            .with_context(|| "Failed to load lists")?;
+
+        Ok(())
+    }
+
+    /// Restore the previous TUI session, saying so in the status bar if it fails
+    ///
+    /// A session that cannot be restored must not prevent the TUI from starting.
+    pub async fn resume_session(&mut self) {
+        if let Err(e) = self.restore_session().await {
+            self.status = Some(StatusMessage::error(format!(
+                "Failed to restore last session: {}",
+                e
+            )));
+        }
+    }
+
+    /// Restore the database and selection of the previous TUI session
+    ///
+    /// The last database is only reopened if no other database was requested
+    /// for this session (via `--db`, `JUDO_DB` or a project-local database).
+    pub async fn restore_session(&mut self) -> Result<()> {
+        if !self.config.remember_session {
+            return Ok(());
+        }
+        let Some(session_path) = &self.session_path else {
+            return Ok(());
+        };
+
+        self.session_state =
+            SessionState::read(session_path).with_context(|| "Failed to read session state")?;
+
+        if self.config.session.is_none()
+            && let Some(last_db) = self.session_state.last_db.clone()
+            && last_db != self.current_db_config.name
+            && let Some(db_config) = self.config.dbs.iter().find(|db| db.name == last_db)
+        {
+            self.open_db(db_config.clone()).await?;
+        }
+
+        self.restore_selection();
+        Ok(())
+    }
+
+    /// Save the database and selection of this TUI session
+    pub fn save_session(&mut self) -> Result<()> {
+        if !self.config.remember_session {
+            return Ok(());
+        }
+
+        let Some(session_path) = self.session_path.clone() else {
+            return Ok(());
+        };
+
+        self.remember_selection();
+        self.session_state
+            .write(&session_path)
+            .with_context(|| "Failed to save session state")
+    }
+
+    /// Record the current database and its selected list and item
+    pub fn remember_selection(&mut self) {
+        // Smart lists have no list ID to come back to
+        let selected_list = self
+            .lists_component
+            .get_selected_list()
+            .filter(|ui_list| !ui_list.is_smart());
+        let selection = DbSelection {
+            list_id: selected_list.map(|ui_list| ui_list.list.id),
+            item_id: selected_list.and_then(|ui_list| {
+                ui_list
+                    .item_state
+                    .selected()
+                    .and_then(|j| ui_list.items.get(j))
+                    .map(|ui_item| ui_item.item.id)
+            }),
+        };
+
+        self.session_state.last_db = Some(self.current_db_config.name.clone());
+        self.session_state
+            .selections
+            .insert(self.current_db_config.connection_str.clone(), selection);
+    }
+
+    /// Select the list and item recorded for the current database, if they still exist
+    pub fn restore_selection(&mut self) {
+        let Some(selection) = self
+            .session_state
+            .selections
+            .get(&self.current_db_config.connection_str)
+            .cloned()
+        else {
+            return;
+        };
+
+        if let Some(list_id) = selection.list_id
+            && self.lists_component.select_list_by_id(list_id)
+            && let Some(item_id) = selection.item_id
+            && let Some(ui_list) = self.lists_component.get_selected_list_mut()
+            && let Some(j) = ui_list.items.iter().position(|i| i.item.id == item_id)
+        {
+            ui_list.item_state.select(Some(j));
+        }
+    }
+
     /// Set the selected database as default
     pub async fn set_selected_db_as_default(&mut self) -> Result<()> {
         if let Some(selected_db) = self.config.dbs.get(self.selected_db_index) {
             // Update the default in config
-            self.config.default = selected_db.name.clone();
+            self.config.set_default(selected_db.name.clone());
 
             // Write updated config to file
-            let config_dir = dirs::config_dir()
-                .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?
-                .join("judo");
-            let config_path = config_dir.join("judo.toml");
-
             self.config
-                .write(&config_path)
+                .write(&self.config_path)
                 .with_context(|| "Failed to save config")?;
         }
         Ok(())
@@ -345,7 +1043,7 @@ impl Widget for &mut App {
         // Render db selector only when not in database-related popups
         if !matches!(
             self.current_screen,
-            CurrentScreen::ChangeDB | CurrentScreen::AddDB
+            CurrentScreen::ChangeDB | CurrentScreen::AddDB | CurrentScreen::RegisterDB
         ) {
             DBSelector::render(
                 closed_selector_area,
@@ -356,12 +1054,33 @@ impl Widget for &mut App {
         }
 
         // Render the main areas
-        self.lists_component
-            .render(lists_area, buf, self.config.clone());
+        if self.current_screen == CurrentScreen::Archive {
+            // Archived lists replace the active ones, with no items shown
+            self.archive_component
+                .render(lists_area, buf, self.config.clone());
+            ItemsComponent::render(None, false, items_area, buf, self.config.clone());
+        } else if self.current_screen == CurrentScreen::Agenda {
+            // The agenda spans both panels
+            self.agenda_component
+                .render(lists_area.union(items_area), buf, self.config.clone());
+        } else if self.current_screen == CurrentScreen::Board {
+            // So does the board
+            self.board_component
+                .render(lists_area.union(items_area), buf, self.config.clone());
+        } else if self.current_screen == CurrentScreen::Trash {
+            // Same for the trash
+            self.trash_component
+                .render(lists_area, buf, self.config.clone());
+            ItemsComponent::render(None, false, items_area, buf, self.config.clone());
+        } else {
+            self.lists_component
+                .render(lists_area, buf, self.config.clone());
 
-        // Render items with the selected list
-        let selected_list = self.lists_component.get_selected_list_mut();
-        ItemsComponent::render(selected_list, items_area, buf, self.config.clone());
+            // Render items with the selected list
+            let selected_list = self.lists_component.get_selected_list_mut();
+            let grabbed = self.current_screen == CurrentScreen::Grab;
+            ItemsComponent::render(selected_list, grabbed, items_area, buf, self.config.clone());
+        }
 
         // Render popup screens if active
         match self.current_screen {
@@ -377,6 +1096,13 @@ impl Widget for &mut App {
             CurrentScreen::ModifyItem => {
                 ModifyItemPopUp::render(self.config.clone(), &self.input_state, items_area, buf)
             }
+            CurrentScreen::Filter => {
+                FilterPopUp::render(self.config.clone(), &self.input_state, items_area, buf)
+            }
+            CurrentScreen::DueDate => {
+                DueDatePopUp::render(self.config.clone(), &self.input_state, items_area, buf)
+            }
+            CurrentScreen::ItemKeys => ItemKeysPopUp::render(self.config.clone(), items_area, buf),
             CurrentScreen::ChangeDB => {
                 ChangeDBPopUp::render(&self.config, self.selected_db_index, db_selector_area, buf)
             }
@@ -386,7 +1112,22 @@ impl Widget for &mut App {
                 db_selector_area,
                 buf,
             ),
+            CurrentScreen::RegisterDB => RegisterDBPopUp::render(
+                self.config.clone(),
+                &self.input_state,
+                db_selector_area,
+                buf,
+            ),
             _ => {}
         }
+
+        if let Some(status) = &self.status {
+            StatusBar::render(
+                status,
+                AppLayout::calculate_status_area(area),
+                buf,
+                self.config.clone(),
+            );
+        }
     }
 }
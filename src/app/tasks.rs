@@ -0,0 +1,65 @@
+use anyhow::Result;
+use std::future::Future;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+/// Outcome of work done in the background, handed back to the event loop
+#[derive(Debug)]
+pub enum TaskResult {
+    /// The work is done, with a message to show if there is anything to say
+    Done(Option<String>),
+    /// The work failed
+    Failed(anyhow::Error),
+}
+
+/// Runs slow work off the event loop, so that the TUI keeps responding
+///
+/// Results come back through a channel, in the order the tasks finish.
+#[derive(Debug)]
+pub struct BackgroundTasks {
+    sender: UnboundedSender<TaskResult>,
+    receiver: UnboundedReceiver<TaskResult>,
+}
+
+impl Default for BackgroundTasks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackgroundTasks {
+    pub fn new() -> Self {
+        let (sender, receiver) = unbounded_channel();
+        Self { sender, receiver }
+    }
+
+    /// Run a task on the runtime, sending back its result when it finishes
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = Result<Option<String>>> + Send + 'static,
+    {
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let result = match task.await {
+                Ok(message) => TaskResult::Done(message),
+                Err(e) => TaskResult::Failed(e),
+            };
+            // Nobody is listening anymore if the app has exited
+            let _ = sender.send(result);
+        });
+    }
+
+    /// Wait for the next task to finish
+    pub async fn next(&mut self) -> TaskResult {
+        match self.receiver.recv().await {
+            Some(result) => result,
+            // This holds a sender itself, so the channel should never close; if it
+            // does, there is nothing more to wait for and the event loop goes on
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Take the result of a finished task, if there is one
+    pub fn try_next(&mut self) -> Option<TaskResult> {
+        self.receiver.try_recv().ok()
+    }
+}
@@ -1,14 +1,59 @@
+use crate::db::dates::parse_due_date;
+use crate::db::models::{Priority, SortMode};
+use crate::db::query::Query;
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
 #[command(version, about)]
 pub struct Cli {
+    /// Path to the config file to use instead of the standard judo.toml
+    #[arg(long, env = "JUDO_CONFIG")]
+    pub config: Option<String>,
+
+    /// Name of the database to use instead of the default one
+    #[arg(long, env = "JUDO_DB")]
+    pub db: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
+    /// Create a project-local database in the current directory
+    Init {
+        /// Name of the project database (defaults to the directory name)
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+
+    /// Open the TUI, optionally on a given database and list
+    Tui {
+        /// Name of the database to open (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Name of the list to select (do not use with -i|--list-id)
+        #[arg(short, long, conflicts_with = "list_id")]
+        list: Option<String>,
+
+        /// ID of the list to select (do not use with -l|--list)
+        #[arg(short = 'i', long)]
+        list_id: Option<i64>,
+    },
+
+    /// Show the pending items with a due date, grouped by urgency
+    Agenda {
+        /// Name of the database to show (default DB if omitted)
+        #[arg(short, long, conflicts_with = "all")]
+        db: Option<String>,
+
+        /// Show the items of all databases
+        #[arg(short, long)]
+        all: bool,
+    },
+
     /// Manage databases
     Dbs {
         #[command(subcommand)]
@@ -26,6 +71,18 @@ pub enum Commands {
         #[command(subcommand)]
         command: Option<ItemCommands>,
     },
+
+    /// Manage deleted todo lists and items
+    Trash {
+        #[command(subcommand)]
+        command: Option<TrashCommands>,
+    },
+
+    /// Manage smart lists (saved filters shown as lists of the matching items)
+    Smart {
+        #[command(subcommand)]
+        command: Option<SmartCommands>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -33,11 +90,15 @@ pub enum DbCommands {
     /// List all databases
     Show,
 
-    /// Add a new database with the given name
+    /// Add a new database with the given name, or register an existing file with --path
     Add {
-        /// Name of the new database
+        /// Name of the new database (defaults to the file name when used with -p|--path)
+        #[arg(short, long, required_unless_present = "path")]
+        name: Option<String>,
+
+        /// Path to an existing SQLite file to register instead of creating a new database
         #[arg(short, long)]
-        name: String,
+        path: Option<String>,
     },
 }
 
@@ -48,6 +109,10 @@ pub enum ListCommands {
         /// Name of the new todo list
         #[arg(short, long)]
         name: Option<String>,
+
+        /// Show archived lists instead of active ones
+        #[arg(short, long)]
+        archived: bool,
     },
 
     /// Add a new todo list with the given name to the specified database (default DB if omitted)
@@ -75,16 +140,86 @@ pub enum ListCommands {
         #[arg(short, long)]
         db: Option<String>,
     },
+
+    /// Archive an existing todo list with the given name or ID in the specified database (default DB if omitted)
+    Archive {
+        /// Name of the list to be archived (do not use with -i|--id)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// ID of the list to be archived (do not use with -n|--name)
+        #[arg(short, long)]
+        id: Option<i64>,
+
+        /// Name of the database that contains the target list (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Restore an archived todo list with the given name or ID in the specified database (default DB if omitted)
+    Unarchive {
+        /// Name of the list to be restored (do not use with -i|--id)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// ID of the list to be restored (do not use with -n|--name)
+        #[arg(short, long)]
+        id: Option<i64>,
+
+        /// Name of the database that contains the target list (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Choose how the items of a todo list are sorted (manual, priority, due_date, created, alphabetical or done_last)
+    Sort {
+        /// Sort mode to use for the items of the list
+        mode: SortMode,
+
+        /// Name of the list to be sorted (do not use with -i|--id)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// ID of the list to be sorted (do not use with -n|--name)
+        #[arg(short, long)]
+        id: Option<i64>,
+
+        /// Name of the database that contains the target list (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Set the board columns of a todo list, the last one holding completed items (e.g. "todo,doing,done")
+    Columns {
+        /// Comma-separated names of the columns
+        columns: String,
+
+        /// Name of the target list (do not use with -i|--id)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// ID of the target list (do not use with -n|--name)
+        #[arg(short, long)]
+        id: Option<i64>,
+
+        /// Name of the database that contains the target list (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
 pub enum ItemCommands {
     /// List all todo items in a table which shows what list and database each belongs to
-    Show,
+    Show {
+        /// Only show the items matching a query, e.g. 'priority:high due<7d !done list:work "text"'
+        #[arg(short, long)]
+        query: Option<Query>,
+    },
 
     /// Add a new todo item with the given name to the specified list (by ID or name) and database (default DB if omitted)
     Add {
-        /// Name of the new todo item
+        /// Name of the new todo item, which may set its fields too, e.g. 'Pay rent !high @tomorrow #home ^chores'
         #[arg(short, long)]
         name: String,
 
@@ -99,6 +234,10 @@ pub enum ItemCommands {
         /// ID of the list to hold the new todo item (do not use with -n|--name)
         #[arg(short = 'i', long)]
         list_id: Option<i64>,
+
+        /// Due date, e.g. 'tomorrow', 'next fri', 'in 3 days', 'eow', 'oct 20' or '2026-10-20'
+        #[arg(long, value_parser = parse_due_date)]
+        due: Option<DateTime<Utc>>,
     },
 
     /// Delete an existing todo item with the given ID from the given database (default DB if omitted)
@@ -111,6 +250,21 @@ pub enum ItemCommands {
         db: Option<String>,
     },
 
+    /// Delete all completed items of the specified list (by ID or name) and database (default DB if omitted)
+    ClearDone {
+        /// Name of the database that contains the list (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+
+        /// Name of the list to clear (do not use with -i|--list-id)
+        #[arg(short, long, alias = "list", conflicts_with = "list_id")]
+        list_name: Option<String>,
+
+        /// ID of the list to clear (do not use with -l|--list-name)
+        #[arg(short = 'i', long)]
+        list_id: Option<i64>,
+    },
+
     /// Toggle whether a todo item is marked as done or not
     ToggleDone {
         /// ID of the target item
@@ -121,4 +275,170 @@ pub enum ItemCommands {
         #[arg(short, long)]
         db: Option<String>,
     },
+
+    /// Set the tags of a todo item (e.g. "home,errands"), replacing the current ones
+    Tag {
+        /// Comma-separated tags (empty to remove all tags)
+        tags: String,
+
+        /// ID of the target item
+        #[arg(short, long)]
+        id: i64,
+
+        /// Name of the database containing the target item
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Set or clear the due date of a todo item
+    Due {
+        /// Due date, e.g. 'tomorrow', 'next fri', 'in 3 days', 'eow', 'oct 20' or '2026-10-20'
+        #[arg(long, value_parser = parse_due_date, required_unless_present = "clear")]
+        due: Option<DateTime<Utc>>,
+
+        /// Remove the due date instead
+        #[arg(long, conflicts_with = "due")]
+        clear: bool,
+
+        /// ID of the target item
+        #[arg(short, long)]
+        id: i64,
+
+        /// Name of the database containing the target item
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Move a todo item to another board column of its list (moving to the last one marks it as done)
+    Move {
+        /// Column to move the item to
+        status: String,
+
+        /// ID of the target item
+        #[arg(short, long)]
+        id: i64,
+
+        /// Name of the database containing the target item
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Move a todo item to another position in its list (a sorted list switches to manual sorting, keeping its order)
+    Reorder {
+        /// ID of the target item
+        #[arg(short, long)]
+        id: i64,
+
+        /// New position of the item, 1 being the top (positions past the end move it to the bottom)
+        #[arg(short, long)]
+        position: usize,
+
+        /// Name of the database containing the target item
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TrashCommands {
+    /// List the deleted todo lists and items of the specified database (default DB if omitted)
+    Show {
+        /// Name of the database whose trash is shown (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Restore a deleted todo list or item with the given ID in the specified database (default DB if omitted)
+    Restore {
+        /// ID of the deleted list (do not use with -i|--item-id)
+        #[arg(
+            short,
+            long,
+            conflicts_with = "item_id",
+            required_unless_present = "item_id"
+        )]
+        list_id: Option<i64>,
+
+        /// ID of the deleted item (do not use with -l|--list-id)
+        #[arg(short, long)]
+        item_id: Option<i64>,
+
+        /// Name of the database that contains the deleted list or item (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Permanently delete everything in the trash of the specified database (default DB if omitted)
+    Empty {
+        /// Name of the database whose trash is emptied (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SmartCommands {
+    /// List the smart lists of a database along with their criteria
+    Show {
+        /// Name of the database to show (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Save a new smart list showing the items that match all of the given criteria
+    Add {
+        /// Name of the new smart list
+        #[arg(short, long)]
+        name: String,
+
+        /// Only items with at least this priority (high, medium or low)
+        #[arg(short, long)]
+        priority: Option<Priority>,
+
+        /// Only items due within this many days from today, overdue ones included
+        #[arg(long)]
+        due_within: Option<i64>,
+
+        /// Only completed items (do not use with --pending)
+        #[arg(long, conflicts_with = "pending")]
+        done: bool,
+
+        /// Only items that are not completed (do not use with --done)
+        #[arg(long)]
+        pending: bool,
+
+        /// Only items with this tag
+        #[arg(short, long)]
+        tag: Option<String>,
+
+        /// Only items whose name contains this text
+        #[arg(long)]
+        text: Option<String>,
+
+        /// Name of the database to add the smart list to (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Show the items currently matched by a smart list
+    Items {
+        /// ID of the smart list
+        #[arg(short, long)]
+        id: i64,
+
+        /// Name of the database that contains the smart list (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
+
+    /// Delete a smart list (the items it shows are left alone)
+    Delete {
+        /// ID of the smart list
+        #[arg(short, long)]
+        id: i64,
+
+        /// Name of the database that contains the smart list (default DB if omitted)
+        #[arg(short, long)]
+        db: Option<String>,
+    },
 }
@@ -0,0 +1,16 @@
+use std::fmt;
+
+/// Something asked of a command that can't be done, such as a list that doesn't exist
+///
+/// Commands return it rather than exiting on the spot, so that `main` can
+/// close the databases before exiting with `DATAERR`.
+#[derive(Debug)]
+pub struct InvalidInput(pub String);
+
+impl fmt::Display for InvalidInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidInput {}
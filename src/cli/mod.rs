@@ -1,3 +1,5 @@
 pub mod args;
+pub mod error;
 pub mod ops;
+pub use error::InvalidInput;
 pub use ops::*;
@@ -1,10 +1,19 @@
+#![allow(clippy::empty_line_after_doc_comments)]
+
 use std::io::Write;
 
 use crate::app::App;
+use crate::cli::InvalidInput;
 use crate::db::config::DBConfig;
-use crate::db::connections::{self, get_db_pool};
-use crate::db::models::{NewTodoItem, NewTodoList, TodoItem, TodoList};
-use anyhow::{Context, Result};
+use crate::db::connections::init_db;
+use crate::db::models::{
+    ItemWithList, NewSmartList, NewTodoList, SmartList, SortMode, TodoItem, TodoList, TrashEntry,
+};
+use crate::db::project;
+use crate::db::query::Query;
+use crate::db::quick_add::QuickAdd;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
 use sqlx::{Pool, Sqlite};
 use tabwriter::TabWriter;
 
@@ -33,23 +42,48 @@ pub fn list_dbs(app: &App) -> Result<()> {
     Ok(())
 }
 
-/// Creates a new database with the given name
-pub async fn add_db(mut app: App, name: String) -> Result<()> {
-    app.create_new_database(name, false)
+/// Creates a new database with the given name, or registers an existing file if a path is given
+pub async fn add_db(mut app: App, name: Option<String>, path: Option<String>) -> Result<()> {
+    match (name, path) {
+        (name, Some(path)) => app
+            .register_existing_database(path, name)
+            .await
+            .with_context(|| "Failed to register existing database")?,
+        (Some(name), None) => app
+            .create_new_database(name, false)
+            .await
+            .with_context(|| "Failed to create new database")?,
+        (None, None) => {
+            bail!(InvalidInput(
+                "Please provide either the name or the path of the database".to_string()
+            ))
+        }
+    }
+    Ok(())
+}
+
+/// Creates a project-local database in the current directory
+///
+/// Any `judo` invocation from this directory or below will use it by default.
+pub async fn init_project(name: Option<String>) -> Result<()> {
+    let cwd = std::env::current_dir().with_context(|| "Failed to read current directory")?;
+    let db =
+        project::init_project(&cwd, name).with_context(|| "Failed to create project directory")?;
+    init_db(&db.connection_str)
         .await
-        .with_context(|| "Failed to create new database")?;
+        .with_context(|| format!("Failed to initialize project database '{}'", db.name))?;
     Ok(())
 }
 
 /// List operations
 
-/// Lists all todo lists across all configured databases
+/// Lists all todo lists (or all archived ones) across all configured databases
 ///
 /// Displays lists in a formatted table showing:
 /// - List name and ID
 /// - Database name
 /// - Number of items in each list
-pub async fn list_lists(app: &App, name: Option<String>) -> Result<()> {
+pub async fn list_lists(app: &App, name: Option<String>, archived: bool) -> Result<()> {
     // Filter dbs if name is passed
     let dbs: Vec<_> = app
         .config
@@ -64,20 +98,24 @@ pub async fn list_lists(app: &App, name: Option<String>) -> Result<()> {
 
     // Iterate through all databases
     for db in dbs {
-        let db_pool = connections::get_db_pool(db.connection_str.as_str())
+        let db_pool = app
+            .pools
+            .get(db)
             .await
             .with_context(|| format!("Failed to get database pool for '{}'", db.name))?;
-        let lists = TodoList::get_all(&db_pool)
+        let lists = if archived {
+            TodoList::get_archived(&db_pool).await
+        } else {
+            TodoList::get_all(&db_pool).await
+        }
+        .with_context(|| format!("Failed to get lists from database '{}'", db.name))?;
+        let counts = TodoList::count_items(&db_pool)
             .await
-            .with_context(|| format!("Failed to get lists from database '{}'", db.name))?;
+            .with_context(|| format!("Failed to count items in database '{}'", db.name))?;
 
-        // For each list, count items and display info
+        // Display each list with its number of items
         for list in lists {
-            let num = list
-                .get_all_items(&db_pool)
-                .await
-                .with_context(|| format!("Failed to get items for list '{}'", list.name))?
-                .len();
+            let num = counts.get(&list.id).copied().unwrap_or_default();
             writeln!(tw, "{}\t{}\t{}\t{}", list.name, list.id, db.name, num)
                 .with_context(|| format!("Failed to write list entry for '{}'", list.name))?;
         }
@@ -105,7 +143,7 @@ pub async fn add_list(app: &App, name: String, db_name: &Option<String>) -> Resu
     Ok(())
 }
 
-/// Deletes a todo list by name or ID from the specified database
+/// Moves a todo list by name or ID to the trash of the specified database
 pub async fn delete_list(
     app: &App,
     name: Option<String>,
@@ -116,7 +154,7 @@ pub async fn delete_list(
         .await
         .with_context(|| "Unable to get pool")?;
 
-    let target_list = get_list_by_name_or_id(app, name, id, db_name).await?;
+    let target_list = get_list_by_name_or_id(app, name, id, db_name, false).await?;
     target_list
         .delete(&pool)
         .await
@@ -124,15 +162,93 @@ pub async fn delete_list(
     Ok(())
 }
 
+/// Archives a todo list by name or ID in the specified database
+pub async fn archive_list(
+    app: &App,
+    name: Option<String>,
+    id: Option<i64>,
+    db_name: &Option<String>,
+) -> Result<()> {
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+
+    let mut target_list = get_list_by_name_or_id(app, name, id, db_name, false).await?;
+    target_list
+        .archive(&pool)
+        .await
+        .with_context(|| format!("Failed to archive list '{}'", target_list.name))?;
+    Ok(())
+}
+
+/// Restores an archived todo list by name or ID in the specified database
+pub async fn unarchive_list(
+    app: &App,
+    name: Option<String>,
+    id: Option<i64>,
+    db_name: &Option<String>,
+) -> Result<()> {
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+
+    let mut target_list = get_list_by_name_or_id(app, name, id, db_name, true).await?;
+    target_list
+        .unarchive(&pool)
+        .await
+        .with_context(|| format!("Failed to unarchive list '{}'", target_list.name))?;
+    Ok(())
+}
+
+/// Sets how the items of a todo list (by name or ID) are sorted in the specified database
+pub async fn sort_list(
+    app: &App,
+    mode: SortMode,
+    name: Option<String>,
+    id: Option<i64>,
+    db_name: &Option<String>,
+) -> Result<()> {
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+
+    let mut target_list = get_list_by_name_or_id(app, name, id, db_name, false).await?;
+    target_list
+        .set_sort_mode(&pool, mode)
+        .await
+        .with_context(|| format!("Failed to sort list '{}'", target_list.name))?;
+    Ok(())
+}
+
+/// Sets the board columns of a list from a comma-separated string
+pub async fn set_list_columns(
+    app: &App,
+    columns: String,
+    name: Option<String>,
+    id: Option<i64>,
+    db_name: &Option<String>,
+) -> Result<()> {
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+
+    let mut target_list = get_list_by_name_or_id(app, name, id, db_name, false).await?;
+    let columns = columns.split(',').map(str::to_string).collect();
+    if let Err(e) = target_list.set_statuses(&pool, columns).await {
+        bail!(InvalidInput(e.to_string()))
+    }
+    Ok(())
+}
+
 /// Item operations
 
-/// Lists all todo items across all databases and lists
+/// Lists the todo items matching a query across all databases and lists
 ///
 /// Displays items in a formatted table showing:
 /// - Item name, ID, and completion status
 /// - Parent list name and ID
 /// - Database name
-pub async fn list_items(app: &App) -> Result<()> {
+pub async fn list_items(app: &App, query: Query) -> Result<()> {
     let dbs = &app.config.dbs;
     let mut tw = TabWriter::new(vec![]);
     writeln!(tw, "Name\tID\tList name\tList ID\tDB\tDone?")
@@ -140,27 +256,25 @@ pub async fn list_items(app: &App) -> Result<()> {
     writeln!(tw, "----\t--\t---------\t-------\t--\t-----")
         .with_context(|| "Failed to write table separator")?;
 
-    // Iterate through all databases and their lists
+    // Iterate through all databases, an empty query matching every item
     for db in dbs {
-        let pool = get_db_pool(db.connection_str.as_str())
+        let pool = app
+            .pools
+            .get(db)
             .await
             .with_context(|| format!("Failed to get database pool for '{}'", db.name))?;
-        let lists = TodoList::get_all(&pool)
+        let matches = query
+            .get_matches(&pool, None)
             .await
-            .with_context(|| format!("Failed to get lists from database '{}'", db.name))?;
-        for list in lists {
-            let items = TodoItem::get_by_list_id(&pool, list.id)
-                .await
-                .with_context(|| format!("Failed to get items for list '{}'", list.name))?;
-            // Display each item with its context information
-            for item in items {
-                writeln!(
-                    tw,
-                    "{}\t{}\t{}\t{}\t{}\t{}",
-                    item.name, item.id, list.name, list.id, db.name, item.is_done
-                )
-                .with_context(|| format!("Failed to write item entry for '{}'", item.name))?
-            }
+            .with_context(|| format!("Failed to get items from database '{}'", db.name))?;
+        // Display each item with its context information
+        for ItemWithList { item, list_name } in matches {
+            writeln!(
+                tw,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                item.name, item.id, list_name, item.list_id, db.name, item.is_done
+            )
+            .with_context(|| format!("Failed to write item entry for '{}'", item.name))?
         }
     }
     tw.flush().with_context(|| "Failed to flush table writer")?;
@@ -173,32 +287,47 @@ pub async fn list_items(app: &App) -> Result<()> {
     Ok(())
 }
 
-/// Creates a new todo item in the specified list and database
+/// Creates a new todo item in the specified list and database, reading the quick-add syntax of its name
+///
+/// A due date given with `due` replaces the one typed in the name.
 pub async fn add_item(
     app: &App,
     name: String,
     db_name: &Option<String>,
     list_id: Option<i64>,
     list_name: Option<String>,
+    due: Option<DateTime<Utc>>,
 ) -> Result<()> {
     let pool = get_db_pool_from_option(app, db_name)
         .await
         .with_context(|| "Unable to get pool")?;
-    let target_list = get_list_by_name_or_id(app, list_name, list_id, db_name).await?;
+    let quick_add = QuickAdd::parse(&name);
 
-    let new_item = NewTodoItem {
-        name: name.clone(),
-        list_id: target_list.id,
-        priority: None,
-        due_date: None,
+    // ^list stands for --list-name when no list is given otherwise
+    let list_name = match (&quick_add.list, list_name, list_id) {
+        (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+            bail!(InvalidInput(
+                "Please provide the list either with ^list or with the list options, not both"
+                    .to_string()
+            ))
+        }
+        (Some(quick_list), None, None) => Some(quick_list.clone()),
+        (None, list_name, _) => list_name,
     };
-    TodoItem::create(&pool, new_item)
+    let target_list = get_list_by_name_or_id(app, list_name, list_id, db_name, false).await?;
+
+    let today = chrono::Local::now().date_naive();
+    let mut new_item = quick_add.new_item(target_list.id, today);
+    if due.is_some() {
+        new_item.due_date = due;
+    }
+    TodoItem::create_with_tags(&pool, new_item, quick_add.tags)
         .await
-        .with_context(|| format!("Failed to create item '{}'", name))?;
+        .with_context(|| format!("Failed to create item '{}'", quick_add.name))?;
     Ok(())
 }
 
-/// Deletes a todo item by ID from the specified database
+/// Moves a todo item by ID to the trash of the specified database
 pub async fn delete_item(app: &App, id: i64, db_name: &Option<String>) -> Result<()> {
     let db = get_db_from_option(app, db_name)
         .with_context(|| "Failed to get database from database name")?;
@@ -211,11 +340,10 @@ pub async fn delete_item(app: &App, id: i64, db_name: &Option<String>) -> Result
     {
         Some(this) => this,
         None => {
-            eprintln!(
-                "Error: Item with ID '{}' not found in database '{}'",
+            bail!(InvalidInput(format!(
+                "Item with ID '{}' not found in database '{}'",
                 id, db.name
-            );
-            std::process::exit(exitcode::DATAERR)
+            )))
         }
     };
 
@@ -224,6 +352,25 @@ pub async fn delete_item(app: &App, id: i64, db_name: &Option<String>) -> Result
         .with_context(|| format!("Failed to delete item with ID '{}'", id))
 }
 
+/// Moves all completed items of a list to the trash of the specified database
+pub async fn clear_done_items(
+    app: &App,
+    db_name: &Option<String>,
+    list_id: Option<i64>,
+    list_name: Option<String>,
+) -> Result<()> {
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    let target_list = get_list_by_name_or_id(app, list_name, list_id, db_name, false).await?;
+
+    target_list
+        .clear_done(&pool)
+        .await
+        .with_context(|| format!("Failed to clear completed items of '{}'", target_list.name))?;
+    Ok(())
+}
+
 /// Toggles the completion status of a todo item
 pub async fn toggle_done_item(app: &App, id: i64, db_name: &Option<String>) -> Result<()> {
     let db = get_db_from_option(app, db_name)
@@ -240,11 +387,450 @@ pub async fn toggle_done_item(app: &App, id: i64, db_name: &Option<String>) -> R
             .await
             .with_context(|| format!("Failed to toggle done status for item with ID '{}'", id)),
         None => {
-            eprintln!(
-                "Error: Item with ID '{}' not found in database '{}'",
+            bail!(InvalidInput(format!(
+                "Item with ID '{}' not found in database '{}'",
                 id, db.name
-            );
-            std::process::exit(exitcode::DATAERR);
+            )))
+        }
+    }
+}
+
+/// Replaces the tags of an item with a comma-separated list of tags
+pub async fn tag_item(app: &App, tags: String, id: i64, db_name: &Option<String>) -> Result<()> {
+    let db = get_db_from_option(app, db_name)
+        .with_context(|| "Failed to get database from database name")?;
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    let item = TodoItem::get_by_id(&pool, id)
+        .await
+        .with_context(|| format!("Failed to query item with ID '{}'", id))?;
+    let Some(mut item) = item else {
+        bail!(InvalidInput(format!(
+            "Item with ID '{}' not found in database '{}'",
+            id, db.name
+        )))
+    };
+
+    let tags = tags.split(',').map(str::to_string).collect();
+    if let Err(e) = item.set_tags(&pool, tags).await {
+        bail!(InvalidInput(e.to_string()))
+    }
+    Ok(())
+}
+
+/// Sets the due date of an item, or clears it when no date is given
+pub async fn set_item_due_date(
+    app: &App,
+    due: Option<DateTime<Utc>>,
+    id: i64,
+    db_name: &Option<String>,
+) -> Result<()> {
+    let db = get_db_from_option(app, db_name)
+        .with_context(|| "Failed to get database from database name")?;
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    let item = TodoItem::get_by_id(&pool, id)
+        .await
+        .with_context(|| format!("Failed to query item with ID '{}'", id))?;
+    let Some(mut item) = item else {
+        bail!(InvalidInput(format!(
+            "Item with ID '{}' not found in database '{}'",
+            id, db.name
+        )))
+    };
+
+    match due {
+        Some(due) => item.update_due_date(&pool, due).await,
+        None => item.clear_due_date(&pool).await,
+    }
+    .with_context(|| format!("Failed to set due date of item with ID '{}'", id))
+}
+
+/// Moves an item to the board column with the given name
+pub async fn move_item_to_column(
+    app: &App,
+    status: String,
+    id: i64,
+    db_name: &Option<String>,
+) -> Result<()> {
+    let db = get_db_from_option(app, db_name)
+        .with_context(|| "Failed to get database from database name")?;
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    let item = TodoItem::get_by_id(&pool, id)
+        .await
+        .with_context(|| format!("Failed to query item with ID '{}'", id))?;
+    let Some(mut item) = item else {
+        bail!(InvalidInput(format!(
+            "Item with ID '{}' not found in database '{}'",
+            id, db.name
+        )))
+    };
+
+    let list = TodoList::get_by_id(&pool, item.list_id)
+        .await
+        .with_context(|| format!("Failed to query list with ID '{}'", item.list_id))?
+        .with_context(|| format!("List with ID '{}' not found", item.list_id))?;
+    let statuses = list.statuses();
+    let Some(column) = statuses.iter().position(|s| s == &status) else {
+        bail!(InvalidInput(format!(
+            "List '{}' has no column '{}' (columns: {})",
+            list.name,
+            status,
+            statuses.join(", ")
+        )))
+    };
+
+    item.move_to_column(&pool, &statuses, column)
+        .await
+        .with_context(|| format!("Failed to move item with ID '{}'", id))
+}
+
+/// Moves an item to the given position of its list, counted from 1
+pub async fn reorder_item(
+    app: &App,
+    id: i64,
+    position: usize,
+    db_name: &Option<String>,
+) -> Result<()> {
+    if position == 0 {
+        bail!(InvalidInput("Positions start at 1".to_string()))
+    }
+    let db = get_db_from_option(app, db_name)
+        .with_context(|| "Failed to get database from database name")?;
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    let item = TodoItem::get_by_id(&pool, id)
+        .await
+        .with_context(|| format!("Failed to query item with ID '{}'", id))?;
+    let Some(mut item) = item else {
+        bail!(InvalidInput(format!(
+            "Item with ID '{}' not found in database '{}'",
+            id, db.name
+        )))
+    };
+
+    // Positions follow what the list shows, so a sorted list keeps its order as the manual one
+    let mut list = TodoList::get_by_id(&pool, item.list_id)
+        .await
+        .with_context(|| format!("Failed to query list with ID '{}'", item.list_id))?
+        .with_context(|| format!("List with ID '{}' not found", item.list_id))?;
+    list.move_item_to_position(&pool, &mut item, position - 1)
+        .await
+        .with_context(|| format!("Failed to reorder item with ID '{}'", id))
+}
+
+/// Agenda operations
+
+/// Lists the pending items with a due date of one or all databases
+///
+/// Displays items in a formatted table showing:
+/// - Their group (overdue, today, tomorrow, this week or later) and due date
+/// - Item name and ID
+/// - Parent list name and database name
+///
+/// With `all`, databases that can't be read are left out and the error names them.
+pub async fn show_agenda(app: &App, db_name: &Option<String>, all: bool) -> Result<()> {
+    let dbs = if all {
+        app.config.dbs.clone()
+    } else {
+        vec![get_db_from_option(app, db_name).with_context(|| "Failed to get database")?]
+    };
+
+    let mut rows = Vec::new();
+    let mut skipped = Vec::new();
+    for db in dbs {
+        let entries = match get_agenda_of(app, &db).await {
+            Ok(entries) => entries,
+            // One database that can't be read leaves the others on the agenda, and fails the command after it
+            Err(e) if all => {
+                skipped.push(format!("{:#}", e));
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        rows.extend(entries.into_iter().map(|entry| (entry, db.name.clone())));
+    }
+    rows.sort_by_key(|(entry, _)| entry.item.due_date);
+
+    let mut tw = TabWriter::new(vec![]);
+    writeln!(tw, "When\tDue\tName\tID\tList name\tDB")
+        .with_context(|| "Failed to write table header")?;
+    writeln!(tw, "----\t---\t----\t--\t---------\t--")
+        .with_context(|| "Failed to write table separator")?;
+    for (entry, db_name) in rows {
+        let due = entry
+            .item
+            .due_date
+            .map(|date| {
+                date.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        writeln!(
+            tw,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            entry.bucket().label(),
+            due,
+            entry.item.name,
+            entry.item.id,
+            entry.list_name,
+            db_name
+        )
+        .with_context(|| format!("Failed to write item entry for '{}'", entry.item.name))?;
+    }
+    tw.flush().with_context(|| "Failed to flush table writer")?;
+    let output = String::from_utf8(
+        tw.into_inner()
+            .with_context(|| "Failed to get table writer buffer")?,
+    )
+    .with_context(|| "Failed to convert table output to string")?;
+    print!("{output}");
+
+    if !skipped.is_empty() {
+        bail!("Some databases were left out: {}", skipped.join("; "));
+    }
+    Ok(())
+}
+
+/// Gets the agenda entries of one database
+async fn get_agenda_of(app: &App, db: &DBConfig) -> Result<Vec<ItemWithList>> {
+    let pool = app
+        .pools
+        .get(db)
+        .await
+        .with_context(|| format!("Failed to get database pool for '{}'", db.name))?;
+    ItemWithList::get_agenda(&pool)
+        .await
+        .with_context(|| format!("Failed to get agenda from database '{}'", db.name))
+}
+
+/// Trash operations
+
+/// Lists the deleted todo lists and items of the specified database
+///
+/// Displays entries in a formatted table showing:
+/// - Whether the entry is a list or an item
+/// - Its name and ID
+/// - The list an item belongs to
+/// - When it was deleted
+pub async fn list_trash(app: &App, db_name: &Option<String>) -> Result<()> {
+    let pool = get_trash_pool(app, db_name).await?;
+    let entries = TrashEntry::get_all(&pool)
+        .await
+        .with_context(|| "Failed to get trash")?;
+
+    let mut tw = TabWriter::new(vec![]);
+    writeln!(tw, "Type\tName\tID\tList name\tDeleted at")
+        .with_context(|| "Failed to write table header")?;
+    writeln!(tw, "----\t----\t--\t---------\t----------")
+        .with_context(|| "Failed to write table separator")?;
+    for entry in entries {
+        let deleted_at = entry
+            .deleted_at()
+            .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        match entry {
+            TrashEntry::List(list) => {
+                writeln!(tw, "list\t{}\t{}\t\t{}", list.name, list.id, deleted_at)
+                    .with_context(|| format!("Failed to write list entry for '{}'", list.name))?
+            }
+            TrashEntry::Item { item, list_name } => writeln!(
+                tw,
+                "item\t{}\t{}\t{}\t{}",
+                item.name, item.id, list_name, deleted_at
+            )
+            .with_context(|| format!("Failed to write item entry for '{}'", item.name))?,
+        }
+    }
+    tw.flush().with_context(|| "Failed to flush table writer")?;
+    let output = String::from_utf8(
+        tw.into_inner()
+            .with_context(|| "Failed to get table writer buffer")?,
+    )
+    .with_context(|| "Failed to convert table output to string")?;
+    print!("{output}");
+    Ok(())
+}
+
+/// Restores a deleted todo list or item by ID in the specified database
+pub async fn restore_from_trash(
+    app: &App,
+    list_id: Option<i64>,
+    item_id: Option<i64>,
+    db_name: &Option<String>,
+) -> Result<()> {
+    let db = get_db_from_option(app, db_name)
+        .with_context(|| "Failed to get database from database name")?;
+    let pool = get_trash_pool(app, db_name).await?;
+
+    let (entry, kind, id) = match (list_id, item_id) {
+        (Some(id), None) => (TrashEntry::get_list(&pool, id).await?, "List", id),
+        (None, Some(id)) => (TrashEntry::get_item(&pool, id).await?, "Item", id),
+        _ => {
+            bail!(InvalidInput(
+                "Please provide either the ID of a list or the ID of an item".to_string()
+            ))
+        }
+    };
+
+    match entry {
+        Some(entry) => entry
+            .restore(&pool)
+            .await
+            .with_context(|| format!("Failed to restore {} with ID '{}'", kind, id)),
+        None => {
+            bail!(InvalidInput(format!(
+                "{} with ID '{}' not found in the trash of database '{}'",
+                kind, id, db.name
+            )))
+        }
+    }
+}
+
+/// Permanently deletes everything in the trash of the specified database
+pub async fn empty_trash(app: &App, db_name: &Option<String>) -> Result<()> {
+    let pool = get_trash_pool(app, db_name).await?;
+    TrashEntry::empty(&pool)
+        .await
+        .with_context(|| "Failed to empty trash")?;
+    Ok(())
+}
+
+/// Gets a pool for the specified database, first purging what has expired from its trash
+async fn get_trash_pool(app: &App, db_name: &Option<String>) -> Result<Pool<Sqlite>> {
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    TrashEntry::purge_expired(&pool, app.config.trash_retention_days)
+        .await
+        .with_context(|| "Failed to purge expired trash")?;
+    Ok(pool)
+}
+
+/// Smart list operations
+
+/// Lists the smart lists of a database along with their criteria
+pub async fn list_smart_lists(app: &App, db_name: &Option<String>) -> Result<()> {
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    let smart_lists = SmartList::get_all(&pool)
+        .await
+        .with_context(|| "Failed to get smart lists")?;
+
+    let mut tw = TabWriter::new(vec![]);
+    writeln!(tw, "Name\tID\tCriteria").with_context(|| "Failed to write table header")?;
+    writeln!(tw, "----\t--\t--------").with_context(|| "Failed to write table separator")?;
+    for smart_list in smart_lists {
+        writeln!(
+            tw,
+            "{}\t{}\t{}",
+            smart_list.name,
+            smart_list.id,
+            smart_list.describe()
+        )
+        .with_context(|| format!("Failed to write smart list entry for '{}'", smart_list.name))?;
+    }
+    tw.flush().with_context(|| "Failed to flush table writer")?;
+    let output = String::from_utf8(
+        tw.into_inner()
+            .with_context(|| "Failed to get table writer buffer")?,
+    )
+    .with_context(|| "Failed to convert table output to string")?;
+    print!("{output}");
+    Ok(())
+}
+
+/// Saves a new smart list in the specified database (default DB if omitted)
+pub async fn add_smart_list(
+    app: &App,
+    new_smart_list: NewSmartList,
+    db_name: &Option<String>,
+) -> Result<()> {
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    SmartList::create(&pool, new_smart_list)
+        .await
+        .with_context(|| "Failed to create smart list")?;
+    Ok(())
+}
+
+/// Lists the items currently matched by a smart list
+pub async fn list_smart_list_items(app: &App, id: i64, db_name: &Option<String>) -> Result<()> {
+    let db = get_db_from_option(app, db_name)
+        .with_context(|| "Failed to get database from database name")?;
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    let Some(smart_list) = SmartList::get_by_id(&pool, id)
+        .await
+        .with_context(|| format!("Failed to query smart list with ID '{}'", id))?
+    else {
+        bail!(InvalidInput(format!(
+            "Smart list with ID '{}' not found in database '{}'",
+            id, db.name
+        )))
+    };
+    let items = smart_list.get_items(&pool).await?;
+
+    let mut tw = TabWriter::new(vec![]);
+    writeln!(tw, "Name\tID\tList ID\tDone?\tDue\tTags")
+        .with_context(|| "Failed to write table header")?;
+    writeln!(tw, "----\t--\t-------\t-----\t---\t----")
+        .with_context(|| "Failed to write table separator")?;
+    for item in items {
+        let due = item
+            .due_date
+            .map(|date| {
+                date.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .unwrap_or_default();
+        writeln!(
+            tw,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            item.name, item.id, item.list_id, item.is_done, due, item.tags
+        )
+        .with_context(|| format!("Failed to write item entry for '{}'", item.name))?;
+    }
+    tw.flush().with_context(|| "Failed to flush table writer")?;
+    let output = String::from_utf8(
+        tw.into_inner()
+            .with_context(|| "Failed to get table writer buffer")?,
+    )
+    .with_context(|| "Failed to convert table output to string")?;
+    print!("{output}");
+    Ok(())
+}
+
+/// Deletes a smart list by ID, leaving the items it shows alone
+pub async fn delete_smart_list(app: &App, id: i64, db_name: &Option<String>) -> Result<()> {
+    let db = get_db_from_option(app, db_name)
+        .with_context(|| "Failed to get database from database name")?;
+    let pool = get_db_pool_from_option(app, db_name)
+        .await
+        .with_context(|| "Unable to get pool")?;
+    match SmartList::get_by_id(&pool, id)
+        .await
+        .with_context(|| format!("Failed to query smart list with ID '{}'", id))?
+    {
+        Some(smart_list) => smart_list
+            .delete(&pool)
+            .await
+            .with_context(|| format!("Failed to delete smart list with ID '{}'", id)),
+        None => {
+            bail!(InvalidInput(format!(
+                "Smart list with ID '{}' not found in database '{}'",
+                id, db.name
+            )))
         }
     }
 }
@@ -268,7 +854,9 @@ fn get_db_from_option(app: &App, db: &Option<String>) -> Result<DBConfig> {
 
 /// Retrieves a todo list by either name or ID from the specified database
 ///
-/// Exactly one of `name` or `id` must be provided. Exits with error if:
+/// Names and IDs are looked up among the archived lists if `archived` is set, and
+/// among the active ones otherwise. Exactly one of `name` or `id` must be provided.
+/// Fails with `InvalidInput` if:
 /// - Both name and ID are provided
 /// - Neither name nor ID are provided  
 /// - The specified list is not found
@@ -277,6 +865,7 @@ async fn get_list_by_name_or_id(
     name: Option<String>,
     id: Option<i64>,
     db_name: &Option<String>,
+    archived: bool,
 ) -> Result<TodoList> {
     let db = get_db_from_option(app, db_name)
         .with_context(|| "Failed to get database from database name")?;
@@ -290,49 +879,53 @@ async fn get_list_by_name_or_id(
                 .await
                 .with_context(|| format!("Failed to query list with ID '{}'", list_id))?
             {
-                Some(list) => Ok(list),
-                None => {
-                    eprintln!(
-                        "Error: List with ID '{}' not found in database '{}'",
+                Some(list) if list.archived_at.is_some() == archived => Ok(list),
+                _ => {
+                    bail!(InvalidInput(format!(
+                        "List with ID '{}' not found in database '{}'",
                         list_id, db.name
-                    );
-                    std::process::exit(exitcode::DATAERR)
+                    )))
                 }
             };
         }
         // Search by name
         (None, Some(list_name)) => {
-            let lists = TodoList::get_all(&pool)
-                .await
-                .with_context(|| format!("Failed to get all lists from database '{}'", db.name))?;
+            let lists = if archived {
+                TodoList::get_archived(&pool).await
+            } else {
+                TodoList::get_all(&pool).await
+            }
+            .with_context(|| format!("Failed to get all lists from database '{}'", db.name))?;
             for list in lists {
                 if list.name == list_name {
                     return Ok(list);
                 }
             }
-            eprintln!(
-                "Error: List with name '{}' not found in database '{}'",
+            bail!(InvalidInput(format!(
+                "List with name '{}' not found in database '{}'",
                 list_name, db.name
-            );
-            std::process::exit(exitcode::DATAERR)
+            )))
         }
         // Error cases
         (Some(_), Some(_)) => {
-            eprintln!("Please provide either the name or the ID of the list, not both");
-            std::process::exit(exitcode::DATAERR);
+            bail!(InvalidInput(
+                "Please provide either the name or the ID of the list, not both".to_string()
+            ))
         }
         (None, None) => {
-            eprintln!("Please provide either the name or the ID of the list");
-            std::process::exit(exitcode::DATAERR);
+            bail!(InvalidInput(
+                "Please provide either the name or the ID of the list".to_string()
+            ))
         }
     }
 }
 
-/// Gets a database connection pool for the specified database
+/// Gets the connection pool of the specified database, opened once and shared by all operations
 async fn get_db_pool_from_option(app: &App, db_option: &Option<String>) -> Result<Pool<Sqlite>> {
     let target_db = get_db_from_option(app, db_option)
         .with_context(|| "Failed to get database from database name")?;
-    return get_db_pool(target_db.connection_str.as_str())
+    app.pools
+        .get(&target_db)
         .await
-        .with_context(|| format!("Failed to create database pool for '{}'", target_db.name));
+        .with_context(|| format!("Failed to create database pool for '{}'", target_db.name))
 }
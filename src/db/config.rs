@@ -1,47 +1,120 @@
-use anyhow::{Context, Result};
+use crate::cli::InvalidInput;
+use crate::db::theme::Theme;
+use anyhow::{Context, Result, bail};
+use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // Default variables
 const DEFAULT_DB_NAME: &str = "dojo";
 const DEFAULT_DB_FILE: &str = "judo.db";
-
-const DEFAULT_FG_COLOUR: &str = "#FCF1D5";
-const DEFAULT_HL_COLOUR: &str = "#FFA69E";
-const DEFAULT_BG_COLOUR: &str = "#002626";
+const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+const DEFAULT_BUSY_TIMEOUT_MS: u64 = 5000;
 
 /// Config file definition
-#[derive(Deserialize, Serialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct Config {
     pub default: String,
     pub dbs: Vec<DBConfig>,
     #[serde(default)]
     pub colours: Theme,
+    /// Reopen the last database, list and item when starting the TUI
+    #[serde(default = "default_remember_session")]
+    pub remember_session: bool,
+    /// Days after which deleted lists and items are purged from the trash (0 keeps them)
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Overrides of the default database for this session
+    #[serde(skip)]
+    pub session: Option<SessionDefault>,
 }
 
 /// Database configuration
-#[derive(Deserialize, Serialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Debug)]
 pub struct DBConfig {
     pub name: String,
     pub connection_str: String,
+    /// SQLite settings, written next to the name and connection string
+    #[serde(flatten)]
+    pub sqlite: SqliteSettings,
 }
 
-#[derive(Deserialize, Serialize, Clone)]
-pub struct Theme {
-    pub background: String,
-    pub foreground: String,
-    pub highlight: String,
+/// SQLite settings of a database, each left out taking the default
+///
+/// The defaults suit several processes sharing the file, such as the CLI
+/// running while the TUI is open.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+pub struct SqliteSettings {
+    /// Journal mode (default: wal)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub journal_mode: Option<JournalMode>,
+    /// Milliseconds to wait for another connection to release a lock (default: 5000)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub busy_timeout_ms: Option<u64>,
+    /// Enforce foreign keys (default: true)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreign_keys: Option<bool>,
+    /// How often SQLite waits for data to reach the disk (default: normal)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synchronous: Option<Synchronous>,
 }
 
-impl Default for Theme {
-    fn default() -> Self {
-        Self {
-            background: DEFAULT_BG_COLOUR.to_string(),
-            foreground: DEFAULT_FG_COLOUR.to_string(),
-            highlight: DEFAULT_HL_COLOUR.to_string(),
-        }
+impl SqliteSettings {
+    pub fn journal_mode(&self) -> JournalMode {
+        self.journal_mode.unwrap_or(JournalMode::Wal)
+    }
+
+    pub fn busy_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.busy_timeout_ms.unwrap_or(DEFAULT_BUSY_TIMEOUT_MS))
+    }
+
+    pub fn foreign_keys(&self) -> bool {
+        self.foreign_keys.unwrap_or(true)
     }
+
+    pub fn synchronous(&self) -> Synchronous {
+        self.synchronous.unwrap_or(Synchronous::Normal)
+    }
+}
+
+/// SQLite journal modes
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalMode {
+    Delete,
+    Truncate,
+    Persist,
+    Memory,
+    Wal,
+    Off,
+}
+
+/// SQLite synchronous settings
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Synchronous {
+    Off,
+    Normal,
+    Full,
+    Extra,
+}
+
+fn default_remember_session() -> bool {
+    true
+}
+
+fn default_trash_retention_days() -> u32 {
+    DEFAULT_TRASH_RETENTION_DAYS
+}
+
+/// Default database overridden for the current session only
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionDefault {
+    /// Default database from judo.toml, which is what gets written back to disk
+    pub global_default: String,
+    /// Database added for this session only, which must not be written to judo.toml
+    pub transient_db: Option<String>,
 }
 
 impl Default for DBConfig {
@@ -61,6 +134,7 @@ impl Default for DBConfig {
         Self {
             name: DEFAULT_DB_NAME.to_string(),
             connection_str,
+            sqlite: SqliteSettings::default(),
         }
     }
 }
@@ -72,6 +146,9 @@ impl Default for Config {
             default: DEFAULT_DB_NAME.to_string(),
             dbs: vec![DBConfig::default()],
             colours: Theme::default(),
+            remember_session: default_remember_session(),
+            trash_retention_days: default_trash_retention_days(),
+            session: None,
         }
     }
 }
@@ -79,9 +156,18 @@ impl Default for Config {
 impl Config {
     /// Write config struct to judo.toml file
     pub fn write(&self, config_path: &PathBuf) -> Result<()> {
+        // Session overrides are never persisted
+        let mut on_disk = self.clone();
+        if let Some(session) = &self.session {
+            on_disk.default = session.global_default.clone();
+            if let Some(transient_db) = &session.transient_db {
+                on_disk.dbs.retain(|db| &db.name != transient_db);
+            }
+        }
+
         // Convert config to string to be written to config file
         let toml_content =
-            toml::to_string_pretty(&self).with_context(|| "Failed to serialize judo.toml")?;
+            toml::to_string_pretty(&on_disk).with_context(|| "Failed to serialize judo.toml")?;
 
         // Write string to file
         fs::write(config_path, toml_content).with_context(|| {
@@ -94,30 +180,34 @@ impl Config {
         Ok(())
     }
 
-    /// Read and serialize a judo.toml file
-    pub fn read() -> Result<Self> {
-        // Use config directory to standardize storage of config file
-        let config_dir = dirs::config_dir().unwrap().join("judo");
-
-        // Define the config file path
-        let config_path = config_dir.join("judo.toml");
+    /// Path of the judo.toml file in the standard config directory
+    pub fn default_path() -> Result<PathBuf> {
+        let config_dir = dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not find config directory"))?
+            .join("judo");
+        Ok(config_dir.join("judo.toml"))
+    }
 
+    /// Read and serialize a judo.toml file
+    pub fn read(config_path: &Path) -> Result<Self> {
         // Create config if not existing
-        if !config_dir.exists() | !config_path.exists() {
+        if !config_path.exists() {
             // Create directory
-            std::fs::create_dir_all(&config_dir)
-                .with_context(|| "Failed to create config directory")?;
+            if let Some(config_dir) = config_path.parent() {
+                std::fs::create_dir_all(config_dir)
+                    .with_context(|| "Failed to create config directory")?;
+            }
 
             // Create default config
             let config = Self::default();
 
             // Create config file
             config
-                .write(&config_path)
+                .write(&config_path.to_path_buf())
                 .with_context(|| "Failed to create config file")?;
 
             // Create default config
-            return Ok(Self::default());
+            return Ok(config);
         }
 
         // Serialize judo.toml into YomoProject struct
@@ -129,16 +219,42 @@ impl Config {
         Ok(judo_config)
     }
 
-    pub fn foreground(&self) -> &str {
-        &self.colours.foreground
+    /// Replace judo.toml with a default one, keeping the old file as judo.toml.bak
+    ///
+    /// Earlier backups are never overwritten: later ones go to judo.toml.bak.1, .2 and so on.
+    pub fn recreate(config_path: &Path) -> Result<Self> {
+        if config_path.exists() {
+            let backup = Self::free_backup_path(config_path);
+            fs::rename(config_path, &backup).with_context(|| {
+                format!(
+                    "Failed to back up {} to {}",
+                    config_path.display(),
+                    backup.display()
+                )
+            })?;
+        }
+        Self::read(config_path)
     }
 
-    pub fn highlight(&self) -> &str {
-        &self.colours.highlight
+    /// First of judo.toml.bak, judo.toml.bak.1, judo.toml.bak.2... not taken yet
+    fn free_backup_path(config_path: &Path) -> PathBuf {
+        let backup = config_path.with_extension("toml.bak");
+        std::iter::once(backup.clone())
+            .chain((1..).map(|n| backup.with_extension(format!("bak.{}", n))))
+            .find(|path| !path.exists())
+            .expect("There is always a free backup name")
     }
 
-    pub fn background(&self) -> &str {
-        &self.colours.background
+    pub fn foreground(&self) -> Color {
+        self.colours.foreground()
+    }
+
+    pub fn highlight(&self) -> Color {
+        self.colours.highlight()
+    }
+
+    pub fn background(&self) -> Color {
+        self.colours.background()
     }
 
     pub fn get_db_by_name(self, name: String) -> Result<DBConfig> {
@@ -149,8 +265,74 @@ impl Config {
             }
         }
 
-        eprintln!("ERROR: No database found called '{}'", name);
-        std::process::exit(exitcode::DATAERR)
+        bail!(InvalidInput(format!("No database found called '{}'", name)))
+    }
+
+    /// Make a project-local database the default for this session
+    ///
+    /// If the same file is already configured, that entry is reused. Otherwise the
+    /// database is added for the session only and never written to judo.toml.
+    pub fn use_project_db(&mut self, project_db: DBConfig) {
+        let existing = self
+            .dbs
+            .iter()
+            .find(|db| db.connection_str == project_db.connection_str)
+            .map(|db| db.name.clone());
+
+        match existing {
+            Some(name) => self.override_default(name),
+            None => {
+                // Avoid clashing with the name of a configured database
+                let mut name = project_db.name.clone();
+                if self.has_db(&name) {
+                    name = format!("{} (project)", name);
+                }
+                self.dbs.push(DBConfig {
+                    name: name.clone(),
+                    connection_str: project_db.connection_str,
+                    sqlite: project_db.sqlite,
+                });
+                self.override_default(name.clone());
+                if let Some(session) = self.session.as_mut() {
+                    session.transient_db = Some(name);
+                }
+            }
+        }
+    }
+
+    /// Make the database with the given name the default for this session only
+    pub fn use_db(&mut self, name: String) -> Result<()> {
+        if !self.has_db(&name) {
+            anyhow::bail!("No database found called '{}'", name);
+        }
+        self.override_default(name);
+        Ok(())
+    }
+
+    /// Set the default database, both for this session and in judo.toml
+    pub fn set_default(&mut self, name: String) {
+        if let Some(session) = self.session.as_mut()
+            && session.transient_db.as_ref() != Some(&name)
+        {
+            session.global_default = name.clone();
+        }
+        self.default = name;
+    }
+
+    /// Change the default database without touching the one written to judo.toml
+    fn override_default(&mut self, name: String) {
+        if self.session.is_none() {
+            self.session = Some(SessionDefault {
+                global_default: self.default.clone(),
+                transient_db: None,
+            });
+        }
+        self.default = name;
+    }
+
+    /// Check whether a database with the given name is already configured
+    pub fn has_db(&self, name: &str) -> bool {
+        self.dbs.iter().any(|db| db.name == name)
     }
 
     /// Get config of default database
@@ -168,3 +350,26 @@ impl Config {
         }
     }
 }
+
+/// Turn a user-provided path into an absolute one
+///
+/// A leading `~` is expanded to the home directory and relative paths
+/// are resolved against the current working directory.
+pub fn resolve_path(path: &str) -> Result<PathBuf> {
+    let expanded = if path == "~" {
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
+            .join(rest)
+    } else {
+        PathBuf::from(path)
+    };
+
+    if expanded.is_absolute() {
+        return Ok(expanded);
+    }
+
+    let cwd = std::env::current_dir().with_context(|| "Failed to read current directory")?;
+    Ok(cwd.join(expanded))
+}
@@ -1,17 +1,70 @@
+use crate::db::config::{DBConfig, JournalMode, SqliteSettings, Synchronous};
 use anyhow::{Context, Result};
 use sqlx::migrate::Migrator;
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqlitePoolOptions, SqliteSynchronous,
+};
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// Connection options for a database with the given SQLite settings
+fn connect_options(
+    db_connection_str: &str,
+    settings: &SqliteSettings,
+) -> Result<SqliteConnectOptions> {
+    let journal_mode = match settings.journal_mode() {
+        JournalMode::Delete => SqliteJournalMode::Delete,
+        JournalMode::Truncate => SqliteJournalMode::Truncate,
+        JournalMode::Persist => SqliteJournalMode::Persist,
+        JournalMode::Memory => SqliteJournalMode::Memory,
+        JournalMode::Wal => SqliteJournalMode::Wal,
+        JournalMode::Off => SqliteJournalMode::Off,
+    };
+    let synchronous = match settings.synchronous() {
+        Synchronous::Off => SqliteSynchronous::Off,
+        Synchronous::Normal => SqliteSynchronous::Normal,
+        Synchronous::Full => SqliteSynchronous::Full,
+        Synchronous::Extra => SqliteSynchronous::Extra,
+    };
+
+    let opts = SqliteConnectOptions::from_str(db_connection_str)
+        .with_context(|| "Failed to create options for DB")?
+        .journal_mode(journal_mode)
+        .busy_timeout(settings.busy_timeout())
+        .foreign_keys(settings.foreign_keys())
+        .synchronous(synchronous);
+    Ok(opts)
+}
+
+/// Options of a pool holding one connection, open for as long as the pool
+///
+/// Judo does one thing at a time on a database, and a lasting connection is what
+/// lets a `ChangeWatcher` tell the app's own writes from everybody else's.
+fn pool_options() -> SqlitePoolOptions {
+    SqlitePoolOptions::new()
+        .max_connections(1)
+        .min_connections(1)
+        .idle_timeout(None)
+        .max_lifetime(None)
+}
 
 /// Create connection to SQLite DB pool and create DB if not present
 pub async fn get_db_pool(db_connection_str: &str) -> Result<SqlitePool> {
+    get_db_pool_with(db_connection_str, &SqliteSettings::default()).await
+}
+
+/// Create connection to SQLite DB pool with the given settings, creating the DB if not present
+pub async fn get_db_pool_with(
+    db_connection_str: &str,
+    settings: &SqliteSettings,
+) -> Result<SqlitePool> {
     // Create connection options
-    let opts = SqliteConnectOptions::from_str(db_connection_str)
-        .with_context(|| "Failed to create options for DB")?
-        .create_if_missing(true);
+    let opts = connect_options(db_connection_str, settings)?.create_if_missing(true);
 
     // Connect in a pool
-    let pool = SqlitePool::connect_with(opts)
+    let pool = pool_options()
+        .connect_with(opts)
         .await
         .with_context(|| "Failed to create DB pool")?;
 
@@ -34,7 +87,12 @@ async fn run_migrations(pool: &SqlitePool) -> Result<()> {
 /// Initialize database with connection and run migrations
 /// This is safe to call on every startup - migrations are idempotent
 pub async fn init_db(connection_str: &str) -> Result<SqlitePool> {
-    let pool = get_db_pool(connection_str).await?;
+    init_db_with(connection_str, &SqliteSettings::default()).await
+}
+
+/// Initialize database with connection using the given settings and run migrations
+pub async fn init_db_with(connection_str: &str, settings: &SqliteSettings) -> Result<SqlitePool> {
+    let pool = get_db_pool_with(connection_str, settings).await?;
 
     // Always run migrations on startup - they're idempotent and fast
     run_migrations(&pool).await?;
@@ -42,6 +100,139 @@ pub async fn init_db(connection_str: &str) -> Result<SqlitePool> {
     Ok(pool)
 }
 
+/// Check that a database already holds the judo schema
+pub async fn validate_judo_schema(pool: &SqlitePool) -> Result<()> {
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+    )
+    .fetch_all(pool)
+    .await
+    .with_context(|| "Failed to read database schema (is this a SQLite file?)")?;
+
+    // Empty and stray SQLite files alike are not judo databases
+    let has_table = |name: &str| tables.iter().any(|t| t == name);
+    if !(has_table("todo_lists") && has_table("todo_items")) {
+        anyhow::bail!("Not a judo database");
+    }
+
+    Ok(())
+}
+
+/// Open an existing judo database with the given settings and run pending migrations
+pub async fn open_existing_db(
+    connection_str: &str,
+    settings: &SqliteSettings,
+) -> Result<SqlitePool> {
+    // Never create the file: registering a missing database is a user error
+    let opts = connect_options(connection_str, settings)?.create_if_missing(false);
+
+    let pool = pool_options()
+        .connect_with(opts)
+        .await
+        .with_context(|| "Failed to open existing database")?;
+
+    validate_judo_schema(&pool).await?;
+    run_migrations(&pool).await?;
+
+    Ok(pool)
+}
+
+/// Pools of the databases opened so far, by database name
+///
+/// Each database is opened and migrated once, however many operations use it.
+/// Clones share the same pools.
+#[derive(Debug, Clone, Default)]
+pub struct PoolRegistry {
+    pools: Arc<Mutex<HashMap<String, SqlitePool>>>,
+}
+
+impl PoolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, SqlitePool>> {
+        // The map stays consistent even if a holder panicked
+        self.pools.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Keep an already opened and migrated pool for the given database
+    pub fn insert(&self, db: &DBConfig, pool: SqlitePool) {
+        self.lock().insert(db.name.clone(), pool);
+    }
+
+    /// Get the pool of a database, opening it and running migrations the first time
+    pub async fn get(&self, db: &DBConfig) -> Result<SqlitePool> {
+        if let Some(pool) = self.lock().get(&db.name) {
+            return Ok(pool.clone());
+        }
+        let pool = init_db_with(&db.connection_str, &db.sqlite)
+            .await
+            .with_context(|| format!("Failed to open database '{}'", db.name))?;
+
+        // Someone else may have opened it meanwhile, in which case theirs is kept
+        Ok(self.lock().entry(db.name.clone()).or_insert(pool).clone())
+    }
+
+    /// Number of databases opened so far
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no database was opened yet
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Close every pool, waiting for their connections to be released
+    pub async fn close_all(&self) {
+        let pools: Vec<SqlitePool> = self.lock().drain().map(|(_, pool)| pool).collect();
+        for pool in pools {
+            pool.close().await;
+        }
+    }
+}
+
+/// Notices changes committed to a database by other connections, such as the CLI
+///
+/// SQLite's `data_version` moves with every commit of another connection, but not
+/// with those of the connection it is read on. Reading it through the pool's only
+/// connection leaves the app's own writes out, without having to track them.
+#[derive(Debug)]
+pub struct ChangeWatcher {
+    pool: SqlitePool,
+    data_version: i64,
+}
+
+impl ChangeWatcher {
+    /// Start watching the database behind a single-connection pool from its current state
+    pub async fn new(pool: &SqlitePool) -> Result<Self> {
+        if pool.options().get_max_connections() != 1 {
+            anyhow::bail!("Changes can only be watched through a pool of a single connection");
+        }
+        let data_version = Self::read_data_version(pool).await?;
+        Ok(Self {
+            pool: pool.clone(),
+            data_version,
+        })
+    }
+
+    async fn read_data_version(pool: &SqlitePool) -> Result<i64> {
+        sqlx::query_scalar("PRAGMA data_version")
+            .fetch_one(pool)
+            .await
+            .with_context(|| "Failed to read data version")
+    }
+
+    /// Whether anything was committed elsewhere since the last call, or since the watcher started
+    pub async fn has_changed(&mut self) -> Result<bool> {
+        let data_version = Self::read_data_version(&self.pool).await?;
+        let changed = data_version != self.data_version;
+        self.data_version = data_version;
+        Ok(changed)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -61,4 +252,58 @@ mod test {
         run_migrations(&pool).await?;
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_pool_registry_opens_each_database_once() -> Result<()> {
+        let registry = PoolRegistry::new();
+        let db = DBConfig {
+            name: "memory".to_string(),
+            connection_str: "sqlite::memory:".to_string(),
+            sqlite: Default::default(),
+        };
+
+        // Migrations ran on the first use, and the second one gets the same pool
+        let pool = registry.get(&db).await?;
+        sqlx::query("INSERT INTO todo_lists (name, ordering, created_at, updated_at) VALUES ('Work', 1, '', '')")
+            .execute(&pool)
+            .await?;
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM todo_lists")
+            .fetch_one(&registry.get(&db).await?)
+            .await?;
+        assert_eq!(count, 1);
+        assert_eq!(registry.len(), 1);
+
+        // Clones share the pools
+        let other = DBConfig {
+            name: "other".to_string(),
+            connection_str: "sqlite::memory:".to_string(),
+            sqlite: Default::default(),
+        };
+        registry.clone().get(&other).await?;
+        assert_eq!(registry.len(), 2);
+
+        registry.close_all().await;
+        assert!(registry.is_empty());
+        assert!(pool.is_closed());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_validate_empty_and_migrated_schema() -> Result<()> {
+        let pool = get_db_pool("sqlite::memory:").await?;
+        assert!(validate_judo_schema(&pool).await.is_err());
+        run_migrations(&pool).await?;
+        validate_judo_schema(&pool).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_validate_foreign_schema() -> Result<()> {
+        let pool = get_db_pool("sqlite::memory:").await?;
+        sqlx::query("CREATE TABLE notes (id INTEGER PRIMARY KEY)")
+            .execute(&pool)
+            .await?;
+        assert!(validate_judo_schema(&pool).await.is_err());
+        Ok(())
+    }
 }
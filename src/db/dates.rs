@@ -0,0 +1,189 @@
+//! Due dates typed in plain words, e.g. `tomorrow`, `next fri`, `in 3 days`, `eow` or `oct 20`
+//!
+//! Dates are read relative to the local day and ignore case and extra spaces:
+//! - `today`, `tomorrow` (`tmr`), `yesterday`
+//! - `in 3 days`, `3 days`, `in a week`, `2 weeks`, `in 1 month`, or `3d`, `2w`, `1m`
+//! - `fri`, `friday`, `this fri`: the next Friday after today
+//! - `next fri`: the Friday of next week, weeks starting on Monday
+//! - `next week`, `next month`, `next year`: the first day of it
+//! - `eow`, `eom`, `eoy` (or `end of week`, ...): the last day of the current week, month or year
+//! - `oct 20`, `20 oct`, `october 20th`: the next October 20th from today on, or
+//!   `oct 20 2027` for another year
+//! - `2026-10-20`
+use chrono::{DateTime, Datelike, Duration, Local, Months, NaiveDate, Utc, Weekday};
+
+/// Read a date typed in plain words, relative to `today`
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let normalised = input.trim().to_lowercase();
+    let words: Vec<&str> = normalised.split_whitespace().collect();
+    parse_words(&words, today).ok_or_else(|| {
+        format!(
+            "invalid date '{}' (expected e.g. today, tomorrow, fri, next fri, in 3 days, 2w, eow, oct 20 or 2026-10-20)",
+            input.trim()
+        )
+    })
+}
+
+/// Read a date typed in plain words as the start of that local day, for due dates
+pub fn parse_due_date(input: &str) -> Result<DateTime<Utc>, String> {
+    parse_date(input, Local::now().date_naive()).map(start_of_day)
+}
+
+/// First instant of a local day, in UTC
+pub fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|start| start.and_local_timezone(Local).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+}
+
+fn parse_words(words: &[&str], today: NaiveDate) -> Option<NaiveDate> {
+    match words {
+        ["today" | "tod"] => Some(today),
+        ["tomorrow" | "tmr" | "tmrw"] => today.succ_opt(),
+        ["yesterday"] => today.pred_opt(),
+        ["eow"] | ["end", "of", "week"] => Some(start_of_week(today) + Duration::days(6)),
+        ["eom"] | ["end", "of", "month"] => start_of_month(today)
+            .checked_add_months(Months::new(1))?
+            .pred_opt(),
+        ["eoy"] | ["end", "of", "year"] => NaiveDate::from_ymd_opt(today.year(), 12, 31),
+        ["next", "week"] => Some(start_of_week(today) + Duration::weeks(1)),
+        ["next", "month"] => start_of_month(today).checked_add_months(Months::new(1)),
+        ["next", "year"] => NaiveDate::from_ymd_opt(today.year() + 1, 1, 1),
+        ["next", day] => {
+            let weekday = parse_weekday(day)?;
+            Some(start_of_week(today) + Duration::days(7 + weekday.num_days_from_monday() as i64))
+        }
+        ["this", day] | [day] if parse_weekday(day).is_some() => {
+            let weekday = parse_weekday(day)?;
+            let ahead = (weekday.num_days_from_monday() as i64
+                - today.weekday().num_days_from_monday() as i64)
+                .rem_euclid(7);
+            Some(today + Duration::days(if ahead == 0 { 7 } else { ahead }))
+        }
+        ["in", amount, unit] | [amount, unit] if parse_unit(unit).is_some() => {
+            add_offset(today, parse_amount(amount)?, parse_unit(unit)?)
+        }
+        ["in", offset] | [offset] if parse_compact_offset(offset).is_some() => {
+            let (amount, unit) = parse_compact_offset(offset)?;
+            add_offset(today, amount, unit)
+        }
+        [date] => NaiveDate::parse_from_str(date, "%Y-%m-%d").ok(),
+        [first, second] => {
+            let (month, day) = parse_month_day(first, second)?;
+            next_month_day(today, month, day)
+        }
+        [first, second, year] => {
+            let (month, day) = parse_month_day(first, second)?;
+            NaiveDate::from_ymd_opt(year.parse().ok()?, month, day)
+        }
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Unit {
+    Day,
+    Week,
+    Month,
+}
+
+fn parse_unit(unit: &str) -> Option<Unit> {
+    match unit {
+        "d" | "day" | "days" => Some(Unit::Day),
+        "w" | "week" | "weeks" => Some(Unit::Week),
+        "m" | "month" | "months" => Some(Unit::Month),
+        _ => None,
+    }
+}
+
+fn parse_amount(amount: &str) -> Option<u32> {
+    match amount {
+        "a" | "an" | "one" => Some(1),
+        _ => amount.parse().ok(),
+    }
+}
+
+/// Split offsets such as `3d` into their amount and unit
+fn parse_compact_offset(offset: &str) -> Option<(u32, Unit)> {
+    let split = offset.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = offset.split_at(split);
+    Some((amount.parse().ok()?, parse_unit(unit)?))
+}
+
+fn add_offset(today: NaiveDate, amount: u32, unit: Unit) -> Option<NaiveDate> {
+    match unit {
+        Unit::Day => today.checked_add_signed(Duration::days(amount.into())),
+        Unit::Week => today.checked_add_signed(Duration::weeks(amount.into())),
+        Unit::Month => today.checked_add_months(Months::new(amount)),
+    }
+}
+
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    match day {
+        "mon" | "monday" => Some(Weekday::Mon),
+        "tue" | "tues" | "tuesday" => Some(Weekday::Tue),
+        "wed" | "wednesday" => Some(Weekday::Wed),
+        "thu" | "thur" | "thurs" | "thursday" => Some(Weekday::Thu),
+        "fri" | "friday" => Some(Weekday::Fri),
+        "sat" | "saturday" => Some(Weekday::Sat),
+        "sun" | "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_month(month: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+    // Full names, or abbreviations of at least three letters such as `oct` or `sept`
+    MONTHS
+        .iter()
+        .position(|name| month.len() >= 3 && name.starts_with(month))
+        .map(|i| i as u32 + 1)
+}
+
+/// Read a day of the month, with or without an ordinal suffix such as `20th`
+fn parse_day(day: &str) -> Option<u32> {
+    let digits = ["st", "nd", "rd", "th"]
+        .iter()
+        .find_map(|suffix| day.strip_suffix(suffix))
+        .unwrap_or(day);
+    digits.parse().ok().filter(|day| (1..=31).contains(day))
+}
+
+/// Read a month and a day given in either order
+fn parse_month_day(first: &str, second: &str) -> Option<(u32, u32)> {
+    match (parse_month(first), parse_month(second)) {
+        (Some(month), None) => Some((month, parse_day(second)?)),
+        (None, Some(month)) => Some((month, parse_day(first)?)),
+        _ => None,
+    }
+}
+
+/// The next time a month and day come around, today included
+fn next_month_day(today: NaiveDate, month: u32, day: u32) -> Option<NaiveDate> {
+    // February 29th may be up to four years away
+    (today.year()..=today.year() + 4)
+        .filter_map(|year| NaiveDate::from_ymd_opt(year, month, day))
+        .find(|date| *date >= today)
+}
+
+fn start_of_week(today: NaiveDate) -> NaiveDate {
+    today - Duration::days(today.weekday().num_days_from_monday() as i64)
+}
+
+fn start_of_month(today: NaiveDate) -> NaiveDate {
+    today.with_day(1).unwrap_or(today)
+}
@@ -1,4 +1,9 @@
 pub mod config;
 pub mod connections;
+pub mod dates;
 pub mod models;
 pub mod ops;
+pub mod project;
+pub mod query;
+pub mod quick_add;
+pub mod theme;
@@ -1,6 +1,9 @@
-use chrono::{DateTime, Utc};
+use crate::db::query::Query;
+use chrono::{DateTime, NaiveDate, Utc};
 use ratatui::widgets::ListState;
 use sqlx::{FromRow, Type};
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Eq, Type)]
 #[sqlx(type_name = "TEXT", rename_all = "lowercase")]
@@ -10,11 +13,135 @@ pub enum Priority {
     Low,
 }
 
+impl Priority {
+    pub const ALL: [Priority; 3] = [Priority::High, Priority::Medium, Priority::Low];
+
+    /// Name used in the database, on the command line and in the UI
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
+        }
+    }
+
+    /// Rank of the priority, higher being more urgent
+    pub fn rank(&self) -> i64 {
+        match self {
+            Priority::High => 3,
+            Priority::Medium => 2,
+            Priority::Low => 1,
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalised = s.to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|priority| priority.as_str() == normalised)
+            .ok_or_else(|| {
+                format!(
+                    "unknown priority '{}' (expected one of: high, medium, low)",
+                    s
+                )
+            })
+    }
+}
+
+/// How the items of a list are sorted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+pub enum SortMode {
+    /// Order chosen by the user (the `ordering` field)
+    #[default]
+    Manual,
+    /// Highest priority first, items without priority last
+    Priority,
+    /// Earliest due date first, items without due date last
+    DueDate,
+    /// Oldest first
+    Created,
+    /// By name, ignoring case
+    Alphabetical,
+    /// Manual order, with completed items at the bottom
+    DoneLast,
+}
+
+impl SortMode {
+    pub const ALL: [SortMode; 6] = [
+        SortMode::Manual,
+        SortMode::Priority,
+        SortMode::DueDate,
+        SortMode::Created,
+        SortMode::Alphabetical,
+        SortMode::DoneLast,
+    ];
+
+    /// Name used in the database, on the command line and in the UI
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortMode::Manual => "manual",
+            SortMode::Priority => "priority",
+            SortMode::DueDate => "due_date",
+            SortMode::Created => "created",
+            SortMode::Alphabetical => "alphabetical",
+            SortMode::DoneLast => "done_last",
+        }
+    }
+
+    /// The mode after this one, wrapping around
+    pub fn next(&self) -> SortMode {
+        let i = Self::ALL.iter().position(|mode| mode == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+}
+
+impl fmt::Display for SortMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SortMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalised = s.to_lowercase().replace('-', "_");
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.as_str() == normalised)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(SortMode::as_str).collect();
+                format!(
+                    "unknown sort mode '{}' (expected one of: {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
 #[derive(Debug, FromRow, Clone)]
 pub struct TodoList {
     pub id: i64,
     pub name: String,
     pub ordering: i64,
+    pub hide_done: bool,
+    pub sort_mode: SortMode,
+    /// Columns of the list's board, comma separated (the last one holds completed items)
+    pub statuses: String,
+    pub archived_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -25,9 +152,34 @@ pub struct TodoItem {
     pub list_id: i64,
     pub name: String,
     pub is_done: bool,
+    /// Board column the item was last put in
+    pub status: String,
     pub priority: Option<Priority>,
     pub due_date: Option<DateTime<Utc>>,
+    /// Tags of the item, comma separated
+    pub tags: String,
     pub ordering: i64,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Saved filter shown as a virtual list of the items matching all of its criteria
+#[derive(Debug, FromRow, Clone)]
+pub struct SmartList {
+    pub id: i64,
+    pub name: String,
+    pub ordering: i64,
+    /// Only items with at least this priority
+    pub min_priority: Option<Priority>,
+    /// Only items due within this many days from today (overdue ones included)
+    pub due_within_days: Option<i64>,
+    /// Only completed (true) or pending (false) items
+    pub is_done: Option<bool>,
+    /// Only items with this tag
+    pub tag: Option<String>,
+    /// Only items whose name contains this text, ignoring case
+    pub text: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -46,10 +198,24 @@ pub struct NewTodoItem {
     pub due_date: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Default, Clone)]
+pub struct NewSmartList {
+    pub name: String,
+    pub min_priority: Option<Priority>,
+    pub due_within_days: Option<i64>,
+    pub is_done: Option<bool>,
+    pub tag: Option<String>,
+    pub text: Option<String>,
+}
+
 // Convenient repackaging of DB items to cache reads from DB
 #[derive(Debug, Clone)]
 pub struct UIList {
     pub list: TodoList,
+    /// Filter computing the items when this is a smart list rather than a real one
+    pub smart_list: Option<SmartList>,
+    /// Query narrowing down the items shown
+    pub filter: Option<Query>,
     pub item_state: ListState,
     pub items: Vec<UIItem>,
 }
@@ -59,3 +225,52 @@ pub struct UIItem {
     pub item: TodoItem,
     pub state: ListState,
 }
+
+// Deleted list or item waiting in the trash to be restored or purged
+#[derive(Debug, Clone)]
+pub enum TrashEntry {
+    List(TodoList),
+    Item { item: TodoItem, list_name: String },
+}
+
+// Item along with the name of its list, as shown in the agenda and query results
+#[derive(Debug, FromRow, Clone)]
+pub struct ItemWithList {
+    #[sqlx(flatten)]
+    pub item: TodoItem,
+    pub list_name: String,
+}
+
+/// Groups of the agenda, from the most to the least urgent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AgendaBucket {
+    Overdue,
+    Today,
+    Tomorrow,
+    /// Within the next 7 days
+    ThisWeek,
+    Later,
+}
+
+impl AgendaBucket {
+    /// Find the group of an item due on `due`, as seen on `today`
+    pub fn for_date(due: NaiveDate, today: NaiveDate) -> AgendaBucket {
+        match (due - today).num_days() {
+            ..0 => AgendaBucket::Overdue,
+            0 => AgendaBucket::Today,
+            1 => AgendaBucket::Tomorrow,
+            2..7 => AgendaBucket::ThisWeek,
+            _ => AgendaBucket::Later,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AgendaBucket::Overdue => "Overdue",
+            AgendaBucket::Today => "Today",
+            AgendaBucket::Tomorrow => "Tomorrow",
+            AgendaBucket::ThisWeek => "This week",
+            AgendaBucket::Later => "Later",
+        }
+    }
+}
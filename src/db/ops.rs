@@ -1,32 +1,108 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
-use sqlx::SqlitePool;
-
-use crate::db::models::{NewTodoItem, NewTodoList, Priority, TodoItem, TodoList, UIItem, UIList};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{Sqlite, SqliteConnection, SqlitePool, Transaction};
+
+use crate::db::models::{
+    AgendaBucket, ItemWithList, NewSmartList, NewTodoItem, NewTodoList, Priority, SmartList,
+    SortMode, TodoItem, TodoList, TrashEntry, UIItem, UIList,
+};
+use crate::db::query::Query;
 use ratatui::widgets::ListState;
+use std::collections::HashMap;
+
+/// Start a transaction holding the write lock, so the orderings it reads stay valid until it commits
+async fn begin_write(pool: &SqlitePool) -> Result<Transaction<'static, Sqlite>> {
+    pool.begin_with("BEGIN IMMEDIATE")
+        .await
+        .with_context(|| "Failed to start transaction")
+}
+
+/// Swap the orderings of two rows of a table, given as (id, ordering)
+async fn swap_orderings(
+    conn: &mut SqliteConnection,
+    table: &str,
+    (id, ordering): (i64, i64),
+    (other_id, other_ordering): (i64, i64),
+) -> Result<()> {
+    // Orderings are unique, so the first row steps aside to a negative value no row uses
+    let sql = format!("UPDATE {} SET ordering = ?1 WHERE id = ?2", table);
+    for (row, value) in [(id, -id), (other_id, ordering), (id, other_ordering)] {
+        sqlx::query(&sql)
+            .bind(value)
+            .bind(row)
+            .execute(&mut *conn)
+            .await
+            .with_context(|| format!("Failed to update ordering in {}", table))?;
+    }
+    Ok(())
+}
+
+/// Tags as stored: trimmed, without a leading '#', deduplicated and comma separated
+fn join_tags(tags: Vec<String>) -> Result<String> {
+    let mut cleaned: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim().trim_start_matches('#').to_string();
+        if tag.contains(',') {
+            anyhow::bail!("Tags can't contain commas");
+        }
+        if !tag.is_empty() && !cleaned.contains(&tag) {
+            cleaned.push(tag);
+        }
+    }
+    Ok(cleaned.join(","))
+}
+
+/// Put the given rows of a table in the given order, reusing the orderings they already hold
+///
+/// Rows left out keep their ordering, so items in the trash or archived lists stay put.
+async fn reorder_rows(conn: &mut SqliteConnection, table: &str, ids: &[i64]) -> Result<()> {
+    let mut orderings = Vec::with_capacity(ids.len());
+    let select = format!("SELECT ordering FROM {} WHERE id = ?1", table);
+    for id in ids {
+        let ordering: i64 = sqlx::query_scalar(&select)
+            .bind(id)
+            .fetch_one(&mut *conn)
+            .await
+            .with_context(|| format!("Failed to read ordering in {}", table))?;
+        orderings.push(ordering);
+    }
+    orderings.sort();
+
+    // Go through negative values so no two rows ever share an ordering
+    let update = format!("UPDATE {} SET ordering = ?1 WHERE id = ?2", table);
+    for (i, id) in ids.iter().enumerate() {
+        sqlx::query(&update)
+            .bind(-(i as i64) - 1)
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .with_context(|| format!("Failed to update ordering in {}", table))?;
+    }
+    for (id, ordering) in ids.iter().zip(orderings) {
+        sqlx::query(&update)
+            .bind(ordering)
+            .bind(id)
+            .execute(&mut *conn)
+            .await
+            .with_context(|| format!("Failed to update ordering in {}", table))?;
+    }
+    Ok(())
+}
 
 impl TodoList {
     /// Create a new todo list
     pub async fn create(pool: &SqlitePool, new_list: NewTodoList) -> Result<TodoList> {
         let now = Utc::now();
 
-        // Get the next ordering value (max + 1)
-        let next_ordering: i64 =
-            sqlx::query_scalar("SELECT COALESCE(MAX(ordering), 0) + 1 FROM todo_lists")
-                .fetch_one(pool)
-                .await
-                .with_context(|| "Failed to get next ordering value")?;
-
-        // Use query_as to map results to a struct
+        // The next ordering value (max + 1) is taken in the same statement, so concurrent creates can't share it
         let row = sqlx::query_as::<_, TodoList>(
             r#"
             INSERT INTO todo_lists (name, ordering, created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4)
-            RETURNING id, name, ordering, created_at, updated_at
+            VALUES (?1, (SELECT COALESCE(MAX(ordering), 0) + 1 FROM todo_lists), ?2, ?3)
+            RETURNING id, name, ordering, hide_done, sort_mode, statuses, archived_at, deleted_at, created_at, updated_at
             "#,
         )
         .bind(&new_list.name)
-        .bind(next_ordering)
         .bind(now)
         .bind(now)
         .fetch_one(pool)
@@ -36,10 +112,15 @@ impl TodoList {
         Ok(row)
     }
 
-    /// Get all todo lists
+    /// Get all todo lists that are neither archived nor deleted
     pub async fn get_all(pool: &SqlitePool) -> Result<Vec<TodoList>> {
         let lists = sqlx::query_as::<_, TodoList>(
-            "SELECT id, name, ordering, created_at, updated_at FROM todo_lists ORDER BY ordering",
+            r#"
+            SELECT id, name, ordering, hide_done, sort_mode, statuses, archived_at, deleted_at, created_at, updated_at
+            FROM todo_lists
+            WHERE archived_at IS NULL AND deleted_at IS NULL
+            ORDER BY ordering
+            "#,
         )
         .fetch_all(pool)
         .await
@@ -48,10 +129,27 @@ impl TodoList {
         Ok(lists)
     }
 
-    /// Get a specific todo list by ID
+    /// Get all archived todo lists, most recently archived first
+    pub async fn get_archived(pool: &SqlitePool) -> Result<Vec<TodoList>> {
+        let lists = sqlx::query_as::<_, TodoList>(
+            r#"
+            SELECT id, name, ordering, hide_done, sort_mode, statuses, archived_at, deleted_at, created_at, updated_at
+            FROM todo_lists
+            WHERE archived_at IS NOT NULL AND deleted_at IS NULL
+            ORDER BY archived_at DESC
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .with_context(|| "Failed to fetch archived todo lists")?;
+
+        Ok(lists)
+    }
+
+    /// Get a specific todo list by ID, unless it is in the trash
     pub async fn get_by_id(pool: &SqlitePool, id: i64) -> Result<Option<TodoList>> {
         let list = sqlx::query_as::<_, TodoList>(
-            "SELECT id, name, ordering, created_at, updated_at FROM todo_lists WHERE id = ?1",
+            "SELECT id, name, ordering, hide_done, sort_mode, statuses, archived_at, deleted_at, created_at, updated_at FROM todo_lists WHERE id = ?1 AND deleted_at IS NULL",
         )
         .bind(id)
         .fetch_optional(pool)
@@ -78,9 +176,233 @@ impl TodoList {
         Ok(())
     }
 
-    /// Delete todo list (and all its items due to CASCADE)
+    /// Show or hide completed items of the list
+    pub async fn set_hide_done(&mut self, pool: &SqlitePool, hide_done: bool) -> Result<()> {
+        let now = Utc::now();
+
+        sqlx::query("UPDATE todo_lists SET hide_done = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(hide_done)
+            .bind(now)
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to update todo list visibility of done items")?;
+
+        self.hide_done = hide_done;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Change how the items of the list are sorted
+    pub async fn set_sort_mode(&mut self, pool: &SqlitePool, sort_mode: SortMode) -> Result<()> {
+        let now = Utc::now();
+
+        sqlx::query("UPDATE todo_lists SET sort_mode = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(sort_mode)
+            .bind(now)
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to update todo list sort mode")?;
+
+        self.sort_mode = sort_mode;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Make the current sort order the manual one and switch the list back to manual sorting
+    ///
+    /// This lets items be reordered by hand starting from what is on screen.
+    pub async fn rebaseline_ordering(&mut self, pool: &SqlitePool) -> Result<()> {
+        if self.sort_mode == SortMode::Manual {
+            return Ok(());
+        }
+
+        let mut tx = begin_write(pool).await?;
+        self.rebaseline_ordering_in(&mut tx).await?;
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
+
+    /// Switch the list to manual sorting from what it shows, then move one of its items
+    /// to a position, 0 being the top
+    ///
+    /// Both happen in one transaction, so nothing can change the list in between.
+    pub async fn move_item_to_position(
+        &mut self,
+        pool: &SqlitePool,
+        item: &mut TodoItem,
+        position: usize,
+    ) -> Result<()> {
+        let mut tx = begin_write(pool).await?;
+        if self.sort_mode != SortMode::Manual {
+            self.rebaseline_ordering_in(&mut tx).await?;
+        }
+        item.move_to_position_in(&mut tx, position).await?;
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
+
+    async fn rebaseline_ordering_in(&mut self, conn: &mut SqliteConnection) -> Result<()> {
+        let now = Utc::now();
+        let mut items = sqlx::query_as::<_, TodoItem>(
+            r#"
+            SELECT id, list_id, name, is_done, status, priority, due_date, tags, ordering, deleted_at, created_at, updated_at
+            FROM todo_items
+            WHERE list_id = ?1 AND deleted_at IS NULL
+            ORDER BY ordering
+            "#,
+        )
+        .bind(self.id)
+        .fetch_all(&mut *conn)
+        .await
+        .with_context(|| "Failed to fetch todo items")?;
+        UIList::sort_items(self.sort_mode, &mut items);
+
+        let ids: Vec<i64> = items.iter().map(|item| item.id).collect();
+        reorder_rows(conn, "todo_items", &ids).await?;
+
+        sqlx::query("UPDATE todo_lists SET sort_mode = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(SortMode::Manual)
+            .bind(now)
+            .bind(self.id)
+            .execute(&mut *conn)
+            .await
+            .with_context(|| "Failed to update todo list sort mode")?;
+
+        self.sort_mode = SortMode::Manual;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Columns of the list's board, in order
+    pub fn statuses(&self) -> Vec<String> {
+        self.statuses
+            .split(',')
+            .map(|status| status.trim().to_string())
+            .filter(|status| !status.is_empty())
+            .collect()
+    }
+
+    /// Change the columns of the list's board
+    ///
+    /// At least two distinct columns are needed: the last one holds completed items.
+    pub async fn set_statuses(&mut self, pool: &SqlitePool, statuses: Vec<String>) -> Result<()> {
+        let statuses: Vec<String> = statuses
+            .iter()
+            .map(|status| status.trim().to_string())
+            .filter(|status| !status.is_empty())
+            .collect();
+        if statuses.len() < 2 {
+            anyhow::bail!("A board needs at least two columns");
+        }
+        if statuses.iter().any(|status| status.contains(',')) {
+            anyhow::bail!("Column names can't contain commas");
+        }
+        for (i, status) in statuses.iter().enumerate() {
+            if statuses[..i].contains(status) {
+                anyhow::bail!("Column '{}' is listed more than once", status);
+            }
+        }
+
+        let now = Utc::now();
+        let joined = statuses.join(",");
+
+        sqlx::query("UPDATE todo_lists SET statuses = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(&joined)
+            .bind(now)
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to update todo list columns")?;
+
+        self.statuses = joined;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Get the items of the list grouped by board column, including completed ones
+    pub async fn get_board(&self, pool: &SqlitePool) -> Result<Vec<Vec<TodoItem>>> {
+        let statuses = self.statuses();
+        let mut items = TodoItem::get_by_list_id(pool, self.id).await?;
+        UIList::sort_items(self.sort_mode, &mut items);
+
+        let mut columns = vec![Vec::new(); statuses.len()];
+        for item in items {
+            let column = item.board_column(&statuses);
+            columns[column].push(item);
+        }
+        Ok(columns)
+    }
+
+    /// Move all completed items of the list to the trash in a single transaction
+    ///
+    /// Returns the number of deleted items.
+    pub async fn clear_done(&self, pool: &SqlitePool) -> Result<u64> {
+        let now = Utc::now();
+        let mut tx = pool
+            .begin()
+            .await
+            .with_context(|| "Failed to start transaction")?;
+
+        let deleted = sqlx::query(
+            "UPDATE todo_items SET deleted_at = ?1 WHERE list_id = ?2 AND is_done = TRUE AND deleted_at IS NULL",
+        )
+        .bind(now)
+        .bind(self.id)
+        .execute(&mut *tx)
+        .await
+        .with_context(|| "Failed to delete completed todo items")?
+        .rows_affected();
+
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+
+        Ok(deleted)
+    }
+
+    /// Archive todo list, hiding it (and its items) from the active lists
+    pub async fn archive(&mut self, pool: &SqlitePool) -> Result<()> {
+        let now = Utc::now();
+
+        sqlx::query("UPDATE todo_lists SET archived_at = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(now)
+            .bind(now)
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to archive todo list")?;
+
+        self.archived_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Restore an archived todo list to its original position
+    pub async fn unarchive(&mut self, pool: &SqlitePool) -> Result<()> {
+        let now = Utc::now();
+
+        sqlx::query("UPDATE todo_lists SET archived_at = NULL, updated_at = ?1 WHERE id = ?2")
+            .bind(now)
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to unarchive todo list")?;
+
+        self.archived_at = None;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Move todo list (and with it all its items) to the trash
     pub async fn delete(self, pool: &SqlitePool) -> Result<()> {
-        sqlx::query("DELETE FROM todo_lists WHERE id = ?1")
+        sqlx::query("UPDATE todo_lists SET deleted_at = ?1 WHERE id = ?2")
+            .bind(Utc::now())
             .bind(self.id)
             .execute(pool)
             .await
@@ -89,72 +411,121 @@ impl TodoList {
         Ok(())
     }
 
+    /// Permanently delete todo list (and all its items due to CASCADE)
+    pub async fn purge(self, pool: &SqlitePool) -> Result<()> {
+        sqlx::query("DELETE FROM todo_lists WHERE id = ?1")
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to purge todo list")?;
+
+        Ok(())
+    }
+
     /// Move list up (decrease ordering, swap with previous)
     pub async fn move_up(&mut self, pool: &SqlitePool) -> Result<()> {
+        let mut tx = begin_write(pool).await?;
+
+        // Another process may have moved the list since it was loaded
+        let ordering: i64 = sqlx::query_scalar("SELECT ordering FROM todo_lists WHERE id = ?1")
+            .bind(self.id)
+            .fetch_one(&mut *tx)
+            .await
+            .with_context(|| "Failed to read list ordering")?;
+        self.ordering = ordering;
+
         // Find the list with the next lower ordering value
         let prev_list: Option<(i64, i64)> = sqlx::query_as(
-            "SELECT id, ordering FROM todo_lists WHERE ordering < ?1 ORDER BY ordering DESC LIMIT 1"
+            "SELECT id, ordering FROM todo_lists WHERE ordering < ?1 AND archived_at IS NULL AND deleted_at IS NULL ORDER BY ordering DESC LIMIT 1"
         )
-        .bind(self.ordering)
-        .fetch_optional(pool)
+        .bind(ordering)
+        .fetch_optional(&mut *tx)
         .await
         .with_context(|| "Failed to find previous list")?;
 
-        if let Some((prev_id, prev_ordering)) = prev_list {
-            // Swap orderings
-            sqlx::query("UPDATE todo_lists SET ordering = ?1 WHERE id = ?2")
-                .bind(self.ordering)
-                .bind(prev_id)
-                .execute(pool)
-                .await
-                .with_context(|| "Failed to update previous list ordering")?;
-
-            sqlx::query("UPDATE todo_lists SET ordering = ?1 WHERE id = ?2")
-                .bind(prev_ordering)
-                .bind(self.id)
-                .execute(pool)
-                .await
-                .with_context(|| "Failed to update current list ordering")?;
-
-            self.ordering = prev_ordering;
+        if let Some(prev_list) = prev_list {
+            swap_orderings(&mut tx, "todo_lists", (self.id, ordering), prev_list).await?;
+            self.ordering = prev_list.1;
         }
 
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
         Ok(())
     }
 
     /// Move list down (increase ordering, swap with next)
     pub async fn move_down(&mut self, pool: &SqlitePool) -> Result<()> {
+        let mut tx = begin_write(pool).await?;
+
+        // Another process may have moved the list since it was loaded
+        let ordering: i64 = sqlx::query_scalar("SELECT ordering FROM todo_lists WHERE id = ?1")
+            .bind(self.id)
+            .fetch_one(&mut *tx)
+            .await
+            .with_context(|| "Failed to read list ordering")?;
+        self.ordering = ordering;
+
         // Find the list with the next higher ordering value
         let next_list: Option<(i64, i64)> = sqlx::query_as(
-            "SELECT id, ordering FROM todo_lists WHERE ordering > ?1 ORDER BY ordering ASC LIMIT 1",
+            "SELECT id, ordering FROM todo_lists WHERE ordering > ?1 AND archived_at IS NULL AND deleted_at IS NULL ORDER BY ordering ASC LIMIT 1"
         )
-        .bind(self.ordering)
-        .fetch_optional(pool)
+        .bind(ordering)
+        .fetch_optional(&mut *tx)
         .await
         .with_context(|| "Failed to find next list")?;
 
-        if let Some((next_id, next_ordering)) = next_list {
-            // Swap orderings
-            sqlx::query("UPDATE todo_lists SET ordering = ?1 WHERE id = ?2")
-                .bind(self.ordering)
-                .bind(next_id)
-                .execute(pool)
-                .await
-                .with_context(|| "Failed to update next list ordering")?;
+        if let Some(next_list) = next_list {
+            swap_orderings(&mut tx, "todo_lists", (self.id, ordering), next_list).await?;
+            self.ordering = next_list.1;
+        }
 
-            sqlx::query("UPDATE todo_lists SET ordering = ?1 WHERE id = ?2")
-                .bind(next_ordering)
-                .bind(self.id)
-                .execute(pool)
-                .await
-                .with_context(|| "Failed to update current list ordering")?;
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
 
-            self.ordering = next_ordering;
-        }
+    /// Move the list to a position among the active lists, 0 being the top
+    ///
+    /// Positions past the end move it to the bottom.
+    pub async fn move_to_position(&mut self, pool: &SqlitePool, position: usize) -> Result<()> {
+        let mut tx = begin_write(pool).await?;
+
+        let mut ids: Vec<i64> = sqlx::query_scalar(
+            "SELECT id FROM todo_lists WHERE id != ?1 AND archived_at IS NULL AND deleted_at IS NULL ORDER BY ordering",
+        )
+        .bind(self.id)
+        .fetch_all(&mut *tx)
+        .await
+        .with_context(|| "Failed to fetch todo lists")?;
+        let position = position.min(ids.len());
+        ids.insert(position, self.id);
+        reorder_rows(&mut tx, "todo_lists", &ids).await?;
 
+        self.ordering = sqlx::query_scalar("SELECT ordering FROM todo_lists WHERE id = ?1")
+            .bind(self.id)
+            .fetch_one(&mut *tx)
+            .await
+            .with_context(|| "Failed to read list ordering")?;
+
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
         Ok(())
     }
 
+    /// Number of items outside the trash of every list holding any, by list ID
+    pub async fn count_items(pool: &SqlitePool) -> Result<HashMap<i64, i64>> {
+        let counts: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT list_id, COUNT(*) FROM todo_items WHERE deleted_at IS NULL GROUP BY list_id",
+        )
+        .fetch_all(pool)
+        .await
+        .with_context(|| "Failed to count todo items")?;
+        Ok(counts.into_iter().collect())
+    }
+
     pub async fn get_all_items(&self, pool: &SqlitePool) -> Result<Vec<TodoItem>> {
         TodoItem::get_by_list_id(pool, self.id).await
     }
@@ -163,29 +534,37 @@ impl TodoList {
 impl TodoItem {
     /// Create a new todo item
     pub async fn create(pool: &SqlitePool, new_item: NewTodoItem) -> Result<TodoItem> {
-        let now = Utc::now();
+        Self::create_with_tags(pool, new_item, Vec::new()).await
+    }
 
-        // Get the next ordering value for this list (max + 1)
-        let next_ordering: i64 = sqlx::query_scalar(
-            "SELECT COALESCE(MAX(ordering), 0) + 1 FROM todo_items WHERE list_id = ?1",
-        )
-        .bind(new_item.list_id)
-        .fetch_one(pool)
-        .await
-        .with_context(|| "Failed to get next ordering value")?;
+    /// Create a new item already carrying the given tags
+    ///
+    /// The tags go in with the item, so a bad tag leaves no untagged item behind.
+    pub async fn create_with_tags(
+        pool: &SqlitePool,
+        new_item: NewTodoItem,
+        tags: Vec<String>,
+    ) -> Result<TodoItem> {
+        let tags = join_tags(tags)?;
+        let now = Utc::now();
 
+        // The next ordering value of the list (max + 1) is taken in the same statement, so concurrent creates can't share it
         let row = sqlx::query_as::<_, TodoItem>(
             r#"
-            INSERT INTO todo_items (list_id, name, is_done, priority, due_date, ordering, created_at, updated_at)
-            VALUES (?1, ?2, FALSE, ?3, ?4, ?5, ?6, ?7)
-            RETURNING id, list_id, name, is_done, priority, due_date, ordering, created_at, updated_at
+            INSERT INTO todo_items (list_id, name, is_done, priority, due_date, tags, ordering, created_at, updated_at)
+            VALUES (
+                ?1, ?2, FALSE, ?3, ?4, ?5,
+                (SELECT COALESCE(MAX(ordering), 0) + 1 FROM todo_items WHERE list_id = ?1),
+                ?6, ?7
+            )
+            RETURNING id, list_id, name, is_done, status, priority, due_date, tags, ordering, deleted_at, created_at, updated_at
             "#,
         )
         .bind(new_item.list_id)
         .bind(&new_item.name)
         .bind(&new_item.priority)
         .bind(new_item.due_date)
-        .bind(next_ordering)
+        .bind(&tags)
         .bind(now)
         .bind(now)
         .fetch_one(pool)
@@ -195,13 +574,13 @@ impl TodoItem {
         Ok(row)
     }
 
-    /// Get all items for a specific list
+    /// Get all items for a specific list, leaving out the ones in the trash
     pub async fn get_by_list_id(pool: &SqlitePool, list_id: i64) -> Result<Vec<TodoItem>> {
         let items = sqlx::query_as::<_, TodoItem>(
             r#"
-            SELECT id, list_id, name, is_done, priority, due_date, ordering, created_at, updated_at
+            SELECT id, list_id, name, is_done, status, priority, due_date, tags, ordering, deleted_at, created_at, updated_at
             FROM todo_items 
-            WHERE list_id = ?1 
+            WHERE list_id = ?1 AND deleted_at IS NULL
             ORDER BY ordering
             "#,
         )
@@ -213,13 +592,14 @@ impl TodoItem {
         Ok(items)
     }
 
-    /// Get item with a specific id
+    /// Get item with a specific id, unless it (or its list) is in the trash
     pub async fn get_by_id(pool: &SqlitePool, id: i64) -> Result<Option<TodoItem>> {
         let item = sqlx::query_as::<_, TodoItem>(
             r#"
-            SELECT id, list_id, name, is_done, priority, due_date, ordering, created_at, updated_at
+            SELECT id, list_id, name, is_done, status, priority, due_date, tags, ordering, deleted_at, created_at, updated_at
             FROM todo_items 
-            WHERE id = ?1 
+            WHERE id = ?1 AND deleted_at IS NULL
+              AND list_id IN (SELECT id FROM todo_lists WHERE deleted_at IS NULL)
             "#,
         )
         .bind(id)
@@ -267,6 +647,90 @@ impl TodoItem {
         Ok(())
     }
 
+    /// Board column the item belongs in, given the columns of its list
+    ///
+    /// Completed items always sit in the last column, and items whose status
+    /// is not (or no longer) one of the other columns sit in the first one.
+    pub fn board_column(&self, statuses: &[String]) -> usize {
+        let last = statuses.len().saturating_sub(1);
+        if self.is_done {
+            return last;
+        }
+        statuses[..last]
+            .iter()
+            .position(|status| status == &self.status)
+            .unwrap_or(0)
+    }
+
+    /// Move item to a column of its list's board
+    ///
+    /// Moving into the last column completes the item, moving out of it reopens it.
+    pub async fn move_to_column(
+        &mut self,
+        pool: &SqlitePool,
+        statuses: &[String],
+        column: usize,
+    ) -> Result<()> {
+        let Some(status) = statuses.get(column) else {
+            anyhow::bail!("No column {} on the board", column);
+        };
+        let now = Utc::now();
+        let is_done = column + 1 == statuses.len();
+
+        sqlx::query(
+            "UPDATE todo_items SET status = ?1, is_done = ?2, updated_at = ?3 WHERE id = ?4",
+        )
+        .bind(status)
+        .bind(is_done)
+        .bind(now)
+        .bind(self.id)
+        .execute(pool)
+        .await
+        .with_context(|| "Failed to update todo item status")?;
+
+        self.status = status.clone();
+        self.is_done = is_done;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Whether the item is still pending after the day it was due, as seen on `today`
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        !self.is_done
+            && self
+                .due_date
+                .is_some_and(|date| date.with_timezone(&chrono::Local).date_naive() < today)
+    }
+
+    /// Tags of the item, in order
+    pub fn tags(&self) -> Vec<String> {
+        self.tags
+            .split(',')
+            .map(|tag| tag.trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect()
+    }
+
+    /// Replace the tags of the item
+    ///
+    /// A leading `#` is dropped and duplicates are ignored.
+    pub async fn set_tags(&mut self, pool: &SqlitePool, tags: Vec<String>) -> Result<()> {
+        let joined = join_tags(tags)?;
+        let now = Utc::now();
+
+        sqlx::query("UPDATE todo_items SET tags = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(&joined)
+            .bind(now)
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to update todo item tags")?;
+
+        self.tags = joined;
+        self.updated_at = now;
+        Ok(())
+    }
+
     /// Update item priority
     pub async fn update_priority(
         &mut self,
@@ -310,9 +774,26 @@ impl TodoItem {
         Ok(())
     }
 
-    /// Delete todo item
+    /// Remove the due date of the item
+    pub async fn clear_due_date(&mut self, pool: &SqlitePool) -> Result<()> {
+        let now = Utc::now();
+
+        sqlx::query("UPDATE todo_items SET due_date = NULL, updated_at = ?1 WHERE id = ?2")
+            .bind(now)
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to clear todo item due date")?;
+
+        self.due_date = None;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Move todo item to the trash
     pub async fn delete(self, pool: &SqlitePool) -> Result<()> {
-        sqlx::query("DELETE FROM todo_items WHERE id = ?1")
+        sqlx::query("UPDATE todo_items SET deleted_at = ?1 WHERE id = ?2")
+            .bind(Utc::now())
             .bind(self.id)
             .execute(pool)
             .await
@@ -321,99 +802,201 @@ impl TodoItem {
         Ok(())
     }
 
+    /// Permanently delete todo item
+    pub async fn purge(self, pool: &SqlitePool) -> Result<()> {
+        sqlx::query("DELETE FROM todo_items WHERE id = ?1")
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to purge todo item")?;
+
+        Ok(())
+    }
+
     /// Move item up (decrease ordering, swap with previous in same list)
     pub async fn move_up(&mut self, pool: &SqlitePool) -> Result<()> {
+        let mut tx = begin_write(pool).await?;
+
+        // Another process may have moved the item since it was loaded
+        let ordering: i64 = sqlx::query_scalar("SELECT ordering FROM todo_items WHERE id = ?1")
+            .bind(self.id)
+            .fetch_one(&mut *tx)
+            .await
+            .with_context(|| "Failed to read item ordering")?;
+        self.ordering = ordering;
+
         // Find the item with the next lower ordering value in the same list
         let prev_item: Option<(i64, i64)> = sqlx::query_as(
-            "SELECT id, ordering FROM todo_items WHERE list_id = ?1 AND ordering < ?2 ORDER BY ordering DESC LIMIT 1"
+            "SELECT id, ordering FROM todo_items WHERE list_id = ?1 AND ordering < ?2 AND deleted_at IS NULL ORDER BY ordering DESC LIMIT 1"
         )
         .bind(self.list_id)
-        .bind(self.ordering)
-        .fetch_optional(pool)
+        .bind(ordering)
+        .fetch_optional(&mut *tx)
         .await
         .with_context(|| "Failed to find previous item")?;
 
-        if let Some((prev_id, prev_ordering)) = prev_item {
-            // Swap orderings
-            sqlx::query("UPDATE todo_items SET ordering = ?1 WHERE id = ?2")
-                .bind(self.ordering)
-                .bind(prev_id)
-                .execute(pool)
-                .await
-                .with_context(|| "Failed to update previous item ordering")?;
-
-            sqlx::query("UPDATE todo_items SET ordering = ?1 WHERE id = ?2")
-                .bind(prev_ordering)
-                .bind(self.id)
-                .execute(pool)
-                .await
-                .with_context(|| "Failed to update current item ordering")?;
-
-            self.ordering = prev_ordering;
+        if let Some(prev_item) = prev_item {
+            swap_orderings(&mut tx, "todo_items", (self.id, ordering), prev_item).await?;
+            self.ordering = prev_item.1;
         }
 
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
         Ok(())
     }
 
     /// Move item down (increase ordering, swap with next in same list)
     pub async fn move_down(&mut self, pool: &SqlitePool) -> Result<()> {
+        let mut tx = begin_write(pool).await?;
+
+        // Another process may have moved the item since it was loaded
+        let ordering: i64 = sqlx::query_scalar("SELECT ordering FROM todo_items WHERE id = ?1")
+            .bind(self.id)
+            .fetch_one(&mut *tx)
+            .await
+            .with_context(|| "Failed to read item ordering")?;
+        self.ordering = ordering;
+
         // Find the item with the next higher ordering value in the same list
         let next_item: Option<(i64, i64)> = sqlx::query_as(
-            "SELECT id, ordering FROM todo_items WHERE list_id = ?1 AND ordering > ?2 ORDER BY ordering ASC LIMIT 1"
+            "SELECT id, ordering FROM todo_items WHERE list_id = ?1 AND ordering > ?2 AND deleted_at IS NULL ORDER BY ordering ASC LIMIT 1"
         )
         .bind(self.list_id)
-        .bind(self.ordering)
-        .fetch_optional(pool)
+        .bind(ordering)
+        .fetch_optional(&mut *tx)
         .await
         .with_context(|| "Failed to find next item")?;
 
-        if let Some((next_id, next_ordering)) = next_item {
-            // Swap orderings
-            sqlx::query("UPDATE todo_items SET ordering = ?1 WHERE id = ?2")
-                .bind(self.ordering)
-                .bind(next_id)
-                .execute(pool)
-                .await
-                .with_context(|| "Failed to update next item ordering")?;
+        if let Some(next_item) = next_item {
+            swap_orderings(&mut tx, "todo_items", (self.id, ordering), next_item).await?;
+            self.ordering = next_item.1;
+        }
 
-            sqlx::query("UPDATE todo_items SET ordering = ?1 WHERE id = ?2")
-                .bind(next_ordering)
-                .bind(self.id)
-                .execute(pool)
-                .await
-                .with_context(|| "Failed to update current item ordering")?;
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
 
-            self.ordering = next_ordering;
-        }
+    /// Move the item to a position among the items of its list, 0 being the top
+    ///
+    /// Positions past the end move it to the bottom.
+    pub async fn move_to_position(&mut self, pool: &SqlitePool, position: usize) -> Result<()> {
+        let mut tx = begin_write(pool).await?;
+        self.move_to_position_in(&mut tx, position).await?;
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+        Ok(())
+    }
+
+    async fn move_to_position_in(
+        &mut self,
+        conn: &mut SqliteConnection,
+        position: usize,
+    ) -> Result<()> {
+        let mut ids: Vec<i64> = sqlx::query_scalar(
+            "SELECT id FROM todo_items WHERE list_id = ?1 AND id != ?2 AND deleted_at IS NULL ORDER BY ordering",
+        )
+        .bind(self.list_id)
+        .bind(self.id)
+        .fetch_all(&mut *conn)
+        .await
+        .with_context(|| "Failed to fetch todo items")?;
+        let position = position.min(ids.len());
+        ids.insert(position, self.id);
+        reorder_rows(conn, "todo_items", &ids).await?;
 
+        self.ordering = sqlx::query_scalar("SELECT ordering FROM todo_items WHERE id = ?1")
+            .bind(self.id)
+            .fetch_one(&mut *conn)
+            .await
+            .with_context(|| "Failed to read item ordering")?;
         Ok(())
     }
 }
 
 impl UIList {
     /// Get all lists in db already attached to their items
+    ///
+    /// This takes two queries however many lists and items there are.
     pub async fn get_all(pool: &SqlitePool) -> Result<Vec<UIList>> {
-        // Fetch all lists
         let lists = TodoList::get_all(pool)
             .await
             .with_context(|| "Failed to fetch lists from db")?;
+        let mut items_by_list = Self::get_items_by_list(pool, None).await?;
+
+        let ui_lists = lists
+            .into_iter()
+            .map(|list| {
+                let items = items_by_list.remove(&list.id).unwrap_or_default();
+                let items = Self::visible_items(&list, items, &ListState::default());
+                UIList {
+                    list,
+                    smart_list: None,
+                    filter: None,
+                    item_state: ListState::default(),
+                    items,
+                }
+            })
+            .collect();
 
-        let mut ui_lists = Vec::new();
+        Ok(ui_lists)
+    }
 
-        // For each list, fetch its items and create a UIList
-        for list in lists {
-            let items = TodoItem::get_by_list_id(pool, list.id)
-                .await
-                .with_context(|| format!("Failed to fetch items for list {}", list.id))?
-                .iter()
-                .map(|i| UIItem {
-                    item: i.clone(),
-                    state: ListState::default(),
-                })
-                .collect();
+    /// Get the items of all active lists, or only those matching a query, grouped by list ID
+    ///
+    /// Items come in a single query, in the manual order of their list.
+    pub async fn get_items_by_list(
+        pool: &SqlitePool,
+        filter: Option<&Query>,
+    ) -> Result<HashMap<i64, Vec<TodoItem>>> {
+        let items = match filter {
+            Some(query) => query
+                .get_matches(pool, None)
+                .await?
+                .into_iter()
+                .map(|query_match| query_match.item)
+                .collect(),
+            None => sqlx::query_as::<_, TodoItem>(
+                r#"
+                SELECT i.id, i.list_id, i.name, i.is_done, i.status, i.priority, i.due_date, i.tags, i.ordering,
+                       i.deleted_at, i.created_at, i.updated_at
+                FROM todo_items i
+                JOIN todo_lists l ON l.id = i.list_id
+                WHERE i.deleted_at IS NULL AND l.archived_at IS NULL AND l.deleted_at IS NULL
+                ORDER BY i.list_id, i.ordering
+                "#,
+            )
+            .fetch_all(pool)
+            .await
+            .with_context(|| "Failed to fetch todo items")?,
+        };
+
+        let mut items_by_list: HashMap<i64, Vec<TodoItem>> = HashMap::new();
+        for item in items {
+            items_by_list.entry(item.list_id).or_default().push(item);
+        }
+        Ok(items_by_list)
+    }
+
+    /// Get all smart lists in db along with the items they match
+    pub async fn get_smart_lists(pool: &SqlitePool) -> Result<Vec<UIList>> {
+        let smart_lists = SmartList::get_all(pool)
+            .await
+            .with_context(|| "Failed to fetch smart lists from db")?;
+
+        let mut ui_lists = Vec::new();
+        for smart_list in smart_lists {
+            let list = smart_list.as_list();
+            let items = smart_list.get_items(pool).await?;
+            let items = Self::visible_items(&list, items, &ListState::default());
 
             ui_lists.push(UIList {
                 list,
+                smart_list: Some(smart_list),
+                filter: None,
                 item_state: ListState::default(),
                 items,
             });
@@ -422,23 +1005,436 @@ impl UIList {
         Ok(ui_lists)
     }
 
+    /// Whether this is a smart list rather than a real one
+    pub fn is_smart(&self) -> bool {
+        self.smart_list.is_some()
+    }
+
     /// Update items when something changes (new item, deleted item).
     /// Keeps the same list state instead of reinitializing it
     pub async fn update_items(&mut self, pool: &SqlitePool) -> Result<()> {
         // Re-fetch the items but don't change the list state
-        let items = TodoItem::get_by_list_id(pool, self.list.id)
-            .await
-            .with_context(|| "Failed to fetch items for list")?
-            .iter()
+        let items = match (&self.smart_list, &self.filter) {
+            (Some(smart_list), _) => smart_list.get_items(pool).await?,
+            (None, Some(query)) => query
+                .get_matches(pool, Some(self.list.id))
+                .await?
+                .into_iter()
+                .map(|query_match| query_match.item)
+                .collect(),
+            (None, None) => TodoItem::get_by_list_id(pool, self.list.id)
+                .await
+                .with_context(|| "Failed to fetch items for list")?,
+        };
+
+        self.set_items(items);
+        Ok(())
+    }
+
+    /// Replace the items with freshly fetched ones, keeping the list state
+    pub fn set_items(&mut self, items: Vec<TodoItem>) {
+        self.items = Self::visible_items(&self.list, items, &self.item_state);
+
+        // Keep the selection within bounds if items disappeared
+        if let Some(j) = self.item_state.selected()
+            && j >= self.items.len()
+        {
+            self.item_state.select(self.items.len().checked_sub(1));
+        }
+    }
+
+    /// Wrap the items to be displayed in the list's sort order, leaving out
+    /// completed ones if the list hides them
+    fn visible_items(list: &TodoList, mut items: Vec<TodoItem>, state: &ListState) -> Vec<UIItem> {
+        Self::sort_items(list.sort_mode, &mut items);
+        items
+            .into_iter()
+            .filter(|i| !(list.hide_done && i.is_done))
             .map(|i| UIItem {
-                item: i.clone(),
-                state: self.item_state.clone(),
+                item: i,
+                state: state.clone(),
             })
-            .collect();
+            .collect()
+    }
+
+    /// Sort items fetched in manual order according to the given mode
+    ///
+    /// The sort is stable, so ties keep their manual order.
+    pub(crate) fn sort_items(sort_mode: SortMode, items: &mut [TodoItem]) {
+        match sort_mode {
+            SortMode::Manual => {}
+            SortMode::Priority => items.sort_by_key(|i| match i.priority {
+                Some(Priority::High) => 0,
+                Some(Priority::Medium) => 1,
+                Some(Priority::Low) => 2,
+                None => 3,
+            }),
+            SortMode::DueDate => items.sort_by_key(|i| (i.due_date.is_none(), i.due_date)),
+            SortMode::Created => items.sort_by_key(|i| i.created_at),
+            SortMode::Alphabetical => items.sort_by_key(|i| i.name.to_lowercase()),
+            SortMode::DoneLast => items.sort_by_key(|i| i.is_done),
+        }
+    }
+}
+
+impl TrashEntry {
+    /// Get all deleted lists and items, most recently deleted first
+    ///
+    /// Items of a deleted list are not listed on their own: they come back
+    /// (or go away) together with their list.
+    pub async fn get_all(pool: &SqlitePool) -> Result<Vec<TrashEntry>> {
+        let lists = sqlx::query_as::<_, TodoList>(
+            r#"
+            SELECT id, name, ordering, hide_done, sort_mode, statuses, archived_at, deleted_at, created_at, updated_at
+            FROM todo_lists
+            WHERE deleted_at IS NOT NULL
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .with_context(|| "Failed to fetch deleted todo lists")?;
+
+        let items = sqlx::query_as::<_, TodoItem>(
+            r#"
+            SELECT id, list_id, name, is_done, status, priority, due_date, tags, ordering, deleted_at, created_at, updated_at
+            FROM todo_items
+            WHERE deleted_at IS NOT NULL
+              AND list_id IN (SELECT id FROM todo_lists WHERE deleted_at IS NULL)
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .with_context(|| "Failed to fetch deleted todo items")?;
+
+        // Names of the lists the deleted items belong to
+        let list_names: HashMap<i64, String> =
+            sqlx::query_as::<_, (i64, String)>("SELECT id, name FROM todo_lists")
+                .fetch_all(pool)
+                .await
+                .with_context(|| "Failed to fetch todo list names")?
+                .into_iter()
+                .collect();
+
+        let mut entries: Vec<TrashEntry> = lists.into_iter().map(TrashEntry::List).collect();
+        entries.extend(items.into_iter().map(|item| TrashEntry::Item {
+            list_name: list_names.get(&item.list_id).cloned().unwrap_or_default(),
+            item,
+        }));
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.deleted_at()));
+
+        Ok(entries)
+    }
+
+    /// Find a deleted list by ID
+    pub async fn get_list(pool: &SqlitePool, id: i64) -> Result<Option<TrashEntry>> {
+        let entries = Self::get_all(pool).await?;
+        Ok(entries
+            .into_iter()
+            .find(|entry| matches!(entry, TrashEntry::List(list) if list.id == id)))
+    }
 
-        // Update the items
-        self.items = items;
+    /// Find a deleted item by ID
+    pub async fn get_item(pool: &SqlitePool, id: i64) -> Result<Option<TrashEntry>> {
+        let entries = Self::get_all(pool).await?;
+        Ok(entries
+            .into_iter()
+            .find(|entry| matches!(entry, TrashEntry::Item { item, .. } if item.id == id)))
+    }
+
+    /// Name of the deleted list or item
+    pub fn name(&self) -> &str {
+        match self {
+            TrashEntry::List(list) => &list.name,
+            TrashEntry::Item { item, .. } => &item.name,
+        }
+    }
+
+    /// When the list or item was moved to the trash
+    pub fn deleted_at(&self) -> Option<DateTime<Utc>> {
+        match self {
+            TrashEntry::List(list) => list.deleted_at,
+            TrashEntry::Item { item, .. } => item.deleted_at,
+        }
+    }
+
+    /// Take the list or item out of the trash
+    pub async fn restore(&self, pool: &SqlitePool) -> Result<()> {
+        let query = match self {
+            TrashEntry::List(list) => {
+                sqlx::query("UPDATE todo_lists SET deleted_at = NULL WHERE id = ?1").bind(list.id)
+            }
+            TrashEntry::Item { item, .. } => {
+                sqlx::query("UPDATE todo_items SET deleted_at = NULL WHERE id = ?1").bind(item.id)
+            }
+        };
+        query
+            .execute(pool)
+            .await
+            .with_context(|| format!("Failed to restore '{}'", self.name()))?;
 
         Ok(())
     }
+
+    /// Permanently delete the list or item
+    pub async fn purge(self, pool: &SqlitePool) -> Result<()> {
+        match self {
+            TrashEntry::List(list) => list.purge(pool).await,
+            TrashEntry::Item { item, .. } => item.purge(pool).await,
+        }
+    }
+
+    /// Permanently delete everything in the trash
+    ///
+    /// Returns the number of purged entries.
+    pub async fn empty(pool: &SqlitePool) -> Result<u64> {
+        Self::purge_deleted_before(pool, Utc::now()).await
+    }
+
+    /// Permanently delete what has been in the trash for longer than the retention period
+    ///
+    /// A retention of 0 days keeps deleted lists and items until the trash is emptied.
+    /// Returns the number of purged entries.
+    pub async fn purge_expired(pool: &SqlitePool, retention_days: u32) -> Result<u64> {
+        if retention_days == 0 {
+            return Ok(0);
+        }
+        let cutoff = Utc::now() - chrono::Duration::days(retention_days.into());
+        Self::purge_deleted_before(pool, cutoff).await
+    }
+
+    /// Permanently delete lists and items deleted before `cutoff` in a single transaction
+    async fn purge_deleted_before(pool: &SqlitePool, cutoff: DateTime<Utc>) -> Result<u64> {
+        let mut tx = pool
+            .begin()
+            .await
+            .with_context(|| "Failed to start transaction")?;
+
+        // Lists first: their items go with them due to CASCADE
+        let lists = sqlx::query("DELETE FROM todo_lists WHERE deleted_at <= ?1")
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| "Failed to purge deleted todo lists")?
+            .rows_affected();
+
+        let items = sqlx::query("DELETE FROM todo_items WHERE deleted_at <= ?1")
+            .bind(cutoff)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| "Failed to purge deleted todo items")?
+            .rows_affected();
+
+        tx.commit()
+            .await
+            .with_context(|| "Failed to commit transaction")?;
+
+        Ok(lists + items)
+    }
+}
+
+impl ItemWithList {
+    /// Get all pending items with a due date, earliest first
+    ///
+    /// Items of archived or deleted lists are left out.
+    pub async fn get_agenda(pool: &SqlitePool) -> Result<Vec<ItemWithList>> {
+        let entries = sqlx::query_as::<_, ItemWithList>(
+            r#"
+            SELECT i.id, i.list_id, i.name, i.is_done, i.status, i.priority, i.due_date, i.tags, i.ordering,
+                   i.deleted_at, i.created_at, i.updated_at, l.name AS list_name
+            FROM todo_items i
+            JOIN todo_lists l ON l.id = i.list_id
+            WHERE i.is_done = FALSE AND i.due_date IS NOT NULL AND i.deleted_at IS NULL
+              AND l.archived_at IS NULL AND l.deleted_at IS NULL
+            ORDER BY i.due_date, l.ordering, i.ordering
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .with_context(|| "Failed to fetch agenda")?;
+
+        Ok(entries)
+    }
+
+    /// Group of the agenda the item falls in, based on the local date
+    pub fn bucket(&self) -> AgendaBucket {
+        let today = chrono::Local::now().date_naive();
+        let due = self
+            .item
+            .due_date
+            .map(|date| date.with_timezone(&chrono::Local).date_naive())
+            .unwrap_or(today);
+        AgendaBucket::for_date(due, today)
+    }
+}
+
+impl SmartList {
+    /// Save a new smart list, after the existing ones
+    pub async fn create(pool: &SqlitePool, new_smart_list: NewSmartList) -> Result<SmartList> {
+        let now = Utc::now();
+
+        // The next ordering value (max + 1) is taken in the same statement, so concurrent creates can't share it
+        let row = sqlx::query_as::<_, SmartList>(
+            r#"
+            INSERT INTO smart_lists (name, ordering, min_priority, due_within_days, is_done, tag, text, created_at, updated_at)
+            VALUES (?1, (SELECT COALESCE(MAX(ordering), 0) + 1 FROM smart_lists), ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            RETURNING id, name, ordering, min_priority, due_within_days, is_done, tag, text, created_at, updated_at
+            "#,
+        )
+        .bind(&new_smart_list.name)
+        .bind(&new_smart_list.min_priority)
+        .bind(new_smart_list.due_within_days)
+        .bind(new_smart_list.is_done)
+        .bind(&new_smart_list.tag)
+        .bind(&new_smart_list.text)
+        .bind(now)
+        .bind(now)
+        .fetch_one(pool)
+        .await
+        .with_context(|| "Failed to create smart list")?;
+
+        Ok(row)
+    }
+
+    /// Get all smart lists
+    pub async fn get_all(pool: &SqlitePool) -> Result<Vec<SmartList>> {
+        let smart_lists = sqlx::query_as::<_, SmartList>(
+            r#"
+            SELECT id, name, ordering, min_priority, due_within_days, is_done, tag, text, created_at, updated_at
+            FROM smart_lists
+            ORDER BY ordering
+            "#,
+        )
+        .fetch_all(pool)
+        .await
+        .with_context(|| "Failed to fetch smart lists")?;
+
+        Ok(smart_lists)
+    }
+
+    /// Get a smart list by ID
+    pub async fn get_by_id(pool: &SqlitePool, id: i64) -> Result<Option<SmartList>> {
+        let smart_list = sqlx::query_as::<_, SmartList>(
+            "SELECT id, name, ordering, min_priority, due_within_days, is_done, tag, text, created_at, updated_at FROM smart_lists WHERE id = ?1",
+        )
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .with_context(|| "Failed to fetch smart list by id")?;
+
+        Ok(smart_list)
+    }
+
+    /// Update smart list name
+    pub async fn update_name(&mut self, pool: &SqlitePool, new_name: String) -> Result<()> {
+        let now = Utc::now();
+
+        sqlx::query("UPDATE smart_lists SET name = ?1, updated_at = ?2 WHERE id = ?3")
+            .bind(&new_name)
+            .bind(now)
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to update smart list")?;
+
+        self.name = new_name;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Delete the smart list (the items it shows are left alone)
+    pub async fn delete(self, pool: &SqlitePool) -> Result<()> {
+        sqlx::query("DELETE FROM smart_lists WHERE id = ?1")
+            .bind(self.id)
+            .execute(pool)
+            .await
+            .with_context(|| "Failed to delete smart list")?;
+
+        Ok(())
+    }
+
+    /// Get the items matching the criteria, soonest due first
+    ///
+    /// Items of archived or deleted lists are left out.
+    pub async fn get_items(&self, pool: &SqlitePool) -> Result<Vec<TodoItem>> {
+        // Due dates are compared against the end of the last day in range, in local time
+        let due_before = self.due_within_days.and_then(|days| {
+            (chrono::Local::now().date_naive() + chrono::Duration::days(days + 1))
+                .and_hms_opt(0, 0, 0)?
+                .and_local_timezone(chrono::Local)
+                .earliest()
+                .map(|date| date.with_timezone(&Utc))
+        });
+
+        let items = sqlx::query_as::<_, TodoItem>(
+            r#"
+            SELECT i.id, i.list_id, i.name, i.is_done, i.status, i.priority, i.due_date, i.tags, i.ordering,
+                   i.deleted_at, i.created_at, i.updated_at
+            FROM todo_items i
+            JOIN todo_lists l ON l.id = i.list_id
+            WHERE i.deleted_at IS NULL AND l.archived_at IS NULL AND l.deleted_at IS NULL
+              AND (?1 IS NULL OR
+                   CASE i.priority WHEN 'high' THEN 3 WHEN 'medium' THEN 2 WHEN 'low' THEN 1 ELSE 0 END >= ?1)
+              AND (?2 IS NULL OR julianday(i.due_date) < julianday(?2))
+              AND (?3 IS NULL OR i.is_done = ?3)
+              AND (?4 IS NULL OR instr(',' || lower(i.tags) || ',', ',' || lower(?4) || ',') > 0)
+              AND (?5 IS NULL OR instr(lower(i.name), lower(?5)) > 0)
+            ORDER BY i.due_date IS NULL, i.due_date, l.ordering, i.ordering
+            "#,
+        )
+        .bind(self.min_priority.as_ref().map(Priority::rank))
+        .bind(due_before)
+        .bind(self.is_done)
+        .bind(&self.tag)
+        .bind(&self.text)
+        .fetch_all(pool)
+        .await
+        .with_context(|| format!("Failed to fetch items of smart list '{}'", self.name))?;
+
+        Ok(items)
+    }
+
+    /// Stand-in list used to show the smart list in place of a real one
+    ///
+    /// Its ID (0) matches no real list, and its items are kept in query order.
+    pub fn as_list(&self) -> TodoList {
+        TodoList {
+            id: 0,
+            name: self.name.clone(),
+            ordering: self.ordering,
+            hide_done: false,
+            sort_mode: SortMode::Manual,
+            statuses: String::new(),
+            archived_at: None,
+            deleted_at: None,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+        }
+    }
+
+    /// Short description of the criteria, e.g. "priority >= high, due within 7 days"
+    pub fn describe(&self) -> String {
+        let mut criteria = Vec::new();
+        if let Some(priority) = &self.min_priority {
+            criteria.push(format!("priority >= {}", priority));
+        }
+        if let Some(days) = self.due_within_days {
+            criteria.push(format!("due within {} days", days));
+        }
+        match self.is_done {
+            Some(true) => criteria.push("done".to_string()),
+            Some(false) => criteria.push("pending".to_string()),
+            None => {}
+        }
+        if let Some(tag) = &self.tag {
+            criteria.push(format!("#{}", tag));
+        }
+        if let Some(text) = &self.text {
+            criteria.push(format!("\"{}\"", text));
+        }
+        if criteria.is_empty() {
+            "all items".to_string()
+        } else {
+            criteria.join(", ")
+        }
+    }
 }
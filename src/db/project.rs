@@ -0,0 +1,99 @@
+use crate::db::config::{DBConfig, SqliteSettings};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Markers of a project-local database
+pub const PROJECT_DIR: &str = ".judo";
+pub const PROJECT_FILE: &str = ".judo.toml";
+const PROJECT_DB_FILE: &str = "judo.db";
+
+/// Optional .judo.toml file at the root of a project
+#[derive(Deserialize, Serialize, Default)]
+pub struct ProjectConfig {
+    /// Name of the database (defaults to the name of the project directory)
+    pub name: Option<String>,
+    /// Path of the SQLite file, relative to the project root (defaults to .judo/judo.db)
+    pub path: Option<String>,
+}
+
+impl ProjectConfig {
+    /// Read a .judo.toml file
+    pub fn read(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+}
+
+/// Find the project root by walking up from `start`, like git does
+///
+/// A directory is a project root if it contains a .judo directory or a .judo.toml file.
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(PROJECT_FILE).is_file() || dir.join(PROJECT_DIR).is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// Build the database config of the project containing `start`, if any
+pub fn discover_project_db(start: &Path) -> Result<Option<DBConfig>> {
+    let Some(root) = find_project_root(start) else {
+        return Ok(None);
+    };
+
+    // The .judo.toml file is optional: a bare .judo directory uses the defaults
+    let project_file = root.join(PROJECT_FILE);
+    let project_config = if project_file.is_file() {
+        ProjectConfig::read(&project_file)?
+    } else {
+        ProjectConfig::default()
+    };
+
+    let name = match project_config.name {
+        Some(name) => name,
+        None => root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "project".to_string()),
+    };
+
+    let path = match project_config.path {
+        Some(path) => root.join(path),
+        None => root.join(PROJECT_DIR).join(PROJECT_DB_FILE),
+    };
+
+    Ok(Some(DBConfig {
+        name,
+        connection_str: format!("sqlite:{}", path.display()),
+        sqlite: SqliteSettings::default(),
+    }))
+}
+
+/// Create a project-local database in `root`
+///
+/// Creates the .judo directory and, if a name is given, a .judo.toml file recording it.
+/// Returns the config of the new database, which still needs to be initialised.
+pub fn init_project(root: &Path, name: Option<String>) -> Result<DBConfig> {
+    let project_dir = root.join(PROJECT_DIR);
+    if project_dir.exists() || root.join(PROJECT_FILE).exists() {
+        anyhow::bail!("A judo project already exists in {}", root.display());
+    }
+
+    fs::create_dir_all(&project_dir)
+        .with_context(|| format!("Failed to create {}", project_dir.display()))?;
+
+    if let Some(name) = name {
+        let project_config = ProjectConfig {
+            name: Some(name),
+            path: None,
+        };
+        let content = toml::to_string_pretty(&project_config)
+            .with_context(|| "Failed to serialize .judo.toml")?;
+        fs::write(root.join(PROJECT_FILE), content)
+            .with_context(|| "Failed to write .judo.toml")?;
+    }
+
+    discover_project_db(root)?.ok_or_else(|| anyhow::anyhow!("Failed to discover new project"))
+}
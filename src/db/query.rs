@@ -0,0 +1,461 @@
+//! Small query language for filtering items, e.g. `priority:high due<7d !done list:work "text"`
+//!
+//! A query is a list of terms that must all match. Each term can be negated
+//! with a leading `!` and is one of:
+//! - `priority:high`, `priority>=medium` (also `p:`): priority, compared by urgency
+//! - `due<7d`, `due:today`, `due>=2026-10-20`, `due:none` (also `d:`): due date,
+//!   compared by local day, relative to today (`today`, `tomorrow`, `3d`, `2w`, `fri`, `eow`
+//!   or any other date in words understood by `--due`)
+//! - `done`, `pending` (also `is:done`, `is:pending`): completion
+//! - `list:work` (also `l:`): name of the list, ignoring case
+//! - `tag:home` or `#home` (also `t:`): tag
+//! - `status:doing` (also `s:`): board column
+//! - any other word, or `"quoted text"`: part of the item name, ignoring case
+//!
+//! Values containing spaces can be quoted, as in `list:"side projects"`.
+use crate::db::dates::{parse_date, start_of_day};
+use crate::db::models::{ItemWithList, Priority};
+use anyhow::{Context, Result};
+use chrono::{Duration, Local, NaiveDate};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+use std::fmt;
+use std::str::FromStr;
+
+/// Parsed query: all terms must match
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Query {
+    pub terms: Vec<Term>,
+}
+
+/// Single criterion of a query, possibly negated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Term {
+    pub negated: bool,
+    pub filter: Filter,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Filter {
+    Priority(Comparison, Priority),
+    Due(Comparison, DueDate),
+    /// Items without a due date
+    NoDue,
+    Done,
+    List(String),
+    Tag(String),
+    Status(String),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// Day a due date is compared against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DueDate {
+    /// Number of days from today (negative for past days)
+    Relative(i64),
+    On(NaiveDate),
+    /// Date in words, such as `fri` or `eow`, read again against each day
+    Words(String),
+}
+
+impl Comparison {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Comparison::Eq => ":",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+}
+
+impl DueDate {
+    /// The local day meant, as seen on `today`
+    pub fn resolve(&self, today: NaiveDate) -> NaiveDate {
+        match self {
+            DueDate::Relative(days) => today + Duration::days(*days),
+            DueDate::On(date) => *date,
+            // The words were checked when parsed, and read as a date on any day
+            DueDate::Words(words) => parse_date(words, today).unwrap_or(today),
+        }
+    }
+}
+
+impl FromStr for DueDate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.to_lowercase();
+        match value.as_str() {
+            "today" => return Ok(DueDate::Relative(0)),
+            "tomorrow" => return Ok(DueDate::Relative(1)),
+            "yesterday" => return Ok(DueDate::Relative(-1)),
+            _ => {}
+        }
+
+        // Number of days or weeks from today
+        if let Some((number, unit)) = value.split_at_checked(value.len().saturating_sub(1))
+            && let Ok(n) = number.parse::<i64>()
+        {
+            match unit {
+                "d" => return Ok(DueDate::Relative(n)),
+                "w" => return Ok(DueDate::Relative(n * 7)),
+                _ => {}
+            }
+        }
+
+        if let Ok(date) = NaiveDate::parse_from_str(&value, "%Y-%m-%d") {
+            return Ok(DueDate::On(date));
+        }
+
+        // Anything else the due dates of items take, such as `fri` or `eow`
+        parse_date(&value, Local::now().date_naive())
+            .map(|_| DueDate::Words(value.trim().to_string()))
+            .map_err(|_| {
+                format!(
+                    "invalid due date '{}' (expected e.g. today, tomorrow, fri, eow, 3d, 2w or YYYY-MM-DD)",
+                    s
+                )
+            })
+    }
+}
+
+impl fmt::Display for DueDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DueDate::Relative(0) => f.write_str("today"),
+            DueDate::Relative(1) => f.write_str("tomorrow"),
+            DueDate::Relative(-1) => f.write_str("yesterday"),
+            DueDate::Relative(days) if days % 7 == 0 => write!(f, "{}w", days / 7),
+            DueDate::Relative(days) => write!(f, "{}d", days),
+            DueDate::On(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            DueDate::Words(words) => f.write_str(&quoted(words)),
+        }
+    }
+}
+
+/// Quote a value if it would otherwise be split or misread
+fn quoted(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+        format!("\"{}\"", value.replace('"', ""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Priority(cmp, priority) => write!(f, "priority{}{}", cmp.as_str(), priority),
+            Filter::Due(cmp, date) => write!(f, "due{}{}", cmp.as_str(), date),
+            Filter::NoDue => f.write_str("due:none"),
+            Filter::Done => f.write_str("done"),
+            Filter::List(name) => write!(f, "list:{}", quoted(name)),
+            Filter::Tag(tag) => write!(f, "#{}", quoted(tag)),
+            Filter::Status(status) => write!(f, "status:{}", quoted(status)),
+            Filter::Text(text) => write!(f, "\"{}\"", text.replace('"', "")),
+        }
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.negated, &self.filter) {
+            (true, Filter::Done) => f.write_str("pending"),
+            (true, filter) => write!(f, "!{}", filter),
+            (false, filter) => write!(f, "{}", filter),
+        }
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms: Vec<String> = self.terms.iter().map(Term::to_string).collect();
+        f.write_str(&terms.join(" "))
+    }
+}
+
+/// Raw word of a query, before it is interpreted
+struct Token {
+    negated: bool,
+    /// The whole word was a quoted string
+    quoted: bool,
+    text: String,
+}
+
+/// Split a query into words, keeping quoted parts together
+fn tokenize(s: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let negated = c == '!';
+        if negated {
+            chars.next();
+        }
+        let quoted = chars.peek() == Some(&'"');
+
+        let mut text = String::new();
+        let mut in_quotes = false;
+        while let Some(&c) = chars.peek() {
+            if c.is_whitespace() && !in_quotes {
+                break;
+            }
+            chars.next();
+            if c == '"' {
+                in_quotes = !in_quotes;
+            } else {
+                text.push(c);
+            }
+        }
+        if in_quotes {
+            return Err("unterminated quote".to_string());
+        }
+        if text.is_empty() && !quoted {
+            return Err("'!' must be followed by a term".to_string());
+        }
+
+        tokens.push(Token {
+            negated,
+            quoted,
+            text,
+        });
+    }
+    Ok(tokens)
+}
+
+/// Split `key<op>value` at its first operator
+fn split_field(text: &str) -> Option<(&str, Comparison, &str)> {
+    let i = text.find([':', '=', '<', '>'])?;
+    let (key, rest) = text.split_at(i);
+    let (cmp, len) = if rest.starts_with("<=") {
+        (Comparison::Le, 2)
+    } else if rest.starts_with(">=") {
+        (Comparison::Ge, 2)
+    } else if rest.starts_with('<') {
+        (Comparison::Lt, 1)
+    } else if rest.starts_with('>') {
+        (Comparison::Gt, 1)
+    } else {
+        (Comparison::Eq, 1)
+    };
+    Some((key, cmp, &rest[len..]))
+}
+
+/// Interpret a word as a term
+fn parse_term(token: Token) -> Result<Term, String> {
+    let mut negated = token.negated;
+    let text = token.text;
+
+    let filter = if token.quoted {
+        Filter::Text(text)
+    } else if let Some(tag) = text.strip_prefix('#') {
+        if tag.is_empty() {
+            return Err("'#' must be followed by a tag".to_string());
+        }
+        Filter::Tag(tag.to_string())
+    } else if let Some((key, cmp, value)) = split_field(&text) {
+        let key = key.to_lowercase();
+        if value.is_empty() {
+            return Err(format!("missing value for '{}'", key));
+        }
+        let equality_only = |filter: Filter| {
+            if cmp == Comparison::Eq {
+                Ok(filter)
+            } else {
+                Err(format!("'{}' can only be compared with ':'", key))
+            }
+        };
+
+        match key.as_str() {
+            "priority" | "p" => Filter::Priority(cmp, value.parse()?),
+            "due" | "d" if value.eq_ignore_ascii_case("none") => equality_only(Filter::NoDue)?,
+            "due" | "d" => Filter::Due(cmp, value.parse()?),
+            "list" | "l" => equality_only(Filter::List(value.to_string()))?,
+            "tag" | "t" => equality_only(Filter::Tag(value.trim_start_matches('#').to_string()))?,
+            "status" | "s" => equality_only(Filter::Status(value.to_string()))?,
+            "is" => match value.to_lowercase().as_str() {
+                "done" => equality_only(Filter::Done)?,
+                "pending" => {
+                    negated = !negated;
+                    equality_only(Filter::Done)?
+                }
+                _ => {
+                    return Err(format!(
+                        "unknown state '{}' (expected done or pending)",
+                        value
+                    ));
+                }
+            },
+            _ => {
+                return Err(format!(
+                    "unknown field '{}' (expected priority, due, list, tag, status or is)",
+                    key
+                ));
+            }
+        }
+    } else {
+        match text.to_lowercase().as_str() {
+            "done" => Filter::Done,
+            "pending" => {
+                negated = !negated;
+                Filter::Done
+            }
+            _ => Filter::Text(text),
+        }
+    };
+
+    Ok(Term { negated, filter })
+}
+
+impl FromStr for Query {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let terms = tokenize(s)?
+            .into_iter()
+            .map(parse_term)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Query { terms })
+    }
+}
+
+impl Filter {
+    /// Append the SQL condition of the filter, binding its values
+    fn push_sql(&self, builder: &mut QueryBuilder<'_, Sqlite>, today: NaiveDate) {
+        match self {
+            Filter::Priority(cmp, priority) => {
+                let op = match cmp {
+                    Comparison::Eq => "=",
+                    other => other.as_str(),
+                };
+                builder.push(format!(
+                    "CASE i.priority WHEN 'high' THEN 3 WHEN 'medium' THEN 2 WHEN 'low' THEN 1 ELSE 0 END {} ",
+                    op
+                ));
+                builder.push_bind(priority.rank());
+            }
+            Filter::Due(cmp, date) => {
+                // Compare against the bounds of the local day
+                let day = date.resolve(today);
+                let start = start_of_day(day);
+                let end = start_of_day(day + Duration::days(1));
+                let (op, bound) = match cmp {
+                    Comparison::Lt => ("<", start),
+                    Comparison::Le => ("<", end),
+                    Comparison::Gt => (">=", end),
+                    Comparison::Ge => (">=", start),
+                    Comparison::Eq => {
+                        builder.push("julianday(i.due_date) >= julianday(");
+                        builder.push_bind(start);
+                        builder.push(") AND julianday(i.due_date) < julianday(");
+                        builder.push_bind(end);
+                        builder.push(")");
+                        return;
+                    }
+                };
+                builder.push(format!("julianday(i.due_date) {} julianday(", op));
+                builder.push_bind(bound);
+                builder.push(")");
+            }
+            Filter::NoDue => {
+                builder.push("i.due_date IS NULL");
+            }
+            Filter::Done => {
+                builder.push("i.is_done = TRUE");
+            }
+            Filter::List(name) => {
+                builder.push("lower(l.name) = lower(");
+                builder.push_bind(name.clone());
+                builder.push(")");
+            }
+            Filter::Tag(tag) => {
+                builder.push("instr(',' || lower(i.tags) || ',', ',' || lower(");
+                builder.push_bind(tag.clone());
+                builder.push(") || ',') > 0");
+            }
+            Filter::Status(status) => {
+                builder.push("lower(i.status) = lower(");
+                builder.push_bind(status.clone());
+                builder.push(")");
+            }
+            Filter::Text(text) => {
+                builder.push("instr(lower(i.name), lower(");
+                builder.push_bind(text.clone());
+                builder.push(")) > 0");
+            }
+        }
+    }
+}
+
+impl Query {
+    /// Whether the query has no terms, matching every item
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Build the parameterised SQL selecting the matching items, optionally within a single list
+    ///
+    /// Relative due dates are resolved against `today`. Items of archived or
+    /// deleted lists, and items in the trash, never match.
+    pub fn to_sql(&self, list_id: Option<i64>, today: NaiveDate) -> QueryBuilder<'_, Sqlite> {
+        let mut builder = QueryBuilder::new(
+            r#"
+            SELECT i.id, i.list_id, i.name, i.is_done, i.status, i.priority, i.due_date, i.tags, i.ordering,
+                   i.deleted_at, i.created_at, i.updated_at, l.name AS list_name
+            FROM todo_items i
+            JOIN todo_lists l ON l.id = i.list_id
+            WHERE i.deleted_at IS NULL AND l.archived_at IS NULL AND l.deleted_at IS NULL
+            "#,
+        );
+        if let Some(list_id) = list_id {
+            builder.push(" AND i.list_id = ");
+            builder.push_bind(list_id);
+        }
+
+        // Missing values (e.g. no due date) count as not matching, negated or not
+        for term in &self.terms {
+            builder.push(if term.negated {
+                " AND NOT COALESCE(("
+            } else {
+                " AND COALESCE(("
+            });
+            term.filter.push_sql(&mut builder, today);
+            builder.push("), FALSE)");
+        }
+
+        builder.push(" ORDER BY l.ordering, i.ordering");
+        builder
+    }
+
+    /// Get the matching items, optionally within a single list
+    pub async fn get_matches(
+        &self,
+        pool: &SqlitePool,
+        list_id: Option<i64>,
+    ) -> Result<Vec<ItemWithList>> {
+        let today = Local::now().date_naive();
+        let matches = self
+            .to_sql(list_id, today)
+            .build_query_as::<ItemWithList>()
+            .fetch_all(pool)
+            .await
+            .with_context(|| format!("Failed to run query '{}'", self))?;
+
+        Ok(matches)
+    }
+}
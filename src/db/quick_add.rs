@@ -0,0 +1,117 @@
+//! Quick-add syntax for new items, e.g. `Pay rent !high @tomorrow #home ^chores`
+//!
+//! Words of the input are read as:
+//! - `!high`, `!medium`, `!low`: priority
+//! - `@tomorrow`, `@3d`, `@fri`, `@eow`, `@2026-10-20`: due date, with the same values as
+//!   `--due` and `due:` in queries
+//! - `#home`: tag of letters, digits, `-`, `_` or `/`, may be given several times
+//! - `^chores`: name of the list to add the item to
+//! - anything else: part of the item name
+//!
+//! Words that only look like tokens, such as `@someday`, stay in the name, and a
+//! word starting with `\` is always part of the name, without the backslash
+//! (`\#1` gives `#1`). A later priority, due date or list replaces an earlier one.
+use crate::db::dates::start_of_day;
+use crate::db::models::{NewTodoItem, Priority};
+use crate::db::query::DueDate;
+use chrono::NaiveDate;
+
+/// New item as typed with the quick-add syntax
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuickAdd {
+    /// Words that are not tokens, empty if every word is one
+    pub name: String,
+    pub priority: Option<Priority>,
+    pub due: Option<DueDate>,
+    pub tags: Vec<String>,
+    pub list: Option<String>,
+}
+
+/// Whether a word is fit to be a tag
+fn is_tag(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+}
+
+impl QuickAdd {
+    /// Split the typed input into the item name and its fields
+    pub fn parse(input: &str) -> Self {
+        let mut quick_add = QuickAdd::default();
+        let mut words: Vec<&str> = Vec::new();
+
+        for word in input.split_whitespace() {
+            if let Some(escaped) = word.strip_prefix('\\') {
+                words.push(escaped);
+                continue;
+            }
+
+            // Words are never empty, so there is always a first character
+            let mut chars = word.chars();
+            let (Some(sigil), value) = (chars.next(), chars.as_str()) else {
+                continue;
+            };
+
+            // Words that fail to parse as the token they look like stay in the name
+            let is_token = match sigil {
+                '!' => value
+                    .parse::<Priority>()
+                    .map(|priority| quick_add.priority = Some(priority))
+                    .is_ok(),
+                '@' => value
+                    .parse::<DueDate>()
+                    .map(|due| quick_add.due = Some(due))
+                    .is_ok(),
+                '#' if !value.is_empty() && is_tag(value) => {
+                    if !quick_add.tags.iter().any(|tag| tag == value) {
+                        quick_add.tags.push(value.to_string());
+                    }
+                    true
+                }
+                '^' if !value.is_empty() => {
+                    quick_add.list = Some(value.to_string());
+                    true
+                }
+                _ => false,
+            };
+            if !is_token {
+                words.push(word);
+            }
+        }
+
+        quick_add.name = words.join(" ");
+        quick_add
+    }
+
+    /// Build the item to create in the given list, with the due date resolved from `today`
+    pub fn new_item(&self, list_id: i64, today: NaiveDate) -> NewTodoItem {
+        NewTodoItem {
+            list_id,
+            name: self.name.clone(),
+            priority: self.priority.clone(),
+            due_date: self
+                .due
+                .as_ref()
+                .map(|due| start_of_day(due.resolve(today))),
+        }
+    }
+
+    /// One-line summary of the parsed fields other than the name, empty if there are none
+    pub fn describe(&self, today: NaiveDate) -> String {
+        let mut parts = Vec::new();
+        if let Some(priority) = &self.priority {
+            parts.push(format!("priority {}", priority));
+        }
+        if let Some(due) = &self.due {
+            parts.push(format!("due {}", due.resolve(today).format("%a %Y-%m-%d")));
+        }
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|tag| format!("#{}", tag)).collect();
+            parts.push(tags.join(" "));
+        }
+        if let Some(list) = &self.list {
+            parts.push(format!("in {}", list));
+        }
+        parts.join(" · ")
+    }
+}
@@ -0,0 +1,306 @@
+use crate::db::models::Priority;
+use ratatui::style::{Color, Modifier, Style};
+use serde::de::{self, Deserializer};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Built-in colour themes, picked with `theme = "name"` in `[colours]`
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NamedTheme {
+    #[default]
+    Judo,
+    Gruvbox,
+    Solarized,
+    Nord,
+    Light,
+}
+
+impl NamedTheme {
+    pub fn background(self) -> Color {
+        match self {
+            Self::Judo => Color::Rgb(0x00, 0x26, 0x26),
+            Self::Gruvbox => Color::Rgb(0x28, 0x28, 0x28),
+            Self::Solarized => Color::Rgb(0x00, 0x2b, 0x36),
+            Self::Nord => Color::Rgb(0x2e, 0x34, 0x40),
+            Self::Light => Color::Rgb(0xfa, 0xfa, 0xfa),
+        }
+    }
+
+    pub fn foreground(self) -> Color {
+        match self {
+            Self::Judo => Color::Rgb(0xfc, 0xf1, 0xd5),
+            Self::Gruvbox => Color::Rgb(0xeb, 0xdb, 0xb2),
+            Self::Solarized => Color::Rgb(0x93, 0xa1, 0xa1),
+            Self::Nord => Color::Rgb(0xec, 0xef, 0xf4),
+            Self::Light => Color::Rgb(0x38, 0x3a, 0x42),
+        }
+    }
+
+    pub fn highlight(self) -> Color {
+        match self {
+            Self::Judo => Color::Rgb(0xff, 0xa6, 0x9e),
+            Self::Gruvbox => Color::Rgb(0xfe, 0x80, 0x19),
+            Self::Solarized => Color::Rgb(0xb5, 0x89, 0x00),
+            Self::Nord => Color::Rgb(0x88, 0xc0, 0xd0),
+            Self::Light => Color::Rgb(0xe4, 0x56, 0x49),
+        }
+    }
+}
+
+/// Colour given in judo.toml, checked when the config is read
+///
+/// The text is kept as written, so that saving the config doesn't rewrite it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeColour {
+    text: String,
+    colour: Color,
+}
+
+impl ThemeColour {
+    pub fn colour(&self) -> Color {
+        self.colour
+    }
+}
+
+impl FromStr for ThemeColour {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let colour = Color::from_str(text).map_err(|_| {
+            format!(
+                "invalid colour '{}', expected a name such as \"red\" or \"lightblue\", a hex code such as \"#ff8800\" or an index from 0 to 255",
+                text
+            )
+        })?;
+        Ok(Self {
+            text: text.to_string(),
+            colour,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ThemeColour {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for ThemeColour {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.text)
+    }
+}
+
+/// Colour and modifiers of one element of the TUI
+///
+/// Anything left out keeps the look derived from the base colours, and
+/// modifiers set to false take away those that look comes with.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ElementStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fg: Option<ThemeColour>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bg: Option<ThemeColour>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dim: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underlined: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crossed_out: Option<bool>,
+}
+
+impl ElementStyle {
+    /// Whether nothing was set, so that it can be left out of judo.toml
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Apply what was set on top of the derived style
+    pub fn patch(&self, mut style: Style) -> Style {
+        if let Some(fg) = &self.fg {
+            style = style.fg(fg.colour());
+        }
+        if let Some(bg) = &self.bg {
+            style = style.bg(bg.colour());
+        }
+        let modifiers = [
+            (self.bold, Modifier::BOLD),
+            (self.italic, Modifier::ITALIC),
+            (self.dim, Modifier::DIM),
+            (self.underlined, Modifier::UNDERLINED),
+            (self.crossed_out, Modifier::CROSSED_OUT),
+        ];
+        for (enabled, modifier) in modifiers {
+            style = match enabled {
+                Some(true) => style.add_modifier(modifier),
+                Some(false) => style.remove_modifier(modifier),
+                None => style,
+            };
+        }
+        style
+    }
+}
+
+/// Colours of the TUI: a named theme, with any colour set here taking precedence
+///
+/// Elements start from the background, foreground and highlight colours,
+/// and can each be styled on their own.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<NamedTheme>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<ThemeColour>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<ThemeColour>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<ThemeColour>,
+    /// Borders of panels and pop-ups
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub border: ElementStyle,
+    /// Titles of panels and pop-ups
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub title: ElementStyle,
+    /// Selected row of a list
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub selected: ElementStyle,
+    /// Completed items
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub done: ElementStyle,
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub priority_high: ElementStyle,
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub priority_medium: ElementStyle,
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub priority_low: ElementStyle,
+    /// Pending items past their due date
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub overdue: ElementStyle,
+    /// Inside of pop-ups
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub popup: ElementStyle,
+    /// Messages at the bottom of the screen
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub status: ElementStyle,
+    /// Error messages at the bottom of the screen
+    #[serde(default, skip_serializing_if = "ElementStyle::is_empty")]
+    pub status_error: ElementStyle,
+}
+
+impl Default for Theme {
+    /// New configs name their theme, so that it's easy to find and change
+    fn default() -> Self {
+        Self {
+            theme: Some(NamedTheme::default()),
+            background: None,
+            foreground: None,
+            highlight: None,
+            border: ElementStyle::default(),
+            title: ElementStyle::default(),
+            selected: ElementStyle::default(),
+            done: ElementStyle::default(),
+            priority_high: ElementStyle::default(),
+            priority_medium: ElementStyle::default(),
+            priority_low: ElementStyle::default(),
+            overdue: ElementStyle::default(),
+            popup: ElementStyle::default(),
+            status: ElementStyle::default(),
+            status_error: ElementStyle::default(),
+        }
+    }
+}
+
+impl Theme {
+    fn named(&self) -> NamedTheme {
+        self.theme.unwrap_or_default()
+    }
+
+    pub fn background(&self) -> Color {
+        self.background
+            .as_ref()
+            .map_or_else(|| self.named().background(), ThemeColour::colour)
+    }
+
+    pub fn foreground(&self) -> Color {
+        self.foreground
+            .as_ref()
+            .map_or_else(|| self.named().foreground(), ThemeColour::colour)
+    }
+
+    pub fn highlight(&self) -> Color {
+        self.highlight
+            .as_ref()
+            .map_or_else(|| self.named().highlight(), ThemeColour::colour)
+    }
+
+    pub fn border_style(&self) -> Style {
+        self.border.patch(Style::default().fg(self.foreground()))
+    }
+
+    pub fn title_style(&self) -> Style {
+        self.title.patch(Style::default().fg(self.foreground()))
+    }
+
+    /// Foreground and background swapped
+    pub fn selected_style(&self) -> Style {
+        self.selected
+            .patch(Style::default().fg(self.background()).bg(self.foreground()))
+    }
+
+    /// Struck through
+    pub fn done_style(&self) -> Style {
+        self.done
+            .patch(Style::default().add_modifier(Modifier::CROSSED_OUT))
+    }
+
+    /// High priority in the highlight colour, low priority dimmed
+    pub fn priority_style(&self, priority: &Priority) -> Style {
+        match priority {
+            Priority::High => self
+                .priority_high
+                .patch(Style::default().fg(self.highlight())),
+            Priority::Medium => self.priority_medium.patch(Style::default()),
+            Priority::Low => self
+                .priority_low
+                .patch(Style::default().add_modifier(Modifier::DIM)),
+        }
+    }
+
+    /// Bold, in the highlight colour
+    pub fn overdue_style(&self) -> Style {
+        self.overdue.patch(
+            Style::default()
+                .fg(self.highlight())
+                .add_modifier(Modifier::BOLD),
+        )
+    }
+
+    pub fn popup_style(&self) -> Style {
+        self.popup
+            .patch(Style::default().fg(self.foreground()).bg(self.background()))
+    }
+
+    /// Dimmed foreground, for hints such as the quick-add syntax
+    pub fn hint_style(&self) -> Style {
+        Style::default()
+            .fg(self.foreground())
+            .add_modifier(Modifier::DIM)
+    }
+
+    pub fn status_style(&self) -> Style {
+        self.status.patch(Style::default().fg(self.highlight()))
+    }
+
+    pub fn status_error_style(&self) -> Style {
+        self.status_error.patch(Style::default().fg(Color::Red))
+    }
+}
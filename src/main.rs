@@ -1,12 +1,16 @@
 //! Judo - A terminal-based todo list application
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use clap::Parser;
 use judo::{
-    app::App,
+    app::{App, StartupError, StartupOutcome, StartupScreen},
     cli::{
-        args::{Cli, Commands, DbCommands, ItemCommands, ListCommands},
+        InvalidInput,
+        args::{
+            Cli, Commands, DbCommands, ItemCommands, ListCommands, SmartCommands, TrashCommands,
+        },
         ops,
     },
+    db::models::NewSmartList,
 };
 
 /// Application entry point
@@ -17,18 +21,74 @@ use judo::{
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
-    // Set up the app
-    let app = App::new().await;
+    // The tui subcommand can pick its own database
+    let db = match &cli.command {
+        Some(Commands::Tui { db: Some(db), .. }) => Some(db.clone()),
+        _ => cli.db,
+    };
+
+    // The TUI picks up where it was left, unless told where to start
+    let restore_session = match &cli.command {
+        None => true,
+        Some(Commands::Tui { db, list, list_id }) => {
+            db.is_none() && list.is_none() && list_id.is_none()
+        }
+        Some(_) => false,
+    };
+
+    // Set up the app, offering a way out in the TUI if that fails
+    let app = match App::new(cli.config, db, restore_session).await {
+        Ok(app) => app,
+        Err(e) if matches!(cli.command, None | Some(Commands::Tui { .. })) => {
+            match recover_from_startup_error(e).await? {
+                StartupOutcome::Started(app) => *app,
+                StartupOutcome::Exit => return Ok(()),
+            }
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    };
+
+    // Close the databases opened on the way once the command is done
+    let pools = app.pools.clone();
+    let result = run_command(cli.command, app).await;
+    pools.close_all().await;
+
+    // Input that can't be acted on has its own exit code
+    if let Err(e) = &result
+        && let Some(invalid_input) = e.downcast_ref::<InvalidInput>()
+    {
+        eprintln!("Error: {}", invalid_input);
+        std::process::exit(exitcode::DATAERR);
+    }
+    result
+}
 
+/// Run the requested command, or the TUI if there is none
+async fn run_command(command: Option<Commands>, mut app: App) -> Result<()> {
     // Handle CLI arguments
-    match cli.command {
+    match command {
+        // Project commands
+        Some(Commands::Init { name }) => {
+            ops::init_project(name)
+                .await
+                .with_context(|| "Failed to initialize project")?;
+        }
+        // Agenda of due items
+        Some(Commands::Agenda { db, all }) => {
+            ops::show_agenda(&app, &db, all)
+                .await
+                .with_context(|| "Failed to show agenda")?;
+        }
         //Database commands
         Some(Commands::Dbs { command }) => match command {
             Some(DbCommands::Show) => {
                 ops::list_dbs(&app).with_context(|| "Failed to list databases")?;
             }
-            Some(DbCommands::Add { name }) => {
-                ops::add_db(app, name)
+            Some(DbCommands::Add { name, path }) => {
+                ops::add_db(app, name, path)
                     .await
                     .with_context(|| "Failed to add database")?;
             }
@@ -36,8 +96,8 @@ async fn main() -> Result<()> {
         },
         //List commands
         Some(Commands::Lists { command }) => match command {
-            Some(ListCommands::Show { name }) => {
-                ops::list_lists(&app, name)
+            Some(ListCommands::Show { name, archived }) => {
+                ops::list_lists(&app, name, archived)
                     .await
                     .with_context(|| "Failed to list to-do lists")?;
             }
@@ -51,12 +111,37 @@ async fn main() -> Result<()> {
                     .await
                     .with_context(|| "Failed to delete to-do list")?;
             }
+            Some(ListCommands::Archive { name, id, db }) => {
+                ops::archive_list(&app, name, id, &db)
+                    .await
+                    .with_context(|| "Failed to archive to-do list")?;
+            }
+            Some(ListCommands::Sort { mode, name, id, db }) => {
+                ops::sort_list(&app, mode, name, id, &db)
+                    .await
+                    .with_context(|| "Failed to sort to-do list")?;
+            }
+            Some(ListCommands::Columns {
+                columns,
+                name,
+                id,
+                db,
+            }) => {
+                ops::set_list_columns(&app, columns, name, id, &db)
+                    .await
+                    .with_context(|| "Failed to set to-do list columns")?;
+            }
+            Some(ListCommands::Unarchive { name, id, db }) => {
+                ops::unarchive_list(&app, name, id, &db)
+                    .await
+                    .with_context(|| "Failed to unarchive to-do list")?;
+            }
             None => {}
         },
         //Item commands
         Some(Commands::Items { command }) => match command {
-            Some(ItemCommands::Show) => {
-                ops::list_items(&app)
+            Some(ItemCommands::Show { query }) => {
+                ops::list_items(&app, query.unwrap_or_default())
                     .await
                     .with_context(|| "Failed to list to-do items")?;
             }
@@ -65,8 +150,9 @@ async fn main() -> Result<()> {
                 db,
                 list_name,
                 list_id,
+                due,
             }) => {
-                ops::add_item(&app, name, &db, list_id, list_name)
+                ops::add_item(&app, name, &db, list_id, list_name, due)
                     .await
                     .with_context(|| "Failed to add to-do item")?;
             }
@@ -75,27 +161,169 @@ async fn main() -> Result<()> {
                     .await
                     .with_context(|| "Failed to delete to-do item")?;
             }
+            Some(ItemCommands::ClearDone {
+                db,
+                list_name,
+                list_id,
+            }) => {
+                ops::clear_done_items(&app, &db, list_id, list_name)
+                    .await
+                    .with_context(|| "Failed to clear completed to-do items")?;
+            }
             Some(ItemCommands::ToggleDone { id, db }) => {
                 ops::toggle_done_item(&app, id, &db)
                     .await
                     .with_context(|| "Failed to toggle to-do item status")?;
             }
+            Some(ItemCommands::Tag { tags, id, db }) => {
+                ops::tag_item(&app, tags, id, &db)
+                    .await
+                    .with_context(|| "Failed to tag to-do item")?;
+            }
+            Some(ItemCommands::Due {
+                due,
+                clear: _,
+                id,
+                db,
+            }) => {
+                ops::set_item_due_date(&app, due, id, &db)
+                    .await
+                    .with_context(|| "Failed to set due date of to-do item")?;
+            }
+            Some(ItemCommands::Move { status, id, db }) => {
+                ops::move_item_to_column(&app, status, id, &db)
+                    .await
+                    .with_context(|| "Failed to move to-do item")?;
+            }
+            Some(ItemCommands::Reorder { id, position, db }) => {
+                ops::reorder_item(&app, id, position, &db)
+                    .await
+                    .with_context(|| "Failed to reorder to-do item")?;
+            }
+            None => {}
+        },
+        //Trash commands
+        Some(Commands::Trash { command }) => match command {
+            Some(TrashCommands::Show { db }) => {
+                ops::list_trash(&app, &db)
+                    .await
+                    .with_context(|| "Failed to list trash")?;
+            }
+            Some(TrashCommands::Restore {
+                list_id,
+                item_id,
+                db,
+            }) => {
+                ops::restore_from_trash(&app, list_id, item_id, &db)
+                    .await
+                    .with_context(|| "Failed to restore from trash")?;
+            }
+            Some(TrashCommands::Empty { db }) => {
+                ops::empty_trash(&app, &db)
+                    .await
+                    .with_context(|| "Failed to empty trash")?;
+            }
             None => {}
         },
+        //Smart list commands
+        Some(Commands::Smart { command }) => match command {
+            Some(SmartCommands::Show { db }) => {
+                ops::list_smart_lists(&app, &db)
+                    .await
+                    .with_context(|| "Failed to list smart lists")?;
+            }
+            Some(SmartCommands::Add {
+                name,
+                priority,
+                due_within,
+                done,
+                pending,
+                tag,
+                text,
+                db,
+            }) => {
+                let new_smart_list = NewSmartList {
+                    name,
+                    min_priority: priority,
+                    due_within_days: due_within,
+                    is_done: match (done, pending) {
+                        (true, _) => Some(true),
+                        (_, true) => Some(false),
+                        _ => None,
+                    },
+                    tag,
+                    text,
+                };
+                ops::add_smart_list(&app, new_smart_list, &db)
+                    .await
+                    .with_context(|| "Failed to add smart list")?;
+            }
+            Some(SmartCommands::Items { id, db }) => {
+                ops::list_smart_list_items(&app, id, &db)
+                    .await
+                    .with_context(|| "Failed to list smart list items")?;
+            }
+            Some(SmartCommands::Delete { id, db }) => {
+                ops::delete_smart_list(&app, id, &db)
+                    .await
+                    .with_context(|| "Failed to delete smart list")?;
+            }
+            None => {}
+        },
+        // Open the TUI on the requested list
+        Some(Commands::Tui { list, list_id, .. }) => {
+            if let Some(id) = list_id
+                && !app.lists_component.select_list_by_id(id)
+            {
+                bail!(InvalidInput(format!(
+                    "List with ID '{}' not found in database '{}'",
+                    id, app.current_db_config.name
+                )));
+            }
+            if let Some(name) = list
+                && !app.lists_component.select_list_by_name(&name)
+            {
+                bail!(InvalidInput(format!(
+                    "List with name '{}' not found in database '{}'",
+                    name, app.current_db_config.name
+                )));
+            }
+
+            return run_tui(app).await;
+        }
         // No commands means use the TUI
         None => {
-            // Set the terminal up
-            let mut terminal = ratatui::init();
+            return run_tui(app).await;
+        }
+    }
 
-            // Create and run the app
-            let app_result = app.run(&mut terminal).await;
+    Ok(())
+}
 
-            // Restore terminal to original state
-            ratatui::restore();
+/// Explain why the TUI could not start, until the user finds a way out
+async fn recover_from_startup_error(error: StartupError) -> Result<StartupOutcome> {
+    let mut terminal = ratatui::init();
+    let outcome = StartupScreen::new(error).run(&mut terminal).await;
+    ratatui::restore();
+    outcome
+}
 
-            return app_result;
-        }
+/// Run the TUI until the user quits
+async fn run_tui(mut app: App) -> Result<()> {
+    // Set the terminal up
+    let mut terminal = ratatui::init();
+
+    // Create and run the app
+    let app_result = app.run(&mut terminal).await;
+
+    // Restore terminal to original state
+    ratatui::restore();
+
+    // Failing to remember the session must not prevent a clean exit; said
+    // here, where the terminal is back to normal and the message shows
+    if let Err(e) = app.save_session() {
+        eprintln!("Failed to save session: {}", e);
     }
 
-    Ok(())
+    app_result
 }
@@ -0,0 +1,242 @@
+use crate::db::config::{Config, DBConfig};
+use crate::db::connections::PoolRegistry;
+use crate::db::models::{AgendaBucket, ItemWithList};
+use anyhow::Result;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, BorderType, Borders, HighlightSpacing, List, ListItem, ListState, Padding,
+    StatefulWidget,
+};
+use sqlx::SqlitePool;
+
+/// Agenda entry along with the database it comes from
+#[derive(Clone)]
+pub struct AgendaRow {
+    pub db: DBConfig,
+    pub entry: ItemWithList,
+}
+
+/// View of the pending items with a due date, grouped by urgency
+pub struct AgendaComponent {
+    pub rows: Vec<AgendaRow>,
+    /// Index of the selected row (group headers can't be selected)
+    pub selected: Option<usize>,
+    /// Show the items of all databases instead of the current one only
+    pub all_dbs: bool,
+    /// Databases left out of the last load because they could not be read
+    pub skipped_dbs: Vec<String>,
+    list_state: ListState,
+}
+
+impl Default for AgendaComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AgendaComponent {
+    pub fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            selected: None,
+            all_dbs: false,
+            skipped_dbs: Vec::new(),
+            list_state: ListState::default(),
+        }
+    }
+
+    /// Load the agenda of the current database (or of all databases) and keep the selection in bounds
+    ///
+    /// Other databases that can't be read are left out, and listed in `skipped_dbs`.
+    pub async fn load_entries(
+        &mut self,
+        config: &Config,
+        current_db: &DBConfig,
+        pool: &SqlitePool,
+        pools: &PoolRegistry,
+    ) -> Result<()> {
+        let mut rows = Vec::new();
+        self.skipped_dbs.clear();
+        if self.all_dbs {
+            for db in &config.dbs {
+                let entries = if db.connection_str == current_db.connection_str {
+                    ItemWithList::get_agenda(pool).await?
+                } else {
+                    match Self::get_entries_of(db, pools).await {
+                        Ok(entries) => entries,
+                        Err(_) => {
+                            self.skipped_dbs.push(db.name.clone());
+                            continue;
+                        }
+                    }
+                };
+                rows.extend(entries.into_iter().map(|entry| AgendaRow {
+                    db: db.clone(),
+                    entry,
+                }));
+            }
+            // Keep the databases interleaved by due date
+            rows.sort_by_key(|row| row.entry.item.due_date);
+        } else {
+            rows.extend(
+                ItemWithList::get_agenda(pool)
+                    .await?
+                    .into_iter()
+                    .map(|entry| AgendaRow {
+                        db: current_db.clone(),
+                        entry,
+                    }),
+            );
+        }
+        self.rows = rows;
+
+        self.selected = match self.selected {
+            _ if self.rows.is_empty() => None,
+            Some(i) => Some(i.min(self.rows.len() - 1)),
+            None => Some(0),
+        };
+        Ok(())
+    }
+
+    /// Agenda entries of another database
+    async fn get_entries_of(db: &DBConfig, pools: &PoolRegistry) -> Result<Vec<ItemWithList>> {
+        ItemWithList::get_agenda(&pools.get(db).await?).await
+    }
+
+    /// Select next row
+    pub fn select_next(&mut self) {
+        if let Some(i) = self.selected
+            && i + 1 < self.rows.len()
+        {
+            self.selected = Some(i + 1);
+        }
+    }
+
+    /// Select previous row
+    pub fn select_previous(&mut self) {
+        if let Some(i) = self.selected {
+            self.selected = Some(i.saturating_sub(1));
+        }
+    }
+
+    /// Get the selected row
+    pub fn selected_row(&self) -> Option<&AgendaRow> {
+        self.selected.and_then(|i| self.rows.get(i))
+    }
+
+    /// Toggle the completion of the selected item, in whichever database it lives
+    pub async fn toggle_selected_done(
+        &mut self,
+        pool: &SqlitePool,
+        current_db: &DBConfig,
+        pools: &PoolRegistry,
+    ) -> Result<()> {
+        let Some(row) = self.selected_row() else {
+            return Ok(());
+        };
+        let mut item = row.entry.item.clone();
+
+        if row.db.connection_str == current_db.connection_str {
+            item.toggle_done(pool).await?;
+        } else {
+            item.toggle_done(&pools.get(&row.db).await?).await?;
+        }
+        Ok(())
+    }
+
+    /// Render the agenda, with a header line before each group
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, config: Config) {
+        let fg = config.foreground();
+        let hl = config.highlight();
+        // Command hints for the agenda
+        let agenda_command_hints = Line::from(vec![
+            Span::raw(" "),
+            Span::styled(" ↑↓ ", Style::default()),
+            Span::styled("[Space]", Style::default().fg(hl)),
+            Span::styled(" Done", Style::default().fg(fg)),
+            Span::styled(" [Enter]", Style::default().fg(hl)),
+            Span::styled(" Go to list", Style::default().fg(fg)),
+            Span::styled(" [a]", Style::default().fg(hl)),
+            Span::styled(
+                if self.all_dbs {
+                    " Current DB"
+                } else {
+                    " All DBs"
+                },
+                Style::default().fg(fg),
+            ),
+            Span::styled(" [Esc] ", Style::default().fg(hl)),
+            Span::raw(" "),
+        ])
+        .left_aligned();
+
+        let title = if self.all_dbs {
+            "  A G E N D A  (all databases)  "
+        } else {
+            "  A G E N D A  "
+        };
+
+        let block = Block::default()
+            .padding(Padding::new(2, 2, 1, 1))
+            .title_top(Line::styled(title, config.colours.title_style()).left_aligned())
+            .title_bottom(agenda_command_hints)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(config.colours.border_style());
+
+        // Interleave group headers with the rows, remembering where the selection ends up
+        let header_style = Style::default().fg(hl).add_modifier(Modifier::BOLD);
+        let mut items: Vec<ListItem> = Vec::new();
+        let mut selected_line = None;
+        let mut current_bucket: Option<AgendaBucket> = None;
+        for (i, row) in self.rows.iter().enumerate() {
+            let bucket = row.entry.bucket();
+            if current_bucket != Some(bucket) {
+                items.push(ListItem::new(Line::styled(bucket.label(), header_style)));
+                current_bucket = Some(bucket);
+            }
+            if self.selected == Some(i) {
+                selected_line = Some(items.len());
+            }
+
+            let due = row
+                .entry
+                .item
+                .due_date
+                .map(|date| {
+                    date.with_timezone(&chrono::Local)
+                        .format("%a %d %b")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let source = if self.all_dbs {
+                format!("{} / {}", row.db.name, row.entry.list_name)
+            } else {
+                row.entry.list_name.clone()
+            };
+            // Overdue dates stand out
+            let due_style = if bucket == AgendaBucket::Overdue {
+                config.colours.overdue_style()
+            } else {
+                Style::default()
+            };
+            items.push(ListItem::from(Line::from(vec![
+                Span::styled(due, due_style),
+                Span::raw(format!("  {}  ({})", row.entry.item.name, source)),
+            ])));
+        }
+        self.list_state.select(selected_line);
+
+        let list: List = List::new(items)
+            .block(block)
+            .highlight_symbol(" ▸ ") // Selection indicator
+            .highlight_style(config.colours.selected_style())
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.list_state);
+    }
+}
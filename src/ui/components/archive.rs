@@ -0,0 +1,122 @@
+use crate::db::config::Config;
+use crate::db::models::TodoList;
+use anyhow::Result;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, BorderType, Borders, HighlightSpacing, List, ListItem, ListState, Padding,
+    StatefulWidget,
+};
+use sqlx::SqlitePool;
+
+/// View of the archived lists, from which they can be restored
+pub struct ArchiveComponent {
+    pub lists: Vec<TodoList>,
+    pub list_state: ListState,
+}
+
+impl Default for ArchiveComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ArchiveComponent {
+    pub fn new() -> Self {
+        Self {
+            lists: Vec::new(),
+            list_state: ListState::default(),
+        }
+    }
+
+    /// Load archived lists from database and select the first one
+    pub async fn load_lists(&mut self, pool: &SqlitePool) -> Result<()> {
+        self.lists = TodoList::get_archived(pool).await?;
+        self.list_state
+            .select(if self.lists.is_empty() { None } else { Some(0) });
+        Ok(())
+    }
+
+    /// Select next archived list
+    pub fn select_next(&mut self) {
+        self.list_state.select_next();
+    }
+
+    /// Select previous archived list
+    pub fn select_previous(&mut self) {
+        self.list_state.select_previous();
+    }
+
+    /// Restore the selected archived list, returning its ID
+    pub async fn restore_selected_list(&mut self, pool: &SqlitePool) -> Result<Option<i64>> {
+        let Some(i) = self.list_state.selected() else {
+            return Ok(None);
+        };
+        let Some(list) = self.lists.get_mut(i) else {
+            return Ok(None);
+        };
+
+        list.unarchive(pool).await?;
+        let id = list.id;
+
+        // Refresh and keep the selection within bounds
+        self.lists = TodoList::get_archived(pool).await?;
+        if self.lists.is_empty() {
+            self.list_state.select(None);
+        } else if i >= self.lists.len() {
+            self.list_state.select(Some(self.lists.len() - 1));
+        }
+
+        Ok(Some(id))
+    }
+
+    /// Render the list of archived lists
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, config: Config) {
+        let fg = config.foreground();
+        let hl = config.highlight();
+        // Command hints for the archive
+        let archive_command_hints = Line::from(vec![
+            Span::raw(" "),
+            Span::styled(" ↑↓ ", Style::default()),
+            Span::styled("[Enter]", Style::default().fg(hl)),
+            Span::styled(" Restore", Style::default().fg(fg)),
+            Span::styled(" [Esc] ", Style::default().fg(hl)),
+            Span::raw(" "),
+        ])
+        .left_aligned();
+
+        let block = Block::default()
+            .padding(Padding::new(2, 2, 1, 1))
+            .title_top(
+                Line::styled("  A R C H I V E  ", config.colours.title_style()).left_aligned(),
+            )
+            .title_bottom(archive_command_hints)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
+            .border_type(BorderType::Rounded)
+            .border_style(config.colours.border_style());
+
+        // Show when each list was archived
+        let items: Vec<ListItem> = self
+            .lists
+            .iter()
+            .map(|list| {
+                let archived_at = list
+                    .archived_at
+                    .map(|date| date.format(" (%Y-%m-%d)").to_string())
+                    .unwrap_or_default();
+                ListItem::from(format!("{}{}", list.name, archived_at))
+            })
+            .collect();
+
+        let list: List = List::new(items)
+            .block(block)
+            .highlight_symbol(" ▸ ") // Selection indicator
+            .highlight_style(config.colours.selected_style())
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.list_state);
+    }
+}
@@ -0,0 +1,224 @@
+use crate::db::config::Config;
+use crate::db::models::{TodoItem, TodoList};
+use anyhow::Result;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, BorderType, Borders, HighlightSpacing, List, ListItem, ListState, Padding,
+    StatefulWidget, Widget,
+};
+use sqlx::SqlitePool;
+
+/// Kanban board of a list, with one column per status
+pub struct BoardComponent {
+    /// List shown on the board
+    pub list: Option<TodoList>,
+    /// Items of each column
+    pub columns: Vec<Vec<TodoItem>>,
+    /// Index of the selected column
+    pub selected_column: usize,
+    /// Selected card of each column
+    pub column_states: Vec<ListState>,
+}
+
+impl Default for BoardComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BoardComponent {
+    pub fn new() -> Self {
+        Self {
+            list: None,
+            columns: Vec::new(),
+            selected_column: 0,
+            column_states: Vec::new(),
+        }
+    }
+
+    /// Load the board of a list, starting from its first column
+    pub async fn load_list(&mut self, list: TodoList, pool: &SqlitePool) -> Result<()> {
+        self.columns = list.get_board(pool).await?;
+        self.list = Some(list);
+        self.selected_column = 0;
+        self.column_states = self
+            .columns
+            .iter()
+            .map(|cards| {
+                let mut state = ListState::default();
+                state.select(if cards.is_empty() { None } else { Some(0) });
+                state
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Select the column on the left
+    pub fn select_previous_column(&mut self) {
+        self.selected_column = self.selected_column.saturating_sub(1);
+    }
+
+    /// Select the column on the right
+    pub fn select_next_column(&mut self) {
+        if self.selected_column + 1 < self.columns.len() {
+            self.selected_column += 1;
+        }
+    }
+
+    /// Select the card below in the current column
+    pub fn select_next_card(&mut self) {
+        if let Some(state) = self.column_states.get_mut(self.selected_column) {
+            state.select_next();
+        }
+    }
+
+    /// Select the card above in the current column
+    pub fn select_previous_card(&mut self) {
+        if let Some(state) = self.column_states.get_mut(self.selected_column) {
+            state.select_previous();
+        }
+    }
+
+    /// Get the selected card
+    pub fn selected_card(&self) -> Option<&TodoItem> {
+        let j = self.column_states.get(self.selected_column)?.selected()?;
+        self.columns.get(self.selected_column)?.get(j)
+    }
+
+    /// Move the selected card to the column on the left
+    pub async fn move_selected_card_left(&mut self, pool: &SqlitePool) -> Result<()> {
+        if self.selected_column > 0 {
+            self.move_selected_card(pool, self.selected_column - 1)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Move the selected card to the column on the right
+    pub async fn move_selected_card_right(&mut self, pool: &SqlitePool) -> Result<()> {
+        if self.selected_column + 1 < self.columns.len() {
+            self.move_selected_card(pool, self.selected_column + 1)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Move the selected card to another column, keeping it selected
+    async fn move_selected_card(&mut self, pool: &SqlitePool, column: usize) -> Result<()> {
+        let (Some(list), Some(card)) = (self.list.clone(), self.selected_card().cloned()) else {
+            return Ok(());
+        };
+
+        let mut card = card;
+        card.move_to_column(pool, &list.statuses(), column).await?;
+        self.columns = list.get_board(pool).await?;
+
+        // Keep every column's selection within bounds
+        for (state, cards) in self.column_states.iter_mut().zip(&self.columns) {
+            match state.selected() {
+                _ if cards.is_empty() => state.select(None),
+                Some(j) if j >= cards.len() => state.select(Some(cards.len() - 1)),
+                None => state.select(Some(0)),
+                _ => {}
+            }
+        }
+
+        // Follow the card to its new column
+        self.selected_column = column;
+        if let Some(j) = self.columns[column].iter().position(|i| i.id == card.id) {
+            self.column_states[column].select(Some(j));
+        }
+        Ok(())
+    }
+
+    /// Render the columns side by side
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, config: Config) {
+        let fg = config.foreground();
+        let hl = config.highlight();
+        // Command hints for the board
+        let board_command_hints = Line::from(vec![
+            Span::raw(" "),
+            Span::styled(" ←↓↑→ ", Style::default()),
+            Span::styled("[Alt + ←→]", Style::default().fg(hl)),
+            Span::styled(" Move card", Style::default().fg(fg)),
+            Span::styled(" [Esc] ", Style::default().fg(hl)),
+            Span::raw(" "),
+        ])
+        .left_aligned();
+
+        let title = match &self.list {
+            Some(list) => format!("  B O A R D  ({})  ", list.name),
+            None => "  B O A R D  ".to_string(),
+        };
+
+        let block = Block::default()
+            .padding(Padding::new(1, 1, 1, 0))
+            .title_top(Line::styled(title, config.colours.title_style()).left_aligned())
+            .title_bottom(board_command_hints)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(config.colours.border_style());
+        let inner_area = block.inner(area);
+        block.render(area, buf);
+
+        let Some(list) = &self.list else {
+            return;
+        };
+        let statuses = list.statuses();
+
+        // Columns share the width equally
+        let column_areas = Layout::horizontal(
+            statuses
+                .iter()
+                .map(|_| Constraint::Ratio(1, statuses.len() as u32)),
+        )
+        .split(inner_area);
+
+        for (i, status) in statuses.iter().enumerate() {
+            let cards = &self.columns[i];
+            let is_selected = i == self.selected_column;
+
+            // Highlight the border of the selected column
+            let border_style = if is_selected {
+                Style::default().fg(hl)
+            } else {
+                config.colours.border_style()
+            };
+            let column_block = Block::default()
+                .padding(Padding::new(1, 1, 0, 0))
+                .title_top(
+                    Line::styled(
+                        format!(" {} ({}) ", status, cards.len()),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    )
+                    .centered(),
+                )
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(border_style);
+
+            let items: Vec<ListItem> = cards
+                .iter()
+                .map(|card| ListItem::from(card.name.clone()))
+                .collect();
+
+            // Only the selected column shows its selection
+            let highlight_style = if is_selected {
+                config.colours.selected_style()
+            } else {
+                Style::default()
+            };
+            let column = List::new(items)
+                .block(column_block)
+                .highlight_symbol("▸ ")
+                .highlight_style(highlight_style)
+                .highlight_spacing(HighlightSpacing::Always);
+
+            StatefulWidget::render(column, column_areas[i], buf, &mut self.column_states[i]);
+        }
+    }
+}
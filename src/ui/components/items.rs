@@ -1,26 +1,37 @@
 use crate::db::config::Config;
-use crate::db::models::{NewTodoItem, TodoItem, UIItem, UIList};
+use crate::db::models::{SortMode, TodoItem, UIItem, UIList};
+use crate::db::quick_add::QuickAdd;
 use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::Style;
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{
     Block, BorderType, Borders, HighlightSpacing, List, ListItem, Padding, StatefulWidget, Widget,
 };
 use sqlx::SqlitePool;
-use std::str::FromStr;
 use textwrap::wrap;
 pub struct ItemsComponent;
 
 impl ItemsComponent {
-    /// Return the style for a todo item based on its completion status
-    fn item_style(ui_item: &UIItem) -> Style {
-        if ui_item.item.is_done {
-            // Strike through completed items
-            Style::default().add_modifier(Modifier::CROSSED_OUT)
+    /// Return the style for a todo item based on its completion, priority and due date
+    fn item_style(ui_item: &UIItem, config: &Config, today: NaiveDate) -> Style {
+        let item = &ui_item.item;
+        if item.is_done {
+            return config.colours.done_style();
+        }
+
+        let style = item
+            .priority
+            .as_ref()
+            .map_or_else(Style::default, |priority| {
+                config.colours.priority_style(priority)
+            });
+        if item.is_overdue(today) {
+            style.patch(config.colours.overdue_style())
         } else {
-            Style::default()
+            style
         }
     }
 
@@ -63,20 +74,89 @@ impl ItemsComponent {
     pub async fn toggle_item_done(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
         if let Some(j) = ui_list.item_state.selected() {
             ui_list.items[j].item.toggle_done(pool).await?;
+
+            // Completed items vanish from lists that hide them (or from smart
+            // lists no longer matching them) or move to the bottom
+            if ui_list.list.hide_done
+                || ui_list.list.sort_mode == SortMode::DoneLast
+                || ui_list.is_smart()
+            {
+                ui_list.update_items(pool).await?;
+            }
         }
         Ok(())
     }
 
-    /// Create a new item in the given list
-    pub async fn create_item(ui_list: &mut UIList, name: String, pool: &SqlitePool) -> Result<()> {
-        let new_item = NewTodoItem {
-            name,
-            list_id: ui_list.list.id,
-            priority: None,
-            due_date: None,
-        };
+    /// Show or hide the completed items of the list
+    pub async fn toggle_hide_done(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
+        // Smart lists pick completed items through their criteria
+        if ui_list.is_smart() {
+            return Ok(());
+        }
+        let hide_done = !ui_list.list.hide_done;
+        ui_list.list.set_hide_done(pool, hide_done).await?;
+        ui_list.update_items(pool).await?;
+        Ok(())
+    }
+
+    /// Switch the list to the next sort mode, keeping the same item selected
+    pub async fn cycle_sort_mode(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
+        // Smart lists keep the order of their query
+        if ui_list.is_smart() {
+            return Ok(());
+        }
+        let selected_id = ui_list
+            .item_state
+            .selected()
+            .and_then(|j| ui_list.items.get(j))
+            .map(|ui_item| ui_item.item.id);
+
+        let sort_mode = ui_list.list.sort_mode.next();
+        ui_list.list.set_sort_mode(pool, sort_mode).await?;
+        ui_list.update_items(pool).await?;
+
+        if let Some(id) = selected_id
+            && let Some(j) = ui_list.items.iter().position(|i| i.item.id == id)
+        {
+            ui_list.item_state.select(Some(j));
+        }
+        Ok(())
+    }
+
+    /// Turn the current sort order into the manual one before moving items by hand
+    async fn ensure_manual_sort(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
+        if ui_list.list.sort_mode != SortMode::Manual {
+            ui_list.list.rebaseline_ordering(pool).await?;
+            ui_list.update_items(pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete all completed items of the list
+    pub async fn clear_done_items(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
+        // Smart lists span several lists, so this is left to the real ones
+        if ui_list.is_smart() {
+            return Ok(());
+        }
+        ui_list.list.clear_done(pool).await?;
+        ui_list.update_items(pool).await?;
+        Ok(())
+    }
 
-        TodoItem::create(pool, new_item).await?;
+    /// Create a new item in the given list from its quick-add fields
+    pub async fn create_item(
+        ui_list: &mut UIList,
+        quick_add: &QuickAdd,
+        pool: &SqlitePool,
+    ) -> Result<()> {
+        // Smart lists have no list of their own to hold the item
+        if ui_list.is_smart() {
+            return Ok(());
+        }
+        let today = chrono::Local::now().date_naive();
+        let new_item = quick_add.new_item(ui_list.list.id, today);
+
+        TodoItem::create_with_tags(pool, new_item, quick_add.tags.clone()).await?;
         ui_list.update_items(pool).await?;
         Ok(())
     }
@@ -93,6 +173,28 @@ impl ItemsComponent {
         Ok(())
     }
 
+    /// Set or clear the due date of the selected item, keeping it selected
+    pub async fn set_selected_item_due_date(
+        ui_list: &mut UIList,
+        due: Option<DateTime<Utc>>,
+        pool: &SqlitePool,
+    ) -> Result<()> {
+        if let Some(j) = ui_list.item_state.selected() {
+            let mut item = ui_list.items[j].item.clone();
+            match due {
+                Some(due) => item.update_due_date(pool, due).await?,
+                None => item.clear_due_date(pool).await?,
+            }
+
+            // Sorting by due date may move the item
+            ui_list.update_items(pool).await?;
+            if let Some(j) = ui_list.items.iter().position(|i| i.item.id == item.id) {
+                ui_list.item_state.select(Some(j));
+            }
+        }
+        Ok(())
+    }
+
     /// Delete the currently selected item
     pub async fn delete_selected_item(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
         if let Some(j) = ui_list.item_state.selected() {
@@ -112,101 +214,303 @@ impl ItemsComponent {
         Ok(())
     }
 
-    /// Move the currently selected item up
+    /// Move the currently selected item above the item shown above it
     pub async fn move_selected_item_up(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
-        if let Some(j) = ui_list.item_state.selected() {
+        // Smart lists keep the order of their query
+        if ui_list.is_smart() {
+            return Ok(());
+        }
+        if let Some(j) = ui_list.item_state.selected()
+            && j > 0
+            && j < ui_list.items.len()
+        {
+            Self::ensure_manual_sort(ui_list, pool).await?;
             let mut item = ui_list.items[j].item.clone();
-            item.move_up(pool).await?;
+            let above = ui_list.items[j - 1].item.id;
+            let position = Self::position_of(pool, &item, above).await?.unwrap_or(0);
+            item.move_to_position(pool, position).await?;
 
-            // Update list elements to reflect the new order
+            // Update list elements and follow the moved item
             ui_list.update_items(pool).await?;
-
-            // Adjust selection to follow the moved item
-            if j > 0 {
-                ui_list.item_state.select(Some(j - 1));
-            }
+            Self::select_item_by_id(ui_list, item.id);
         }
         Ok(())
     }
 
-    /// Move the currently selected item down
+    /// Move the currently selected item below the item shown below it
     pub async fn move_selected_item_down(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
+        // Smart lists keep the order of their query
+        if ui_list.is_smart() {
+            return Ok(());
+        }
+        if let Some(j) = ui_list.item_state.selected()
+            && j + 1 < ui_list.items.len()
+        {
+            Self::ensure_manual_sort(ui_list, pool).await?;
+            let mut item = ui_list.items[j].item.clone();
+            let below = ui_list.items[j + 1].item.id;
+            let position = Self::position_of(pool, &item, below)
+                .await?
+                .map_or(usize::MAX, |k| k + 1);
+            item.move_to_position(pool, position).await?;
+
+            // Update list elements and follow the moved item
+            ui_list.update_items(pool).await?;
+            Self::select_item_by_id(ui_list, item.id);
+        }
+        Ok(())
+    }
+
+    /// Position of another item among the items of `item`'s list, leaving `item` out
+    ///
+    /// Items hidden or filtered out count too, so that moves are relative to
+    /// what is on screen without losing the place of the rest.
+    async fn position_of(pool: &SqlitePool, item: &TodoItem, id: i64) -> Result<Option<usize>> {
+        Ok(TodoItem::get_by_list_id(pool, item.list_id)
+            .await?
+            .into_iter()
+            .filter(|other| other.id != item.id)
+            .position(|other| other.id == id))
+    }
+
+    /// Select the item with the given id, if it is shown
+    fn select_item_by_id(ui_list: &mut UIList, id: i64) {
+        if let Some(j) = ui_list.items.iter().position(|i| i.item.id == id) {
+            ui_list.item_state.select(Some(j));
+        }
+    }
+
+    /// Move the currently selected item to a position in its list, 0 being the top
+    async fn move_selected_item_to_position(
+        ui_list: &mut UIList,
+        position: usize,
+        pool: &SqlitePool,
+    ) -> Result<()> {
+        // Smart lists keep the order of their query
+        if ui_list.is_smart() {
+            return Ok(());
+        }
         if let Some(j) = ui_list.item_state.selected() {
+            Self::ensure_manual_sort(ui_list, pool).await?;
             let mut item = ui_list.items[j].item.clone();
-            item.move_down(pool).await?;
+            item.move_to_position(pool, position).await?;
 
-            // Update list elements to reflect the new order
             ui_list.update_items(pool).await?;
+            Self::select_item_by_id(ui_list, item.id);
+        }
+        Ok(())
+    }
 
-            // Adjust selection to follow the moved item
-            if j + 1 < ui_list.items.len() {
-                ui_list.item_state.select(Some(j + 1));
-            }
+    /// Move the currently selected item to the top of the list
+    pub async fn move_selected_item_to_top(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
+        Self::move_selected_item_to_position(ui_list, 0, pool).await
+    }
+
+    /// Move the currently selected item to the bottom of the list
+    pub async fn move_selected_item_to_bottom(
+        ui_list: &mut UIList,
+        pool: &SqlitePool,
+    ) -> Result<()> {
+        Self::move_selected_item_to_position(ui_list, usize::MAX, pool).await
+    }
+
+    /// Pick up the selected item so it follows the cursor, returning whether there was one
+    ///
+    /// A sorted list switches to manual sorting first, keeping the order on screen.
+    pub async fn grab_selected_item(ui_list: &mut UIList, pool: &SqlitePool) -> Result<bool> {
+        let Some(id) = ui_list
+            .item_state
+            .selected()
+            .and_then(|j| ui_list.items.get(j))
+            .map(|ui_item| ui_item.item.id)
+        else {
+            return Ok(false);
+        };
+        if ui_list.is_smart() {
+            return Ok(false);
+        }
+        Self::ensure_manual_sort(ui_list, pool).await?;
+        Self::select_item_by_id(ui_list, id);
+        Ok(true)
+    }
+
+    /// Carry the grabbed item to another place on screen, without saving it yet
+    fn carry_grabbed_item(ui_list: &mut UIList, to: usize) {
+        if let Some(j) = ui_list.item_state.selected()
+            && j < ui_list.items.len()
+        {
+            let ui_item = ui_list.items.remove(j);
+            let to = to.min(ui_list.items.len());
+            ui_list.items.insert(to, ui_item);
+            ui_list.item_state.select(Some(to));
+        }
+    }
+
+    /// Carry the grabbed item one step up
+    pub fn carry_grabbed_item_up(ui_list: &mut UIList) {
+        if let Some(j) = ui_list.item_state.selected() {
+            Self::carry_grabbed_item(ui_list, j.saturating_sub(1));
+        }
+    }
+
+    /// Carry the grabbed item one step down
+    pub fn carry_grabbed_item_down(ui_list: &mut UIList) {
+        if let Some(j) = ui_list.item_state.selected() {
+            Self::carry_grabbed_item(ui_list, j + 1);
+        }
+    }
+
+    /// Carry the grabbed item to the top of the list
+    pub fn carry_grabbed_item_to_top(ui_list: &mut UIList) {
+        Self::carry_grabbed_item(ui_list, 0);
+    }
+
+    /// Carry the grabbed item to the bottom of the list
+    pub fn carry_grabbed_item_to_bottom(ui_list: &mut UIList) {
+        Self::carry_grabbed_item(ui_list, usize::MAX);
+    }
+
+    /// Save the grabbed item where it was carried to
+    pub async fn drop_grabbed_item(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
+        let Some(j) = ui_list.item_state.selected() else {
+            return Ok(());
+        };
+        let Some(mut item) = ui_list.items.get(j).map(|ui_item| ui_item.item.clone()) else {
+            return Ok(());
+        };
+
+        // Drop it right after the item now above it
+        let position = match j.checked_sub(1).map(|k| ui_list.items[k].item.id) {
+            Some(above) => Self::position_of(pool, &item, above)
+                .await?
+                .map_or(0, |k| k + 1),
+            None => 0,
+        };
+        item.move_to_position(pool, position).await?;
+
+        ui_list.update_items(pool).await?;
+        Self::select_item_by_id(ui_list, item.id);
+        Ok(())
+    }
+
+    /// Put the grabbed item back where it was
+    pub async fn cancel_grab(ui_list: &mut UIList, pool: &SqlitePool) -> Result<()> {
+        let id = ui_list
+            .item_state
+            .selected()
+            .and_then(|j| ui_list.items.get(j))
+            .map(|ui_item| ui_item.item.id);
+        ui_list.update_items(pool).await?;
+        if let Some(id) = id {
+            Self::select_item_by_id(ui_list, id);
         }
         Ok(())
     }
 
-    /// Render the list of todo items for the selected list
+    /// Render the list of todo items for the selected list, `grabbed` when its selected item is being moved
     pub fn render(
         selected_list: Option<&mut UIList>,
+        grabbed: bool,
         area: Rect,
         buf: &mut Buffer,
         config: Config,
     ) {
         let fg = config.foreground();
         let hl = config.highlight();
-        let bg = config.background();
-        // Command hints for items
-        let list_command_hints = Line::from(vec![
-            Span::raw(" "),
-            Span::styled(" ↓↑ ", Style::default()),
-            Span::styled("[a]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled("dd", Style::default().fg(Color::from_str(fg).unwrap())),
-            Span::styled(" [d]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled("el", Style::default().fg(Color::from_str(fg).unwrap())),
-            Span::styled(" [m]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled("odify", Style::default().fg(Color::from_str(fg).unwrap())),
-            Span::styled(" [c]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled(
-                "opy items ",
-                Style::default().fg(Color::from_str(fg).unwrap()),
-            ),
-            Span::raw(" "),
-        ])
-        .left_aligned();
+        // Command hints for items, or for dropping the grabbed one
+        let list_command_hints = if grabbed {
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(" ↓↑ ", Style::default()),
+                Span::styled("[t]", Style::default().fg(hl)),
+                Span::styled("op", Style::default().fg(fg)),
+                Span::styled(" [b]", Style::default().fg(hl)),
+                Span::styled("ottom", Style::default().fg(fg)),
+                Span::styled(" [enter]", Style::default().fg(hl)),
+                Span::styled(" drop", Style::default().fg(fg)),
+                Span::styled(" [esc]", Style::default().fg(hl)),
+                Span::styled(" cancel ", Style::default().fg(fg)),
+                Span::raw(" "),
+            ])
+            .left_aligned()
+        } else {
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(" ↓↑ ", Style::default()),
+                Span::styled("[a]", Style::default().fg(hl)),
+                Span::styled("dd", Style::default().fg(fg)),
+                Span::styled(" [d]", Style::default().fg(hl)),
+                Span::styled("el", Style::default().fg(fg)),
+                Span::styled(" [m]", Style::default().fg(hl)),
+                Span::styled("odify", Style::default().fg(fg)),
+                Span::styled(" [c]", Style::default().fg(hl)),
+                Span::styled("opy items", Style::default().fg(fg)),
+                Span::styled(" [?]", Style::default().fg(hl)),
+                Span::styled(" keys ", Style::default().fg(fg)),
+                Span::raw(" "),
+            ])
+            .left_aligned()
+        };
 
         // Add "quit" hint, in the bottom right corner
         let quit_hint = Line::from(vec![
             Span::raw(" "),
-            Span::styled("[q]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled("uit ", Style::default().fg(Color::from_str(fg).unwrap())),
+            Span::styled("[q]", Style::default().fg(hl)),
+            Span::styled("uit ", Style::default().fg(fg)),
             Span::raw(" "),
         ])
         .right_aligned();
 
+        // Signal when items are sorted, filtered or completed ones are hidden, or what a smart list matches
+        let mut title = "  I T E M S  ".to_string();
+        if let Some(ui_list) = &selected_list {
+            if let Some(smart_list) = &ui_list.smart_list {
+                title.push_str(&format!("({})  ", smart_list.describe()));
+            }
+            if let Some(filter) = &ui_list.filter {
+                title.push_str(&format!("(filter: {})  ", filter));
+            }
+            if ui_list.list.sort_mode != SortMode::Manual {
+                title.push_str(&format!("(by {})  ", ui_list.list.sort_mode));
+            }
+            if ui_list.list.hide_done {
+                title.push_str("(done hidden)  ");
+            }
+        }
+        if grabbed {
+            title.push_str("(moving)  ");
+        }
+
         let block = Block::default()
             .padding(Padding::new(2, 2, 1, 1))
-            .title_top(Line::raw("  I T E M S  ").left_aligned())
+            .title_top(Line::styled(title, config.colours.title_style()).left_aligned())
             .title_bottom(list_command_hints)
             .title_bottom(quit_hint)
             .title_alignment(Alignment::Center)
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded);
+            .border_type(BorderType::Rounded)
+            .border_style(config.colours.border_style());
 
         if let Some(ui_list) = selected_list {
             // Calculate available width for text wrapping
             // Account for: highlight symbol " ▸ " (4 chars) + padding (2+2) + borders (2)
-            let highlight_symbol = " ▸ ";
+            let highlight_symbol = if grabbed { " ⇅ " } else { " ▸ " };
             let highlight_width = highlight_symbol.chars().count();
             let available_width = area.width.saturating_sub(highlight_width as u16 + 6) as usize;
 
             // Wrap each item's content to fit the available width
+            let today = Local::now().date_naive();
             let items: Vec<ListItem> = ui_list
                 .items
                 .iter()
                 .map(|ui_item| {
-                    let name = &ui_item.item.name;
-                    let style = Self::item_style(ui_item);
+                    // Tags follow the name
+                    let mut name = ui_item.item.name.clone();
+                    for tag in ui_item.item.tags() {
+                        name.push_str(&format!(" #{}", tag));
+                    }
+                    let name = &name;
+                    let style = Self::item_style(ui_item, &config, today);
 
                     let wrapped_lines: Vec<Line> = if available_width > 0 {
                         wrap(name, available_width)
@@ -224,12 +528,12 @@ impl ItemsComponent {
             let list: List = List::new(items)
                 .block(block)
                 .highlight_symbol(highlight_symbol)
-                .highlight_style(
-                    // Swap foreground and background for selected item
-                    Style::default()
-                        .bg(Color::from_str(fg).unwrap())
-                        .fg(Color::from_str(bg).unwrap()),
-                )
+                .highlight_style(if grabbed {
+                    // The grabbed item stands out
+                    config.colours.selected_style().bg(hl)
+                } else {
+                    config.colours.selected_style()
+                })
                 .highlight_spacing(HighlightSpacing::Always);
 
             StatefulWidget::render(list, area, buf, &mut ui_list.item_state);
@@ -1,20 +1,23 @@
 use crate::db::config::Config;
 use crate::db::models::{NewTodoList, TodoList, UIList};
+use crate::db::query::Query;
 use anyhow::Result;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Alignment, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{
     Block, BorderType, Borders, HighlightSpacing, List, ListItem, ListState, Padding,
     StatefulWidget,
 };
 use sqlx::SqlitePool;
-use std::str::FromStr;
+use std::collections::HashMap;
 
 pub struct ListsComponent {
     pub lists: Vec<UIList>,
     pub list_state: ListState,
+    /// Query narrowing down the items of the real lists
+    pub filter: Option<Query>,
 }
 
 impl Default for ListsComponent {
@@ -28,12 +31,100 @@ impl ListsComponent {
         Self {
             lists: Vec::new(),
             list_state: ListState::default(),
+            filter: None,
         }
     }
 
-    /// Initialize lists from database
+    /// Initialize lists from database, smart lists coming after the real ones
     pub async fn load_lists(&mut self, pool: &SqlitePool) -> Result<()> {
         self.lists = UIList::get_all(pool).await?;
+        if self.filter.is_some() {
+            self.apply_filter(pool).await?;
+        }
+        self.lists.extend(UIList::get_smart_lists(pool).await?);
+        Ok(())
+    }
+
+    /// Show only the items matching a query in the real lists, or all of them again with `None`
+    pub async fn set_filter(&mut self, filter: Option<Query>, pool: &SqlitePool) -> Result<()> {
+        self.filter = filter.filter(|query| !query.is_empty());
+        self.apply_filter(pool).await
+    }
+
+    async fn apply_filter(&mut self, pool: &SqlitePool) -> Result<()> {
+        // One query for all the lists, rather than one per list
+        let mut items_by_list = UIList::get_items_by_list(pool, self.filter.as_ref()).await?;
+        for ui_list in self.lists.iter_mut().filter(|ui_list| !ui_list.is_smart()) {
+            ui_list.filter = self.filter.clone();
+            ui_list.set_items(items_by_list.remove(&ui_list.list.id).unwrap_or_default());
+        }
+        Ok(())
+    }
+
+    /// Re-fetch the items of every list, keeping the selections
+    ///
+    /// An item can show in a real list and in smart lists at once, so this
+    /// keeps them all in sync after it changes. Without smart lists there
+    /// is nothing to do.
+    pub async fn sync_items(&mut self, pool: &SqlitePool) -> Result<()> {
+        if !self.lists.iter().any(UIList::is_smart) {
+            return Ok(());
+        }
+        self.reload_items(pool).await
+    }
+
+    /// Re-fetch the items of every list, keeping the selections
+    ///
+    /// The real lists share a single query; each smart list runs its own.
+    pub async fn reload_items(&mut self, pool: &SqlitePool) -> Result<()> {
+        self.apply_filter(pool).await?;
+        for ui_list in self.lists.iter_mut().filter(|ui_list| ui_list.is_smart()) {
+            ui_list.update_items(pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Reload every list from the database, keeping what was selected
+    ///
+    /// Lists and items are followed by ID wherever they moved. When they are
+    /// gone, the selection stays at the same position instead.
+    pub async fn reload(&mut self, pool: &SqlitePool) -> Result<()> {
+        let key = |ui_list: &UIList| (ui_list.is_smart(), ui_list.list.id);
+        let selected_list = self
+            .list_state
+            .selected()
+            .map(|i| (i, self.lists.get(i).map(key)));
+        let selected_items: HashMap<(bool, i64), (usize, Option<i64>)> = self
+            .lists
+            .iter()
+            .filter_map(|ui_list| {
+                let j = ui_list.item_state.selected()?;
+                let item_id = ui_list.items.get(j).map(|ui_item| ui_item.item.id);
+                Some((key(ui_list), (j, item_id)))
+            })
+            .collect();
+
+        self.load_lists(pool).await?;
+
+        for ui_list in self.lists.iter_mut() {
+            let Some(&(j, item_id)) = selected_items.get(&key(ui_list)) else {
+                continue;
+            };
+            let j = item_id
+                .and_then(|id| ui_list.items.iter().position(|i| i.item.id == id))
+                .unwrap_or_else(|| j.min(ui_list.items.len().saturating_sub(1)));
+            ui_list
+                .item_state
+                .select((!ui_list.items.is_empty()).then_some(j));
+        }
+
+        if let Some((i, list_key)) = selected_list {
+            let i = list_key
+                .and_then(|list_key| self.lists.iter().position(|l| key(l) == list_key))
+                .unwrap_or_else(|| i.min(self.lists.len().saturating_sub(1)));
+            self.list_state
+                .select((!self.lists.is_empty()).then_some(i));
+        }
         Ok(())
     }
 
@@ -57,6 +148,39 @@ impl ListsComponent {
         self.list_state.select_last();
     }
 
+    /// Select the list with the given ID, returning false if there is none
+    pub fn select_list_by_id(&mut self, id: i64) -> bool {
+        match self
+            .lists
+            .iter()
+            .position(|ui_list| !ui_list.is_smart() && ui_list.list.id == id)
+        {
+            Some(i) => {
+                self.list_state.select(Some(i));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Select the first list with the given name, returning false if there is none
+    pub fn select_list_by_name(&mut self, name: &str) -> bool {
+        match self.position_of_list_by_name(name) {
+            Some(i) => {
+                self.list_state.select(Some(i));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Index of the first list with the given name, leaving the selection alone
+    pub fn position_of_list_by_name(&self, name: &str) -> Option<usize> {
+        self.lists
+            .iter()
+            .position(|ui_list| !ui_list.is_smart() && ui_list.list.name == name)
+    }
+
     /// Get currently selected list index
     pub fn selected(&self) -> Option<usize> {
         self.list_state.selected()
@@ -102,7 +226,9 @@ impl ListsComponent {
         lists_component: &mut ListsComponent,
         pool: &SqlitePool,
     ) -> Result<()> {
-        if let Some(i) = lists_component.list_state.selected() {
+        if let Some(i) = lists_component.list_state.selected()
+            && !lists_component.lists[i].is_smart()
+        {
             let mut list = lists_component.lists[i].list.clone();
             list.move_up(pool).await?;
 
@@ -122,29 +248,67 @@ impl ListsComponent {
         lists_component: &mut ListsComponent,
         pool: &SqlitePool,
     ) -> Result<()> {
-        if let Some(i) = lists_component.list_state.selected() {
+        if let Some(i) = lists_component.list_state.selected()
+            && !lists_component.lists[i].is_smart()
+        {
             let mut list = lists_component.lists[i].list.clone();
             list.move_down(pool).await?;
 
             // Refresh lists to reflect the new order
             lists_component.refresh_lists(pool).await?;
 
-            // Adjust selection to follow the moved list
-            if i + 1 < lists_component.lists.len() {
-                lists_component.list_state.select(Some(i + 1));
-            }
+            // Follow the moved list, which stays put above the smart lists at the bottom
+            lists_component.select_list_by_id(list.id);
         }
         Ok(())
     }
 
+    /// Move the currently selected list to a position among the lists, 0 being the top
+    async fn move_selected_list_to_position(
+        lists_component: &mut ListsComponent,
+        position: usize,
+        pool: &SqlitePool,
+    ) -> Result<()> {
+        if let Some(i) = lists_component.list_state.selected()
+            && !lists_component.lists[i].is_smart()
+        {
+            let mut list = lists_component.lists[i].list.clone();
+            list.move_to_position(pool, position).await?;
+
+            // Refresh lists and follow the moved one
+            lists_component.refresh_lists(pool).await?;
+            lists_component.select_list_by_id(list.id);
+        }
+        Ok(())
+    }
+
+    /// Move the currently selected list to the top
+    pub async fn move_selected_list_to_top(
+        lists_component: &mut ListsComponent,
+        pool: &SqlitePool,
+    ) -> Result<()> {
+        Self::move_selected_list_to_position(lists_component, 0, pool).await
+    }
+
+    /// Move the currently selected list to the bottom, above the smart lists
+    pub async fn move_selected_list_to_bottom(
+        lists_component: &mut ListsComponent,
+        pool: &SqlitePool,
+    ) -> Result<()> {
+        Self::move_selected_list_to_position(lists_component, usize::MAX, pool).await
+    }
+
     /// Delete the currently selected list (static method like ItemsComponent)
     pub async fn delete_selected_list_static(
         lists_component: &mut ListsComponent,
         pool: &SqlitePool,
     ) -> Result<()> {
         if let Some(i) = lists_component.list_state.selected() {
-            let list = lists_component.lists[i].list.clone();
-            list.delete(pool).await?;
+            // Deleting a smart list only forgets the filter
+            match lists_component.lists[i].smart_list.clone() {
+                Some(smart_list) => smart_list.delete(pool).await?,
+                None => lists_component.lists[i].list.clone().delete(pool).await?,
+            }
 
             // Refresh the lists from database
             lists_component.load_lists(pool).await?;
@@ -161,6 +325,32 @@ impl ListsComponent {
         Ok(())
     }
 
+    /// Archive the currently selected list (static method like ItemsComponent)
+    pub async fn archive_selected_list(
+        lists_component: &mut ListsComponent,
+        pool: &SqlitePool,
+    ) -> Result<()> {
+        if let Some(i) = lists_component.list_state.selected()
+            && !lists_component.lists[i].is_smart()
+        {
+            let mut list = lists_component.lists[i].list.clone();
+            list.archive(pool).await?;
+
+            // Refresh the lists from database
+            lists_component.load_lists(pool).await?;
+
+            // Adjust selection after archiving
+            if lists_component.lists.is_empty() {
+                lists_component.list_state.select(None);
+            } else if i >= lists_component.lists.len() {
+                lists_component
+                    .list_state
+                    .select(Some(lists_component.lists.len() - 1));
+            }
+        }
+        Ok(())
+    }
+
     /// Create a new list and refresh data (static method like ItemsComponent)
     pub async fn create_list(
         lists_component: &mut ListsComponent,
@@ -180,8 +370,13 @@ impl ListsComponent {
         pool: &SqlitePool,
     ) -> Result<()> {
         if let Some(i) = lists_component.list_state.selected() {
-            let mut list = lists_component.lists[i].list.clone();
-            list.update_name(pool, name).await?;
+            match lists_component.lists[i].smart_list.clone() {
+                Some(mut smart_list) => smart_list.update_name(pool, name).await?,
+                None => {
+                    let mut list = lists_component.lists[i].list.clone();
+                    list.update_name(pool, name).await?;
+                }
+            }
             lists_component.load_lists(pool).await?;
         }
         Ok(())
@@ -191,45 +386,46 @@ impl ListsComponent {
     pub fn render(&mut self, area: Rect, buf: &mut Buffer, config: Config) {
         let fg = config.foreground();
         let hl = config.highlight();
-        let bg = config.background();
         // Command hints for lists
         let list_command_hints = Line::from(vec![
             Span::raw(" "),
             Span::styled(" w,s ", Style::default()),
-            Span::styled("[A]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled("dd", Style::default().fg(Color::from_str(fg).unwrap())),
-            Span::styled(" [D]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled("el", Style::default().fg(Color::from_str(fg).unwrap())),
-            Span::styled(" [M]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled("odify ", Style::default().fg(Color::from_str(fg).unwrap())),
+            Span::styled("[A]", Style::default().fg(hl)),
+            Span::styled("dd", Style::default().fg(fg)),
+            Span::styled(" [D]", Style::default().fg(hl)),
+            Span::styled("el", Style::default().fg(fg)),
+            Span::styled(" [M]", Style::default().fg(hl)),
+            Span::styled("odify ", Style::default().fg(fg)),
             Span::raw(" "),
         ])
         .left_aligned();
 
         let block = Block::default()
             .padding(Padding::new(2, 2, 1, 1))
-            .title_top(Line::raw("  L I S T S  ").left_aligned())
+            .title_top(Line::styled("  L I S T S  ", config.colours.title_style()).left_aligned())
             .title_bottom(list_command_hints)
             .title_alignment(Alignment::Center)
             .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
-            .border_type(BorderType::Rounded);
+            .border_type(BorderType::Rounded)
+            .border_style(config.colours.border_style());
 
-        // Convert lists to display items
+        // Convert lists to display items, marking smart lists
         let items: Vec<ListItem> = self
             .lists
             .iter()
-            .map(|ui_list| ListItem::from(ui_list.list.name.clone()))
+            .map(|ui_list| match &ui_list.smart_list {
+                Some(smart_list) => ListItem::from(Line::from(vec![
+                    Span::styled("✦ ", Style::default().fg(hl)),
+                    Span::raw(smart_list.name.clone()),
+                ])),
+                None => ListItem::from(ui_list.list.name.clone()),
+            })
             .collect();
 
         let list: List = List::new(items)
             .block(block)
             .highlight_symbol(" ▸ ") // Selection indicator
-            .highlight_style(
-                // Swap foreground and background for selected item
-                Style::default()
-                    .bg(Color::from_str(fg).unwrap())
-                    .fg(Color::from_str(bg).unwrap()),
-            )
+            .highlight_style(config.colours.selected_style())
             .highlight_spacing(HighlightSpacing::Always);
 
         StatefulWidget::render(list, area, buf, &mut self.list_state);
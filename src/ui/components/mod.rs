@@ -1,15 +1,26 @@
+pub mod agenda;
+pub mod archive;
+pub mod board;
 pub mod db_selector;
 pub mod input_states;
 pub mod items;
 pub mod lists;
 pub mod logo;
 pub mod popups;
+pub mod status;
+pub mod trash;
 
+pub use agenda::AgendaComponent;
+pub use archive::ArchiveComponent;
+pub use board::BoardComponent;
 pub use db_selector::DBSelector;
 pub use input_states::InputState;
 pub use items::ItemsComponent;
 pub use lists::ListsComponent;
 pub use logo::Logo;
 pub use popups::{
-    AddDBPopUp, AddItemPopUp, AddListPopUp, ChangeDBPopUp, ModifyItemPopUp, ModifyListPopUp,
+    AddDBPopUp, AddItemPopUp, AddListPopUp, ChangeDBPopUp, DueDatePopUp, FilterPopUp,
+    ItemKeysPopUp, ModifyItemPopUp, ModifyListPopUp, RegisterDBPopUp,
 };
+pub use status::{StatusBar, StatusMessage};
+pub use trash::TrashComponent;
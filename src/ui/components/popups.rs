@@ -1,13 +1,15 @@
 use crate::db::config::Config;
+use crate::db::dates::parse_date;
+use crate::db::query::Query;
+use crate::db::quick_add::QuickAdd;
 use crate::ui::cursor::CursorState;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Style};
-use ratatui::text::{Line, Span};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{
     Block, BorderType, Borders, Clear, List, ListItem, Padding, Paragraph, Widget, Wrap,
 };
-use std::str::FromStr;
 
 pub struct AddListPopUp;
 pub struct ModifyListPopUp;
@@ -19,13 +21,11 @@ fn render_list_popup_kernel<T: CursorState>(
     buf: &mut Buffer,
     popup_title: &str,
 ) {
-    let fg = config.foreground();
     let hl = config.highlight();
-    let bg = config.background();
     // Command hints for add list popup
     let add_or_modify_list_command_hints = Line::from(vec![
         Span::raw(" "),
-        Span::styled("[Esc]", Style::default().fg(Color::from_str(hl).unwrap())),
+        Span::styled("[Esc]", Style::default().fg(hl)),
         Span::raw(" "),
     ]);
 
@@ -50,17 +50,17 @@ fn render_list_popup_kernel<T: CursorState>(
     // Clear the background of the popup area first
     Clear.render(popup_area, buf);
     Block::default()
-        .style(Style::default().bg(Color::from_str(bg).unwrap()))
+        .style(config.colours.popup_style())
         .render(popup_area, buf);
 
     // Define the popup block with styling
     let popup_block = Block::new()
         .padding(Padding::new(2, 2, 1, 1))
         .title(format!("  {}  ", popup_title))
-        .title_style(Style::new().fg(Color::from_str(fg).unwrap()))
+        .title_style(config.colours.title_style())
         .title_bottom(add_or_modify_list_command_hints)
         .borders(Borders::ALL)
-        .border_style(Style::new().fg(Color::from_str(fg).unwrap()))
+        .border_style(config.colours.border_style())
         .border_type(BorderType::Rounded)
         .padding(Padding::horizontal(1));
 
@@ -92,27 +92,26 @@ impl ModifyListPopUp {
 pub struct AddItemPopUp;
 pub struct ModifyItemPopUp;
 
-/// Render popup for entering a new item name
+/// Render popup for entering a new item name, with an optional line under the input
 pub fn render_item_popup_kernel<T: CursorState>(
     config: Config,
     state: &T,
     area: Rect,
     buf: &mut Buffer,
     popup_title: &str,
+    preview: Option<Line>,
 ) {
-    let fg = config.foreground();
     let hl = config.highlight();
-    let bg = config.background();
     // Command hints for add item popup
     let add_item_command_hints = Line::from(vec![
         Span::raw(" "),
-        Span::styled("[Esc]", Style::default().fg(Color::from_str(hl).unwrap())),
+        Span::styled("[Esc]", Style::default().fg(hl)),
         Span::raw(" "),
     ]);
 
     // Calculate popup dimensions
     let popup_width = (area.width * 3) / 4; // 75% of the area width
-    let popup_height = 4; // Fixed height for just the input field
+    let popup_height = if preview.is_some() { 5 } else { 4 }; // Input field and preview
 
     // Center horizontally within the area
     let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
@@ -131,42 +130,62 @@ pub fn render_item_popup_kernel<T: CursorState>(
     // Clear the background of the popup area first
     Clear.render(popup_area, buf);
     Block::default()
-        .style(Style::default().bg(Color::from_str(bg).unwrap()))
+        .style(config.colours.popup_style())
         .render(popup_area, buf);
 
     // Define the popup block with styling
     let popup_block = Block::new()
         .padding(Padding::new(2, 2, 1, 1))
         .title(format!("  {}  ", popup_title))
-        .title_style(Style::new().fg(Color::from_str(fg).unwrap()))
+        .title_style(config.colours.title_style())
         .title_bottom(add_item_command_hints)
         .borders(Borders::ALL)
-        .border_style(Style::new().fg(Color::from_str(fg).unwrap()))
+        .border_style(config.colours.border_style())
         .border_type(BorderType::Rounded)
         .padding(Padding::horizontal(1));
 
     // Define the text to render
     let text_spans = state.create_cursor_text_spans(config);
-    let text_line = Line::from(text_spans);
+    let mut text = Text::from(Line::from(text_spans));
+    if let Some(preview) = preview {
+        text.push_line(preview);
+    }
 
     // Render the input field
-    Paragraph::new(text_line)
+    Paragraph::new(text)
         .wrap(Wrap { trim: true })
         .block(popup_block)
         .render(popup_area, buf);
 }
 
 impl AddItemPopUp {
-    /// Render popup for entering a new item
+    /// Render popup for entering a new item, previewing the quick-add fields as they are typed
     pub fn render<T: CursorState>(config: Config, state: &T, area: Rect, buf: &mut Buffer) {
-        render_item_popup_kernel(config, state, area, buf, "Add Item");
+        let fg = config.foreground();
+        let today = chrono::Local::now().date_naive();
+        let quick_add = QuickAdd::parse(state.get_text());
+        let preview = if state.get_text().trim().is_empty() {
+            // Hint at the syntax until something is typed
+            Line::styled("!high @tomorrow #tag ^list", config.colours.hint_style())
+        } else if quick_add.name.is_empty() {
+            Line::styled("missing item name", config.colours.status_error_style())
+        } else {
+            let fields = quick_add.describe(today);
+            let summary = if fields.is_empty() {
+                format!("→ {}", quick_add.name)
+            } else {
+                format!("→ {} · {}", quick_add.name, fields)
+            };
+            Line::styled(summary, Style::default().fg(fg))
+        };
+        render_item_popup_kernel(config, state, area, buf, "Add Item", Some(preview));
     }
 }
 
 impl ModifyItemPopUp {
     /// Render popup for modifying item name
     pub fn render<T: CursorState>(config: Config, state: &T, area: Rect, buf: &mut Buffer) {
-        render_item_popup_kernel(config, state, area, buf, "Modify Item");
+        render_item_popup_kernel(config, state, area, buf, "Modify Item", None);
     }
 }
 
@@ -177,38 +196,30 @@ impl ChangeDBPopUp {
     pub fn render(config: &Config, selected_index: usize, area: Rect, buf: &mut Buffer) {
         let fg = config.foreground();
         let hl = config.highlight();
-        let bg = config.background();
         // Command hints for change db popup
         let change_db_command_hints = Line::from(vec![
             Span::raw(" "),
             Span::styled(" ↑↓ ", Style::default()),
-            Span::styled("[A]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled("dd", Style::default().fg(Color::from_str(fg).unwrap())),
-            Span::styled(" [S]", Style::default().fg(Color::from_str(hl).unwrap())),
-            Span::styled(
-                "et Default",
-                Style::default().fg(Color::from_str(fg).unwrap()),
-            ),
-            Span::styled(" [Esc]", Style::default().fg(Color::from_str(hl).unwrap())),
+            Span::styled("[A]", Style::default().fg(hl)),
+            Span::styled("dd", Style::default().fg(fg)),
+            Span::styled(" [S]", Style::default().fg(hl)),
+            Span::styled("et Default", Style::default().fg(fg)),
+            Span::styled(" [Esc]", Style::default().fg(hl)),
             Span::raw(" "),
         ]);
 
         Block::default()
-            .style(
-                Style::default()
-                    .bg(Color::from_str(bg).unwrap())
-                    .fg(Color::from_str(fg).unwrap()),
-            )
+            .style(config.colours.popup_style())
             .render(area, buf);
 
         // Define the popup block with styling
         let popup_block = Block::new()
             .padding(Padding::new(2, 2, 1, 1))
             .title(" Select Database ")
-            .title_style(Style::new().fg(Color::from_str(fg).unwrap()))
+            .title_style(config.colours.title_style())
             .title_bottom(change_db_command_hints)
             .borders(Borders::ALL)
-            .border_style(Style::new().fg(Color::from_str(fg).unwrap()))
+            .border_style(config.colours.border_style())
             .border_type(BorderType::Rounded);
 
         // Create list items from databases
@@ -226,12 +237,7 @@ impl ChangeDBPopUp {
         let list = List::new(items)
             .block(popup_block)
             .highlight_symbol(" ▸ ") // Selection indicator
-            .highlight_style(
-                // Swap foreground and background for selected item
-                Style::default()
-                    .bg(Color::from_str(fg).unwrap())
-                    .fg(Color::from_str(bg).unwrap()),
-            )
+            .highlight_style(config.colours.selected_style())
             .highlight_spacing(ratatui::widgets::HighlightSpacing::Always);
 
         ratatui::widgets::StatefulWidget::render(list, area, buf, &mut temp_list_state);
@@ -239,49 +245,246 @@ impl ChangeDBPopUp {
 }
 
 pub struct AddDBPopUp;
+pub struct RegisterDBPopUp;
+
+/// Render popup for entering a database name or path
+fn render_db_popup_kernel<T: CursorState>(
+    config: Config,
+    state: &T,
+    area: Rect,
+    buf: &mut Buffer,
+    popup_title: &str,
+    tab_hint: &str,
+) {
+    let fg = config.foreground();
+    let hl = config.highlight();
+    // Command hints for add/register db popup
+    let db_command_hints = Line::from(vec![
+        Span::raw(" "),
+        Span::styled("[Tab]", Style::default().fg(hl)),
+        Span::styled(format!(" {}", tab_hint), Style::default().fg(fg)),
+        Span::styled(" [Esc]", Style::default().fg(hl)),
+        Span::raw(" "),
+    ]);
+
+    // Clear the entire area background first
+    Clear.render(area, buf);
+    Block::default()
+        .style(config.colours.popup_style())
+        .render(area, buf);
+
+    // Define the popup block with styling - use full width
+    let popup_block = Block::new()
+        .padding(Padding::new(2, 2, 1, 1))
+        .title(format!(" {} ", popup_title))
+        .title_style(config.colours.title_style())
+        .title_bottom(db_command_hints)
+        .borders(Borders::ALL)
+        .border_style(config.colours.border_style())
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1));
+
+    // Define the text to render
+    let text_spans = state.create_cursor_text_spans(config);
+    let text_line = Line::from(text_spans);
+
+    // Render the input field using the full area
+    Paragraph::new(text_line)
+        .wrap(Wrap { trim: true })
+        .block(popup_block)
+        .render(area, buf);
+}
 
 impl AddDBPopUp {
     /// Render popup for entering a new database name
     pub fn render<T: CursorState>(config: Config, state: &T, area: Rect, buf: &mut Buffer) {
+        render_db_popup_kernel(config, state, area, buf, "Add Database", "Path");
+    }
+}
+
+impl RegisterDBPopUp {
+    /// Render popup for entering the path of an existing database file
+    pub fn render<T: CursorState>(config: Config, state: &T, area: Rect, buf: &mut Buffer) {
+        render_db_popup_kernel(config, state, area, buf, "Register Database", "Name");
+    }
+}
+
+/// Render popup for entering a single line, with feedback on the input next to the hints
+fn render_feedback_popup_kernel<T: CursorState>(
+    config: Config,
+    state: &T,
+    area: Rect,
+    buf: &mut Buffer,
+    popup_title: &str,
+    feedback: Span,
+) {
+    let hl = config.highlight();
+    let command_hints = Line::from(vec![
+        Span::raw(" "),
+        feedback,
+        Span::styled("[Esc]", Style::default().fg(hl)),
+        Span::raw(" "),
+    ]);
+
+    let popup_width = (area.width * 3) / 4; // 75% of the area width
+    let popup_height = 4; // Fixed height for just the input field
+    let popup_area = Rect {
+        x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    // Clear the background of the popup area first
+    Clear.render(popup_area, buf);
+    Block::default()
+        .style(config.colours.popup_style())
+        .render(popup_area, buf);
+
+    let popup_block = Block::new()
+        .title(format!("  {}  ", popup_title))
+        .title_style(config.colours.title_style())
+        .title_bottom(command_hints)
+        .borders(Borders::ALL)
+        .border_style(config.colours.border_style())
+        .border_type(BorderType::Rounded)
+        .padding(Padding::horizontal(1));
+
+    let text_line = Line::from(state.create_cursor_text_spans(config));
+    Paragraph::new(text_line)
+        .wrap(Wrap { trim: true })
+        .block(popup_block)
+        .render(popup_area, buf);
+}
+
+pub struct FilterPopUp;
+
+impl FilterPopUp {
+    /// Render popup for entering a query, telling whether it is valid as it is typed
+    pub fn render<T: CursorState>(config: Config, state: &T, area: Rect, buf: &mut Buffer) {
+        let fg = config.foreground();
+
+        // Parse as the user types, so mistakes show before Enter
+        let feedback = match state.get_text().parse::<Query>() {
+            Ok(query) if query.is_empty() => {
+                Span::styled(" Enter to show all items ", Style::default().fg(fg))
+            }
+            Ok(_) => Span::styled(" Enter to apply ", Style::default().fg(fg)),
+            Err(e) => Span::styled(format!(" {} ", e), config.colours.status_error_style()),
+        };
+        render_feedback_popup_kernel(
+            config,
+            state,
+            area,
+            buf,
+            "Filter (e.g. priority:high due<7d !done #tag \"text\")",
+            feedback,
+        );
+    }
+}
+
+pub struct DueDatePopUp;
+
+impl DueDatePopUp {
+    /// Render popup for entering a due date, showing the day it stands for as it is typed
+    pub fn render<T: CursorState>(config: Config, state: &T, area: Rect, buf: &mut Buffer) {
+        let fg = config.foreground();
+
+        // Resolve the date as the user types, so mistakes show before Enter
+        let input = state.get_text().trim();
+        let today = chrono::Local::now().date_naive();
+        let feedback = if input.is_empty() {
+            Span::styled(" Enter to remove the due date ", Style::default().fg(fg))
+        } else {
+            match parse_date(input, today) {
+                Ok(date) => Span::styled(
+                    format!(" {} ", date.format("%a %Y-%m-%d")),
+                    Style::default().fg(fg),
+                ),
+                Err(e) => Span::styled(format!(" {} ", e), config.colours.status_error_style()),
+            }
+        };
+        render_feedback_popup_kernel(
+            config,
+            state,
+            area,
+            buf,
+            "Due Date (e.g. tomorrow, next fri, in 3 days, eow, oct 20)",
+            feedback,
+        );
+    }
+}
+
+pub struct ItemKeysPopUp;
+
+/// Keys of the items panel, with what they do
+const ITEM_KEYS: &[(&str, &str)] = &[
+    ("↓ ↑", "Move down/up in items"),
+    ("→ ←", "Select the first item / deselect"),
+    ("t b", "Select the top/bottom item"),
+    ("a", "Add item"),
+    ("m", "Modify item"),
+    ("d", "Delete item"),
+    ("Enter", "Toggle done"),
+    ("c", "Copy items to the clipboard"),
+    ("Alt + ↓↑", "Move item down/up"),
+    ("h", "Hide/show completed items"),
+    ("X", "Clear completed items"),
+    ("o", "Cycle the sort mode"),
+    ("g", "View the agenda of due items"),
+    ("k", "View the list as a board"),
+    ("/", "Filter items with a query"),
+    ("@", "Set the due date"),
+    ("Alt + t b", "Move item to the top/bottom"),
+    ("Space", "Grab item to move it with ↓↑, then Enter drops it"),
+];
+
+impl ItemKeysPopUp {
+    /// Render popup listing the keys of the items panel, more than its hints can hold
+    pub fn render(config: Config, area: Rect, buf: &mut Buffer) {
         let fg = config.foreground();
         let hl = config.highlight();
-        let bg = config.background();
-        // Command hints for add db popup
-        let add_db_command_hints = Line::from(vec![
+        let command_hints = Line::from(vec![
             Span::raw(" "),
-            Span::styled("[Esc]", Style::default().fg(Color::from_str(hl).unwrap())),
+            Span::styled("[Esc]", Style::default().fg(hl)),
             Span::raw(" "),
         ]);
 
-        // Clear the entire area background first
-        Clear.render(area, buf);
+        let popup_width = (area.width * 3) / 4; // 75% of the area width
+        let popup_height = (ITEM_KEYS.len() as u16 + 4).min(area.height); // Keys within borders and padding
+        let popup_area = Rect {
+            x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+            y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+            width: popup_width,
+            height: popup_height,
+        };
+
+        // Clear the background of the popup area first
+        Clear.render(popup_area, buf);
         Block::default()
-            .style(
-                Style::default()
-                    .bg(Color::from_str(bg).unwrap())
-                    .fg(Color::from_str(fg).unwrap()),
-            )
-            .render(area, buf);
+            .style(config.colours.popup_style())
+            .render(popup_area, buf);
 
-        // Define the popup block with styling - use full width
         let popup_block = Block::new()
             .padding(Padding::new(2, 2, 1, 1))
-            .title(" Add Database ")
-            .title_style(Style::new().fg(Color::from_str(fg).unwrap()))
-            .title_bottom(add_db_command_hints)
+            .title("  Item Keys  ")
+            .title_style(config.colours.title_style())
+            .title_bottom(command_hints)
             .borders(Borders::ALL)
-            .border_style(Style::new().fg(Color::from_str(fg).unwrap()))
-            .border_type(BorderType::Rounded)
-            .padding(Padding::horizontal(1));
-
-        // Define the text to render
-        let text_spans = state.create_cursor_text_spans(config);
-        let text_line = Line::from(text_spans);
+            .border_style(config.colours.border_style())
+            .border_type(BorderType::Rounded);
 
-        // Render the input field using the full area
-        Paragraph::new(text_line)
-            .wrap(Wrap { trim: true })
+        let lines: Vec<Line> = ITEM_KEYS
+            .iter()
+            .map(|(key, action)| {
+                Line::from(vec![
+                    Span::styled(format!("{:<10}", key), Style::default().fg(hl)),
+                    Span::styled(*action, Style::default().fg(fg)),
+                ])
+            })
+            .collect();
+        Paragraph::new(Text::from(lines))
             .block(popup_block)
-            .render(area, buf);
+            .render(popup_area, buf);
     }
 }
@@ -0,0 +1,56 @@
+use crate::db::config::Config;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+use std::time::{Duration, Instant};
+
+/// How long a status message stays on screen
+const STATUS_DURATION: Duration = Duration::from_secs(4);
+
+/// Short-lived message shown at the bottom of the TUI
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusMessage {
+    pub text: String,
+    pub is_error: bool,
+    pub shown_until: Instant,
+}
+
+impl StatusMessage {
+    pub fn info(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            is_error: false,
+            shown_until: Instant::now() + STATUS_DURATION,
+        }
+    }
+
+    pub fn error(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            is_error: true,
+            shown_until: Instant::now() + STATUS_DURATION,
+        }
+    }
+
+    /// Whether the message has been shown long enough
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now >= self.shown_until
+    }
+}
+
+pub struct StatusBar;
+
+impl StatusBar {
+    pub fn render(message: &StatusMessage, area: Rect, buf: &mut Buffer, config: Config) {
+        let style = if message.is_error {
+            config.colours.status_error_style()
+        } else {
+            config.colours.status_style()
+        };
+
+        Line::styled(format!(" {} ", message.text), style)
+            .right_aligned()
+            .render(area, buf);
+    }
+}
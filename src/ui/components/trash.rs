@@ -0,0 +1,225 @@
+use crate::db::config::Config;
+use crate::db::models::TrashEntry;
+use anyhow::Result;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Alignment, Rect};
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, BorderType, Borders, HighlightSpacing, List, ListItem, ListState, Padding,
+    StatefulWidget,
+};
+use sqlx::SqlitePool;
+
+/// Permanent deletion waiting for the user to confirm it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PendingPurge {
+    /// Purge the selected entry
+    Selected,
+    /// Empty the whole trash
+    All,
+}
+
+/// View of the deleted lists and items, from which they can be restored or purged
+pub struct TrashComponent {
+    pub entries: Vec<TrashEntry>,
+    pub list_state: ListState,
+    /// Purge asked for but not confirmed yet
+    pub pending_purge: Option<PendingPurge>,
+}
+
+impl Default for TrashComponent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TrashComponent {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            list_state: ListState::default(),
+            pending_purge: None,
+        }
+    }
+
+    /// Load the trash from database and select the first entry
+    pub async fn load_entries(&mut self, pool: &SqlitePool) -> Result<()> {
+        self.entries = TrashEntry::get_all(pool).await?;
+        self.pending_purge = None;
+        self.list_state.select(if self.entries.is_empty() {
+            None
+        } else {
+            Some(0)
+        });
+        Ok(())
+    }
+
+    /// Select next entry
+    pub fn select_next(&mut self) {
+        self.list_state.select_next();
+    }
+
+    /// Select previous entry
+    pub fn select_previous(&mut self) {
+        self.list_state.select_previous();
+    }
+
+    /// Restore the selected entry, returning it
+    pub async fn restore_selected_entry(
+        &mut self,
+        pool: &SqlitePool,
+    ) -> Result<Option<TrashEntry>> {
+        let Some(entry) = self.selected_entry() else {
+            return Ok(None);
+        };
+
+        entry.restore(pool).await?;
+        self.refresh_entries(pool).await?;
+        Ok(Some(entry))
+    }
+
+    /// Ask to purge the selected entry, which waits for confirmation
+    pub fn request_purge_selected(&mut self) {
+        if self.selected_entry().is_some() {
+            self.pending_purge = Some(PendingPurge::Selected);
+        }
+    }
+
+    /// Ask to empty the trash, which waits for confirmation
+    pub fn request_empty(&mut self) {
+        if !self.entries.is_empty() {
+            self.pending_purge = Some(PendingPurge::All);
+        }
+    }
+
+    /// Leave the trash as it is
+    pub fn cancel_purge(&mut self) {
+        self.pending_purge = None;
+    }
+
+    /// Carry out the purge that was asked for
+    pub async fn confirm_purge(&mut self, pool: &SqlitePool) -> Result<()> {
+        match self.pending_purge.take() {
+            Some(PendingPurge::Selected) => self.purge_selected_entry(pool).await,
+            Some(PendingPurge::All) => self.empty(pool).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Permanently delete the selected entry
+    pub async fn purge_selected_entry(&mut self, pool: &SqlitePool) -> Result<()> {
+        if let Some(entry) = self.selected_entry() {
+            entry.purge(pool).await?;
+            self.refresh_entries(pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Permanently delete all entries
+    pub async fn empty(&mut self, pool: &SqlitePool) -> Result<()> {
+        TrashEntry::empty(pool).await?;
+        self.refresh_entries(pool).await
+    }
+
+    fn selected_entry(&self) -> Option<TrashEntry> {
+        self.list_state
+            .selected()
+            .and_then(|i| self.entries.get(i))
+            .cloned()
+    }
+
+    /// Refresh the entries and keep the selection within bounds
+    async fn refresh_entries(&mut self, pool: &SqlitePool) -> Result<()> {
+        self.entries = TrashEntry::get_all(pool).await?;
+        if self.entries.is_empty() {
+            self.list_state.select(None);
+        } else if let Some(i) = self.list_state.selected()
+            && i >= self.entries.len()
+        {
+            self.list_state.select(Some(self.entries.len() - 1));
+        }
+        Ok(())
+    }
+
+    /// Render the list of deleted lists and items
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer, config: Config) {
+        let fg = config.foreground();
+        let hl = config.highlight();
+        // Command hints for the trash, or the question before purging
+        let trash_command_hints = match self.pending_purge {
+            Some(pending_purge) => {
+                let question = match pending_purge {
+                    PendingPurge::Selected => format!(
+                        " Purge '{}' for good?",
+                        self.selected_entry()
+                            .map(|entry| entry.name().to_string())
+                            .unwrap_or_default()
+                    ),
+                    PendingPurge::All => {
+                        format!(" Purge all {} entries for good?", self.entries.len())
+                    }
+                };
+                Line::from(vec![
+                    Span::raw(" "),
+                    Span::styled(question, config.colours.status_error_style()),
+                    Span::styled(" [y]", Style::default().fg(hl)),
+                    Span::styled("es", Style::default().fg(fg)),
+                    Span::styled(" [n]", Style::default().fg(hl)),
+                    Span::styled("o ", Style::default().fg(fg)),
+                    Span::raw(" "),
+                ])
+                .left_aligned()
+            }
+            None => Line::from(vec![
+                Span::raw(" "),
+                Span::styled(" ↑↓ ", Style::default()),
+                Span::styled("[Enter]", Style::default().fg(hl)),
+                Span::styled(" Restore", Style::default().fg(fg)),
+                Span::styled(" [d]", Style::default().fg(hl)),
+                Span::styled(" Purge", Style::default().fg(fg)),
+                Span::styled(" [E]", Style::default().fg(hl)),
+                Span::styled(" Empty", Style::default().fg(fg)),
+                Span::styled(" [Esc] ", Style::default().fg(hl)),
+                Span::raw(" "),
+            ])
+            .left_aligned(),
+        };
+
+        let block = Block::default()
+            .padding(Padding::new(2, 2, 1, 1))
+            .title_top(Line::styled("  T R A S H  ", config.colours.title_style()).left_aligned())
+            .title_bottom(trash_command_hints)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
+            .border_type(BorderType::Rounded)
+            .border_style(config.colours.border_style());
+
+        // Show what each entry is and when it was deleted
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let deleted_at = entry
+                    .deleted_at()
+                    .map(|date| date.format(" (%Y-%m-%d)").to_string())
+                    .unwrap_or_default();
+                let label = match entry {
+                    TrashEntry::List(list) => format!("[list] {}", list.name),
+                    TrashEntry::Item { item, list_name } => {
+                        format!("[item] {} in {}", item.name, list_name)
+                    }
+                };
+                ListItem::from(format!("{}{}", label, deleted_at))
+            })
+            .collect();
+
+        let list: List = List::new(items)
+            .block(block)
+            .highlight_symbol(" ▸ ") // Selection indicator
+            .highlight_style(config.colours.selected_style())
+            .highlight_spacing(HighlightSpacing::Always);
+
+        StatefulWidget::render(list, area, buf, &mut self.list_state);
+    }
+}
@@ -1,7 +1,6 @@
 use crate::db::config::Config;
-use ratatui::style::{Color, Style};
+use ratatui::style::Style;
 use ratatui::text::Span;
-use std::str::FromStr;
 
 /// Trait for managing cursor-based text input
 pub trait CursorState {
@@ -124,29 +123,13 @@ pub trait CursorState {
         };
 
         vec![
-            Span::styled(
-                text_before,
-                Style::default().fg(Color::from_str(fg).unwrap()),
-            ),
+            Span::styled(text_before, Style::default().fg(fg)),
             if cursor_char == "█" {
-                Span::styled(
-                    cursor_char,
-                    Style::default()
-                        .fg(Color::from_str(fg).unwrap())
-                        .bg(Color::from_str(bg).unwrap()),
-                )
+                Span::styled(cursor_char, Style::default().fg(fg).bg(bg))
             } else {
-                Span::styled(
-                    cursor_char,
-                    Style::default()
-                        .fg(Color::from_str(bg).unwrap())
-                        .bg(Color::from_str(fg).unwrap()),
-                )
+                Span::styled(cursor_char, Style::default().fg(bg).bg(fg))
             },
-            Span::styled(
-                text_after,
-                Style::default().fg(Color::from_str(fg).unwrap()),
-            ),
+            Span::styled(text_after, Style::default().fg(fg)),
         ]
     }
 }
@@ -1,9 +1,8 @@
 use crate::db::config::Config;
 use ratatui::buffer::Buffer;
 use ratatui::layout::{Constraint, Layout, Margin, Rect};
-use ratatui::style::{Color, Style};
+use ratatui::style::Style;
 use ratatui::widgets::{Block, Widget};
-use std::str::FromStr;
 
 pub struct AppLayout;
 
@@ -65,14 +64,24 @@ impl AppLayout {
         )
     }
 
+    /// Bottom row of the screen, below the panels, for status messages
+    pub fn calculate_status_area(area: Rect) -> Rect {
+        let padded_area = area.inner(Margin {
+            horizontal: 2,
+            vertical: 0,
+        });
+        let [_, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(padded_area);
+        status_area
+    }
+
     /// Render a background that fills the entire area
     pub fn render_background(config: Config, area: Rect, buf: &mut Buffer) {
-        let fg = config.foreground();
-        let bg = config.background();
-        let background_color = Color::from_str(bg).unwrap();
-        let foreground_color = Color::from_str(fg).unwrap();
-        let background =
-            Block::default().style(Style::default().bg(background_color).fg(foreground_color));
+        let background = Block::default().style(
+            Style::default()
+                .bg(config.background())
+                .fg(config.foreground()),
+        );
         background.render(area, buf);
     }
 }
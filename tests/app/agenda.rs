@@ -0,0 +1,146 @@
+use crate::helpers::app::setup_test_app;
+use crate::helpers::db::setup_test_db_shared;
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use judo::app::App;
+use judo::app::state::CurrentScreen;
+use judo::db::config::DBConfig;
+use judo::db::models::{NewTodoItem, NewTodoList, TodoItem, TodoList};
+use sqlx::SqlitePool;
+
+#[tokio::test]
+async fn test_agenda_toggle_and_jump() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    for name in ["Home", "Work"] {
+        TodoList::create(
+            &app.pool,
+            NewTodoList {
+                name: name.to_string(),
+            },
+        )
+        .await?;
+    }
+    let lists = TodoList::get_all(&app.pool).await?;
+    for (list, name, days) in [(&lists[0], "Plants", 1), (&lists[1], "Taxes", 0)] {
+        TodoItem::create(
+            &app.pool,
+            NewTodoItem {
+                list_id: list.id,
+                name: name.to_string(),
+                priority: None,
+                due_date: Some(Utc::now() + Duration::days(days)),
+            },
+        )
+        .await?;
+    }
+    app.lists_component.load_lists(&app.pool).await?;
+
+    app.enter_agenda_screen().await?;
+    assert_eq!(app.current_screen, CurrentScreen::Agenda);
+    assert_eq!(app.agenda_component.rows.len(), 2);
+    assert_eq!(
+        app.agenda_component.selected_row().unwrap().entry.item.name,
+        "Taxes"
+    );
+
+    // Completing an item takes it off the agenda
+    app.toggle_selected_agenda_item_done().await?;
+    assert_eq!(app.agenda_component.rows.len(), 1);
+
+    // Jumping selects the list and item the entry comes from
+    app.jump_to_selected_agenda_item().await?;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+    let selected = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(selected.list.name, "Home");
+    let j = selected.item_state.selected().unwrap();
+    assert_eq!(selected.items[j].item.name, "Plants");
+
+    // The main screen shows the item completed from the agenda
+    let work = &app.lists_component.lists[1];
+    assert!(work.items[0].item.is_done);
+
+    Ok(())
+}
+
+/// Add another database to the config of the app, holding a list with one due item
+///
+/// The returned pool keeps the in-memory database alive.
+async fn add_db_with_due_item(
+    app: &mut App,
+    name: &str,
+    item_name: &str,
+) -> Result<(DBConfig, SqlitePool)> {
+    let (pool, connection_str) = setup_test_db_shared().await?;
+    let list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Errands".to_string(),
+        },
+    )
+    .await?;
+    TodoItem::create(
+        &pool,
+        NewTodoItem {
+            list_id: list.id,
+            name: item_name.to_string(),
+            priority: None,
+            due_date: Some(Utc::now() + Duration::days(1)),
+        },
+    )
+    .await?;
+
+    let db = DBConfig {
+        name: name.to_string(),
+        connection_str,
+        sqlite: Default::default(),
+    };
+    app.config.dbs.push(db.clone());
+    Ok((db, pool))
+}
+
+#[tokio::test]
+async fn test_agenda_of_all_dbs_reuses_their_pools() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    let (_, _pool) = add_db_with_due_item(&mut app, "other", "Post office").await?;
+    assert_eq!(app.pools.len(), 1);
+
+    app.enter_agenda_screen().await?;
+    app.toggle_agenda_all_dbs().await?;
+    assert_eq!(app.agenda_component.rows.len(), 1);
+    assert_eq!(app.agenda_component.rows[0].db.name, "other");
+    assert_eq!(app.pools.len(), 2);
+
+    // Toggling and reloading go through the same pool rather than new ones
+    app.toggle_selected_agenda_item_done().await?;
+    assert!(app.agenda_component.rows.is_empty());
+    app.toggle_agenda_all_dbs().await?;
+    app.toggle_agenda_all_dbs().await?;
+    assert_eq!(app.pools.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_agenda_of_all_dbs_skips_unreadable_ones() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    let (_, _pool) = add_db_with_due_item(&mut app, "other", "Post office").await?;
+    let missing_dir = setup_test_dir()?.join("missing");
+    app.config.dbs.push(DBConfig {
+        name: "broken".to_string(),
+        connection_str: format!("sqlite:{}", missing_dir.join("broken.db").display()),
+        sqlite: Default::default(),
+    });
+
+    // The others are still shown, with a note on the one left out
+    app.enter_agenda_screen().await?;
+    app.toggle_agenda_all_dbs().await?;
+    assert_eq!(app.agenda_component.rows.len(), 1);
+    assert_eq!(app.agenda_component.skipped_dbs, vec!["broken"]);
+    let status = app.status.as_ref().unwrap();
+    assert!(status.is_error);
+    assert!(status.text.contains("broken"));
+
+    Ok(())
+}
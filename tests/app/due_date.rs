@@ -0,0 +1,130 @@
+use crate::helpers::app::setup_test_app;
+use anyhow::Result;
+use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent};
+use judo::app::EventHandler;
+use judo::app::state::CurrentScreen;
+use judo::db::dates::start_of_day;
+use judo::db::models::{NewTodoItem, NewTodoList, TodoItem, TodoList};
+use judo::ui::cursor::CursorState;
+
+#[tokio::test]
+async fn test_set_due_date_of_selected_item() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    let list = TodoList::create(
+        &app.pool,
+        NewTodoList {
+            name: "Chores".to_string(),
+        },
+    )
+    .await?;
+    TodoItem::create(
+        &app.pool,
+        NewTodoItem {
+            list_id: list.id,
+            name: "Pay rent".to_string(),
+            priority: None,
+            due_date: None,
+        },
+    )
+    .await?;
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.select_first();
+
+    // Nothing to date without a selected item
+    app.enter_due_date_screen();
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+
+    app.lists_component
+        .get_selected_list_mut()
+        .unwrap()
+        .item_state
+        .select(Some(0));
+
+    // Invalid dates keep the pop-up open
+    app.enter_due_date_screen();
+    assert_eq!(app.current_screen, CurrentScreen::DueDate);
+    assert_eq!(app.input_state.get_text(), "");
+    for c in "someday".chars() {
+        app.input_state.add_char(c);
+    }
+    assert!(app.apply_due_date().await.is_err());
+    assert_eq!(app.current_screen, CurrentScreen::DueDate);
+    app.exit_due_date_screen();
+
+    app.enter_due_date_screen();
+    for c in "oct 20 2030".chars() {
+        app.input_state.add_char(c);
+    }
+    app.apply_due_date().await?;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+    let item = &app.lists_component.get_selected_list().unwrap().items[0].item;
+    let expected = start_of_day(NaiveDate::from_ymd_opt(2030, 10, 20).unwrap());
+    assert_eq!(item.due_date, Some(expected));
+
+    // The pop-up starts from the current date, and an empty one removes it
+    app.enter_due_date_screen();
+    assert_eq!(app.input_state.get_text(), "2030-10-20");
+    for _ in 0.."2030-10-20".len() {
+        app.input_state.remove_char_before_cursor();
+    }
+    app.apply_due_date().await?;
+    let item = &app.lists_component.get_selected_list().unwrap().items[0].item;
+    assert_eq!(item.due_date, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_due_date_reports_database_errors() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    let list = TodoList::create(
+        &app.pool,
+        NewTodoList {
+            name: "Chores".to_string(),
+        },
+    )
+    .await?;
+    TodoItem::create(
+        &app.pool,
+        NewTodoItem {
+            list_id: list.id,
+            name: "Pay rent".to_string(),
+            priority: None,
+            due_date: None,
+        },
+    )
+    .await?;
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.select_first();
+    app.lists_component
+        .get_selected_list_mut()
+        .unwrap()
+        .item_state
+        .select(Some(0));
+
+    // Invalid dates keep the pop-up open without a status message
+    app.enter_due_date_screen();
+    for c in "someday".chars() {
+        app.input_state.add_char(c);
+    }
+    EventHandler::handle_due_date_screen_key(&mut app, KeyEvent::from(KeyCode::Enter)).await;
+    assert_eq!(app.current_screen, CurrentScreen::DueDate);
+    assert!(app.status.is_none());
+
+    // A valid date that cannot be saved closes it and says why
+    app.input_state.clear();
+    for c in "tomorrow".chars() {
+        app.input_state.add_char(c);
+    }
+    app.pool.close().await;
+    EventHandler::handle_due_date_screen_key(&mut app, KeyEvent::from(KeyCode::Enter)).await;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+    let status = app.status.expect("the failure is reported");
+    assert!(status.is_error);
+    assert!(status.text.starts_with("Failed to set due date"));
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::helpers::app::setup_test_app;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use judo::app::EventHandler;
+use judo::app::state::CurrentScreen;
+use judo::db::models::{NewTodoItem, NewTodoList, Priority, TodoItem, TodoList};
+use judo::ui::cursor::CursorState;
+
+#[tokio::test]
+async fn test_filter_items_with_query() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    let list = TodoList::create(
+        &app.pool,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+    for (name, priority) in [("Urgent fix", Some(Priority::High)), ("Tidy up", None)] {
+        TodoItem::create(
+            &app.pool,
+            NewTodoItem {
+                list_id: list.id,
+                name: name.to_string(),
+                priority,
+                due_date: None,
+            },
+        )
+        .await?;
+    }
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.select_first();
+
+    // Invalid queries keep the pop-up open
+    app.enter_filter_screen();
+    assert_eq!(app.current_screen, CurrentScreen::Filter);
+    for c in "priority:urgent".chars() {
+        app.input_state.add_char(c);
+    }
+    assert!(app.apply_filter().await.is_err());
+    assert_eq!(app.current_screen, CurrentScreen::Filter);
+
+    app.exit_filter_screen();
+    app.enter_filter_screen();
+    for c in "priority:high".chars() {
+        app.input_state.add_char(c);
+    }
+    app.apply_filter().await?;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+    let items = &app.lists_component.get_selected_list().unwrap().items;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].item.name, "Urgent fix");
+
+    // The filter survives reloading the lists and is offered for editing
+    app.lists_component.load_lists(&app.pool).await?;
+    assert_eq!(app.lists_component.lists[0].items.len(), 1);
+    app.enter_filter_screen();
+    assert_eq!(app.input_state.get_text(), "priority:high");
+
+    // An empty query shows every item again
+    app.input_state.clear();
+    app.apply_filter().await?;
+    assert!(app.lists_component.filter.is_none());
+    assert_eq!(app.lists_component.lists[0].items.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_filter_reports_database_errors() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    app.lists_component.load_lists(&app.pool).await?;
+
+    // Invalid queries keep the pop-up open without a status message
+    app.enter_filter_screen();
+    for c in "priority:urgent".chars() {
+        app.input_state.add_char(c);
+    }
+    EventHandler::handle_filter_screen_key(&mut app, KeyEvent::from(KeyCode::Enter)).await;
+    assert_eq!(app.current_screen, CurrentScreen::Filter);
+    assert!(app.status.is_none());
+
+    // A valid query that cannot be run closes it and says why
+    app.input_state.clear();
+    for c in "priority:high".chars() {
+        app.input_state.add_char(c);
+    }
+    app.pool.close().await;
+    EventHandler::handle_filter_screen_key(&mut app, KeyEvent::from(KeyCode::Enter)).await;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+    let status = app.status.expect("the failure is reported");
+    assert!(status.is_error);
+    assert!(status.text.starts_with("Failed to filter items"));
+
+    Ok(())
+}
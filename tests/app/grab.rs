@@ -0,0 +1,205 @@
+use crate::helpers::app::setup_test_app;
+use anyhow::Result;
+use judo::app::state::{App, CurrentScreen};
+use judo::db::models::{NewTodoItem, NewTodoList, SortMode, TodoItem, TodoList};
+use judo::ui::components::ItemsComponent;
+
+async fn setup_list(app: &mut App, names: &[&str]) -> Result<TodoList> {
+    let list = TodoList::create(
+        &app.pool,
+        NewTodoList {
+            name: "Chores".to_string(),
+        },
+    )
+    .await?;
+    for name in names {
+        TodoItem::create(
+            &app.pool,
+            NewTodoItem {
+                list_id: list.id,
+                name: name.to_string(),
+                priority: None,
+                due_date: None,
+            },
+        )
+        .await?;
+    }
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.select_first();
+    Ok(list)
+}
+
+fn shown_names(app: &App) -> Vec<String> {
+    app.lists_component
+        .get_selected_list()
+        .unwrap()
+        .items
+        .iter()
+        .map(|ui_item| ui_item.item.name.clone())
+        .collect()
+}
+
+fn select_item(app: &mut App, j: usize) {
+    app.lists_component
+        .get_selected_list_mut()
+        .unwrap()
+        .item_state
+        .select(Some(j));
+}
+
+#[tokio::test]
+async fn test_grabbed_item_follows_the_cursor_until_dropped() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    let list = setup_list(&mut app, &["A", "B", "C", "D"]).await?;
+
+    // Nothing to grab without a selected item
+    app.enter_grab_screen().await?;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+
+    select_item(&mut app, 0);
+    app.enter_grab_screen().await?;
+    assert_eq!(app.current_screen, CurrentScreen::Grab);
+
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::carry_grabbed_item_down(ui_list);
+    ItemsComponent::carry_grabbed_item_down(ui_list);
+    assert_eq!(ui_list.item_state.selected(), Some(2));
+    assert_eq!(shown_names(&app), vec!["B", "C", "A", "D"]);
+
+    // Nothing is saved until the item is dropped
+    let stored: Vec<String> = TodoItem::get_by_list_id(&app.pool, list.id)
+        .await?
+        .into_iter()
+        .map(|i| i.name)
+        .collect();
+    assert_eq!(stored, vec!["A", "B", "C", "D"]);
+
+    app.drop_grabbed_item().await?;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+    let stored: Vec<String> = TodoItem::get_by_list_id(&app.pool, list.id)
+        .await?
+        .into_iter()
+        .map(|i| i.name)
+        .collect();
+    assert_eq!(stored, vec!["B", "C", "A", "D"]);
+    let ui_list = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(ui_list.item_state.selected(), Some(2));
+
+    // Cancelling puts the item back
+    app.enter_grab_screen().await?;
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::carry_grabbed_item_to_top(ui_list);
+    assert_eq!(shown_names(&app), vec!["A", "B", "C", "D"]);
+    app.cancel_grab().await?;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+    assert_eq!(shown_names(&app), vec!["B", "C", "A", "D"]);
+    let ui_list = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(ui_list.item_state.selected(), Some(2));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drop_counts_hidden_items() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    let list = setup_list(&mut app, &["A", "B", "C", "D"]).await?;
+
+    // B is done and hidden, so D dropped below A lands between A and B
+    let mut b = TodoItem::get_by_list_id(&app.pool, list.id)
+        .await?
+        .remove(1);
+    b.toggle_done(&app.pool).await?;
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::toggle_hide_done(ui_list, &app.pool).await?;
+    assert_eq!(shown_names(&app), vec!["A", "C", "D"]);
+
+    select_item(&mut app, 2);
+    app.enter_grab_screen().await?;
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::carry_grabbed_item_up(ui_list);
+    app.drop_grabbed_item().await?;
+
+    let stored: Vec<String> = TodoItem::get_by_list_id(&app.pool, list.id)
+        .await?
+        .into_iter()
+        .map(|i| i.name)
+        .collect();
+    assert_eq!(stored, vec!["A", "D", "B", "C"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_selected_item_to_top_and_bottom() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    setup_list(&mut app, &["Charlie", "Alpha", "Bravo"]).await?;
+
+    // A sorted list keeps what is shown as its manual order
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ui_list
+        .list
+        .set_sort_mode(&app.pool, SortMode::Alphabetical)
+        .await?;
+    ui_list.update_items(&app.pool).await?;
+    assert_eq!(shown_names(&app), vec!["Alpha", "Bravo", "Charlie"]);
+
+    select_item(&mut app, 2);
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::move_selected_item_to_top(ui_list, &app.pool).await?;
+    assert_eq!(ui_list.list.sort_mode, SortMode::Manual);
+    assert_eq!(ui_list.item_state.selected(), Some(0));
+    assert_eq!(shown_names(&app), vec!["Charlie", "Alpha", "Bravo"]);
+
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::move_selected_item_to_bottom(ui_list, &app.pool).await?;
+    assert_eq!(ui_list.item_state.selected(), Some(2));
+    assert_eq!(shown_names(&app), vec!["Alpha", "Bravo", "Charlie"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_selected_item_skips_hidden_items() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    let list = setup_list(&mut app, &["A", "B", "C", "D"]).await?;
+
+    // B is done and hidden, so moving C up swaps it with A on screen
+    let mut b = TodoItem::get_by_list_id(&app.pool, list.id)
+        .await?
+        .remove(1);
+    b.toggle_done(&app.pool).await?;
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::toggle_hide_done(ui_list, &app.pool).await?;
+    assert_eq!(shown_names(&app), vec!["A", "C", "D"]);
+
+    select_item(&mut app, 1);
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::move_selected_item_up(ui_list, &app.pool).await?;
+    assert_eq!(shown_names(&app), vec!["C", "A", "D"]);
+    let ui_list = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(ui_list.item_state.selected(), Some(0));
+
+    // And back down past A, staying selected
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::move_selected_item_down(ui_list, &app.pool).await?;
+    assert_eq!(shown_names(&app), vec!["A", "C", "D"]);
+    let ui_list = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(ui_list.item_state.selected(), Some(1));
+
+    // Nothing above the first item or below the last one
+    select_item(&mut app, 2);
+    let ui_list = app.lists_component.get_selected_list_mut().unwrap();
+    ItemsComponent::move_selected_item_down(ui_list, &app.pool).await?;
+    assert_eq!(shown_names(&app), vec!["A", "C", "D"]);
+    let ui_list = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(ui_list.item_state.selected(), Some(2));
+
+    let stored: Vec<String> = TodoItem::get_by_list_id(&app.pool, list.id)
+        .await?
+        .into_iter()
+        .map(|i| i.name)
+        .collect();
+    assert_eq!(stored, vec!["A", "C", "B", "D"]);
+
+    Ok(())
+}
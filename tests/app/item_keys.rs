@@ -0,0 +1,56 @@
+use crate::helpers::app::setup_test_app;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use judo::app::{CurrentScreen, EventHandler};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+
+fn screen_text(app: &mut judo::app::App) -> String {
+    let area = Rect::new(0, 0, 120, 40);
+    let mut buf = Buffer::empty(area);
+    app.render(area, &mut buf);
+    (0..area.height)
+        .map(|y| {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[tokio::test]
+async fn test_item_keys_pop_up() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    // The items panel hints at the pop-up, which lists the keys its hints can't hold
+    assert!(screen_text(&mut app).contains("[?] keys"));
+    EventHandler::handle_main_screen_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('?'), KeyModifiers::SHIFT),
+    )
+    .await;
+    assert_eq!(app.current_screen, CurrentScreen::ItemKeys);
+    let text = screen_text(&mut app);
+    assert!(text.contains("Item Keys"));
+    assert!(text.contains("Hide/show completed items"));
+    assert!(text.contains("Clear completed items"));
+    assert!(text.contains("Cycle the sort mode"));
+    assert!(text.contains("View the agenda of due items"));
+    assert!(text.contains("View the list as a board"));
+    assert!(text.contains("Filter items with a query"));
+    assert!(text.contains("Set the due date"));
+    assert!(text.contains("Move item to the top/bottom"));
+    assert!(text.contains("Grab item"));
+
+    // Other keys don't act on the items behind it
+    EventHandler::handle_item_keys_screen_key(&mut app, KeyEvent::from(KeyCode::Char('q'))).await;
+    assert!(!app.exit);
+    assert_eq!(app.current_screen, CurrentScreen::ItemKeys);
+
+    EventHandler::handle_item_keys_screen_key(&mut app, KeyEvent::from(KeyCode::Esc)).await;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+
+    Ok(())
+}
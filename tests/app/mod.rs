@@ -0,0 +1,11 @@
+pub mod agenda;
+pub mod due_date;
+pub mod filter;
+pub mod grab;
+pub mod item_keys;
+pub mod quick_add;
+pub mod refresh;
+pub mod session;
+pub mod startup;
+pub mod tasks;
+pub mod trash;
@@ -0,0 +1,90 @@
+use crate::helpers::app::setup_test_app;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use judo::app::{CurrentScreen, EventHandler};
+use judo::db::models::{NewTodoList, Priority, TodoList};
+use judo::ui::cursor::CursorState;
+
+async fn type_and_submit(app: &mut judo::app::App, text: &str) {
+    for c in text.chars() {
+        app.input_state.add_char(c);
+    }
+    EventHandler::handle_add_or_modify_item_screen_key(
+        app,
+        KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_add_item_with_quick_add_syntax() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    for name in ["Inbox", "Chores"] {
+        TodoList::create(
+            &app.pool,
+            NewTodoList {
+                name: name.to_string(),
+            },
+        )
+        .await?;
+    }
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.select_first();
+
+    // Items without a name keep the pop-up open with the input as typed
+    app.enter_add_item_screen();
+    type_and_submit(&mut app, "!high #home").await;
+    assert_eq!(app.current_screen, CurrentScreen::AddItem);
+    assert_eq!(app.input_state.get_text(), "!high #home");
+    app.exit_add_item_without_saving();
+
+    // So do unknown lists
+    app.enter_add_item_screen();
+    type_and_submit(&mut app, "Pay rent ^Nowhere").await;
+    assert_eq!(app.current_screen, CurrentScreen::AddItem);
+    app.exit_add_item_without_saving();
+
+    // ^list adds the item to that list and selects it
+    app.enter_add_item_screen();
+    type_and_submit(&mut app, "Pay rent !high #home ^Chores").await;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+
+    let selected = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(selected.list.name, "Chores");
+    assert_eq!(selected.items.len(), 1);
+    let item = &selected.items[0].item;
+    assert_eq!(item.name, "Pay rent");
+    assert_eq!(item.priority, Some(Priority::High));
+    assert_eq!(item.tags(), vec!["home"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failed_quick_add_keeps_the_selected_list() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    for name in ["Inbox", "Chores"] {
+        TodoList::create(
+            &app.pool,
+            NewTodoList {
+                name: name.to_string(),
+            },
+        )
+        .await?;
+    }
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.select_first();
+
+    // ^list only moves the selection once the item is in that list
+    app.pool.close().await;
+    app.enter_add_item_screen();
+    type_and_submit(&mut app, "Pay rent ^Chores").await;
+    assert_eq!(app.current_screen, CurrentScreen::AddItem);
+    assert!(app.status.as_ref().unwrap().is_error);
+    let selected = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(selected.list.name, "Inbox");
+
+    Ok(())
+}
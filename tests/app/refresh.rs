@@ -0,0 +1,184 @@
+use crate::helpers::app::setup_test_app;
+use crate::helpers::db::{create_item, setup_test_db};
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use judo::app::state::{App, CurrentScreen};
+use judo::db::config::DBConfig;
+use judo::db::connections::{ChangeWatcher, init_db};
+use judo::db::models::{NewTodoList, TodoItem, TodoList};
+use sqlx::SqlitePool;
+
+/// App on a database file, with a second pool on it standing in for the CLI
+async fn setup_watched_app(names: &[&str]) -> Result<(App, SqlitePool, TodoList)> {
+    let path = setup_test_dir()?.join("watched.db");
+    let connection_str = format!("sqlite:{}", path.display());
+    let pool = init_db(&connection_str).await?;
+
+    let mut app = setup_test_app().await?;
+    app.current_db_config = DBConfig {
+        name: "watched".to_string(),
+        connection_str: connection_str.clone(),
+        sqlite: Default::default(),
+    };
+    app.change_watcher = Some(ChangeWatcher::new(&pool).await?);
+    app.pool = pool;
+
+    let list = TodoList::create(
+        &app.pool,
+        NewTodoList {
+            name: "Chores".to_string(),
+        },
+    )
+    .await?;
+    for name in names {
+        create_item(&app.pool, list.id, name).await?;
+    }
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.select_first();
+    // Nothing left over from the setup
+    app.refresh_if_changed().await?;
+
+    let other = init_db(&connection_str).await?;
+    Ok((app, other, list))
+}
+
+fn selected_item_name(app: &App) -> Option<String> {
+    let ui_list = app.lists_component.get_selected_list()?;
+    let j = ui_list.item_state.selected()?;
+    Some(ui_list.items.get(j)?.item.name.clone())
+}
+
+#[tokio::test]
+async fn test_external_changes_reload_the_lists() -> Result<()> {
+    let (mut app, other, list) = setup_watched_app(&["Dishes", "Laundry", "Vacuum"]).await?;
+    app.lists_component
+        .get_selected_list_mut()
+        .unwrap()
+        .item_state
+        .select(Some(1));
+
+    // Nothing happened yet
+    assert!(!app.refresh_if_changed().await?);
+
+    // Another process adds a list before this one and an item, and moves the selected item
+    let mut first = TodoList::create(
+        &other,
+        NewTodoList {
+            name: "Errands".to_string(),
+        },
+    )
+    .await?;
+    first.move_to_position(&other, 0).await?;
+    create_item(&other, list.id, "Windows").await?;
+    let mut laundry = TodoItem::get_by_list_id(&other, list.id).await?.remove(1);
+    laundry.move_to_position(&other, 0).await?;
+
+    assert!(app.refresh_if_changed().await?);
+
+    // The new list and item show, and the selection followed the list and item
+    assert_eq!(app.lists_component.lists.len(), 2);
+    let ui_list = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(ui_list.list.id, list.id);
+    assert_eq!(ui_list.items.len(), 4);
+    assert_eq!(selected_item_name(&app).as_deref(), Some("Laundry"));
+
+    // Only once
+    assert!(!app.refresh_if_changed().await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_selection_stays_in_place_when_the_item_is_gone() -> Result<()> {
+    let (mut app, other, list) = setup_watched_app(&["Dishes", "Laundry", "Vacuum"]).await?;
+    app.lists_component
+        .get_selected_list_mut()
+        .unwrap()
+        .item_state
+        .select(Some(2));
+
+    let vacuum = TodoItem::get_by_list_id(&other, list.id).await?.remove(2);
+    vacuum.delete(&other).await?;
+
+    assert!(app.refresh_if_changed().await?);
+    assert_eq!(selected_item_name(&app).as_deref(), Some("Laundry"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_no_refresh_outside_the_main_screen() -> Result<()> {
+    let (mut app, other, list) = setup_watched_app(&["Dishes"]).await?;
+    app.current_screen = CurrentScreen::AddItem;
+
+    create_item(&other, list.id, "Laundry").await?;
+
+    assert!(!app.refresh_if_changed().await?);
+    assert_eq!(
+        app.lists_component.get_selected_list().unwrap().items.len(),
+        1
+    );
+
+    // The change is picked up once back on the main screen
+    app.current_screen = CurrentScreen::Main;
+    assert!(app.refresh_if_changed().await?);
+    assert_eq!(
+        app.lists_component.get_selected_list().unwrap().items.len(),
+        2
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_own_writes_do_not_reload_the_lists() -> Result<()> {
+    let (mut app, _other, _list) = setup_watched_app(&["Dishes"]).await?;
+
+    app.handle_key_event(KeyEvent::from(KeyCode::Char('a')))
+        .await;
+    for c in "Laundry".chars() {
+        app.handle_key_event(KeyEvent::from(KeyCode::Char(c))).await;
+    }
+    app.handle_key_event(KeyEvent::from(KeyCode::Enter)).await;
+    assert_eq!(app.current_screen, CurrentScreen::Main);
+    assert_eq!(
+        app.lists_component.get_selected_list().unwrap().items.len(),
+        2
+    );
+
+    assert!(!app.refresh_if_changed().await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_external_changes_survive_own_writes() -> Result<()> {
+    let (mut app, other, list) = setup_watched_app(&["Dishes"]).await?;
+
+    // Changed elsewhere while an item is being typed in
+    app.handle_key_event(KeyEvent::from(KeyCode::Char('a')))
+        .await;
+    create_item(&other, list.id, "Vacuum").await?;
+    for c in "Laundry".chars() {
+        app.handle_key_event(KeyEvent::from(KeyCode::Char(c))).await;
+    }
+    app.handle_key_event(KeyEvent::from(KeyCode::Enter)).await;
+
+    assert!(app.refresh_if_changed().await?);
+    assert_eq!(
+        app.lists_component.get_selected_list().unwrap().items.len(),
+        3
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_changes_are_only_watched_through_a_single_connection() -> Result<()> {
+    // Writes through another connection of the same pool would look like someone else's
+    let pool = setup_test_db().await?;
+    assert!(ChangeWatcher::new(&pool).await.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use crate::helpers::app::setup_test_app;
+use crate::helpers::db::create_item;
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use judo::app::session::{DbSelection, SessionState};
+use judo::db::models::{NewTodoItem, NewTodoList, TodoItem, TodoList};
+
+#[test]
+fn test_session_state_missing_file() -> Result<()> {
+    let path = setup_test_dir()?.join("state.toml");
+
+    let state = SessionState::read(&path)?;
+
+    assert_eq!(state, SessionState::default());
+    Ok(())
+}
+
+#[test]
+fn test_session_state_roundtrip() -> Result<()> {
+    let path = setup_test_dir()?.join("nested").join("state.toml");
+
+    let mut state = SessionState {
+        last_db: Some("work".to_string()),
+        ..Default::default()
+    };
+    state.selections.insert(
+        "sqlite:/tmp/work.db".to_string(),
+        DbSelection {
+            list_id: Some(2),
+            item_id: Some(7),
+        },
+    );
+    state.write(&path)?;
+
+    assert_eq!(SessionState::read(&path)?, state);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_remember_and_restore_selection() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    TodoList::create(
+        &app.pool,
+        NewTodoList {
+            name: "Home".to_string(),
+        },
+    )
+    .await?;
+    let work = TodoList::create(
+        &app.pool,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+    for name in ["First", "Second"] {
+        TodoItem::create(
+            &app.pool,
+            NewTodoItem {
+                list_id: work.id,
+                name: name.to_string(),
+                priority: None,
+                due_date: None,
+            },
+        )
+        .await?;
+    }
+    app.lists_component.load_lists(&app.pool).await?;
+
+    // Select the second item of the second list and remember it
+    app.lists_component.select_list_by_id(work.id);
+    app.lists_component
+        .get_selected_list_mut()
+        .unwrap()
+        .item_state
+        .select(Some(1));
+    app.remember_selection();
+
+    let selection = app.session_state.selections[&app.current_db_config.connection_str].clone();
+    assert_eq!(app.session_state.last_db, Some("test_db".to_string()));
+    assert_eq!(selection.list_id, Some(work.id));
+    assert!(selection.item_id.is_some());
+
+    // Reload everything, as a new launch would, and restore
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.list_state.select(None);
+    app.restore_selection();
+
+    let selected_list = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(selected_list.list.id, work.id);
+    assert_eq!(selected_list.item_state.selected(), Some(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_selection_of_deleted_list() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    app.session_state.selections.insert(
+        app.current_db_config.connection_str.clone(),
+        DbSelection {
+            list_id: Some(42),
+            item_id: Some(42),
+        },
+    );
+    app.restore_selection();
+
+    assert_eq!(app.lists_component.selected(), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_session_of_previous_launch() -> Result<()> {
+    let mut previous = setup_test_app().await?;
+    previous.current_db_config.name = "work".to_string();
+    let list = TodoList::create(
+        &previous.pool,
+        NewTodoList {
+            name: "Errands".to_string(),
+        },
+    )
+    .await?;
+    for name in ["Post office", "Bakery"] {
+        create_item(&previous.pool, list.id, name).await?;
+    }
+    previous.lists_component.load_lists(&previous.pool).await?;
+    previous.lists_component.select_list_by_id(list.id);
+    previous
+        .lists_component
+        .get_selected_list_mut()
+        .unwrap()
+        .item_state
+        .select(Some(1));
+    previous.save_session()?;
+
+    // A new launch, on another default database but remembering in the same place
+    let mut app = setup_test_app().await?;
+    app.config.dbs.push(previous.current_db_config.clone());
+    app.session_path = previous.session_path.clone();
+    app.resume_session().await;
+
+    assert!(app.status.is_none());
+    assert_eq!(app.current_db_config.name, "work");
+    let selected_list = app.lists_component.get_selected_list().unwrap();
+    assert_eq!(selected_list.list.id, list.id);
+    assert_eq!(selected_list.item_state.selected(), Some(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_session_reports_unreadable_state() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    std::fs::write(app.session_path.as_ref().unwrap(), "last_db = [")?;
+
+    app.resume_session().await;
+
+    assert_eq!(app.current_db_config.name, "test_db");
+    let status = app.status.expect("the failure is reported");
+    assert!(status.is_error);
+    assert!(status.text.starts_with("Failed to restore last session"));
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use judo::app::startup::{StartupChoice, StartupStage};
+use judo::app::{App, StartupOutcome, StartupScreen};
+use judo::db::config::{Config, DBConfig};
+use std::path::{Path, PathBuf};
+
+/// judo.toml with two databases in a test directory, the first one being the default
+fn write_config(dir: &Path) -> Result<PathBuf> {
+    let db = |name: &str| DBConfig {
+        name: name.to_string(),
+        connection_str: format!("sqlite:{}", dir.join(format!("{}.db", name)).display()),
+        sqlite: Default::default(),
+    };
+    let config = Config {
+        default: "home".to_string(),
+        dbs: vec![db("home"), db("work")],
+        colours: Default::default(),
+        remember_session: true,
+        trash_retention_days: 30,
+        session: None,
+    };
+    let path = dir.join("judo.toml");
+    config.write(&path)?;
+    Ok(path)
+}
+
+fn path_arg(path: &Path) -> Option<String> {
+    Some(path.display().to_string())
+}
+
+fn started_db(outcome: Option<StartupOutcome>) -> String {
+    match outcome {
+        Some(StartupOutcome::Started(app)) => app.current_db_config.name.clone(),
+        Some(StartupOutcome::Exit) => panic!("Expected the app to start, but it exited"),
+        None => panic!("Expected the app to start, but it failed again"),
+    }
+}
+
+#[tokio::test]
+async fn test_broken_config_offers_to_recreate_it() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(&path, "default = \"home\"\n[[dbs]\n")?;
+
+    let Err(error) = App::new(path_arg(&path), None, false).await else {
+        panic!("A broken config should not start the app");
+    };
+    assert_eq!(error.stage, StartupStage::Config);
+    assert_eq!(error.config_path.as_deref(), Some(path.as_path()));
+    assert!(
+        error
+            .to_string()
+            .contains("could not use the configuration")
+    );
+
+    // Nothing to fall back to without a config
+    let screen = StartupScreen::new(error);
+    assert_eq!(
+        screen.choices,
+        vec![StartupChoice::RecreateConfig, StartupChoice::Exit]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unknown_db_falls_back_to_another() -> Result<()> {
+    let dir = setup_test_dir()?;
+    let path = write_config(&dir)?;
+
+    let Err(error) = App::new(path_arg(&path), Some("missing".to_string()), false).await else {
+        panic!("An unknown database should not start the app");
+    };
+    assert_eq!(error.stage, StartupStage::Config);
+
+    let mut screen = StartupScreen::new(error);
+    assert_eq!(
+        screen.choices,
+        vec![
+            StartupChoice::OpenDb("home".to_string()),
+            StartupChoice::OpenDb("work".to_string()),
+            StartupChoice::RecreateConfig,
+            StartupChoice::Exit,
+        ]
+    );
+
+    screen.handle_key_event(KeyEvent::from(KeyCode::Down)).await;
+    let outcome = screen
+        .handle_key_event(KeyEvent::from(KeyCode::Enter))
+        .await;
+    assert_eq!(started_db(outcome), "work");
+
+    // Only for this session
+    assert_eq!(Config::read(&path)?.default, "home");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_corrupt_db_falls_back_to_another() -> Result<()> {
+    let dir = setup_test_dir()?;
+    let path = write_config(&dir)?;
+    std::fs::write(dir.join("home.db"), "not a database, just some text")?;
+
+    let Err(error) = App::new(path_arg(&path), None, false).await else {
+        panic!("A corrupt database should not start the app");
+    };
+    assert_eq!(error.stage, StartupStage::Database);
+    assert_eq!(error.db_name.as_deref(), Some("home"));
+    assert!(
+        error
+            .to_string()
+            .starts_with("Judo could not open the database 'home'")
+    );
+
+    // The broken database isn't offered again
+    let mut screen = StartupScreen::new(error);
+    assert_eq!(screen.choices[0], StartupChoice::OpenDb("work".to_string()));
+
+    // A fallback that fails too is explained in turn
+    std::fs::write(dir.join("work.db"), "not a database either")?;
+    assert!(screen.choose().await.is_none());
+    assert_eq!(screen.error.db_name.as_deref(), Some("work"));
+    assert_eq!(screen.choices[0], StartupChoice::OpenDb("home".to_string()));
+
+    // Until one works
+    std::fs::remove_file(dir.join("home.db"))?;
+    assert_eq!(started_db(screen.choose().await), "home");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_leave_the_startup_screen() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(&path, "not toml at all")?;
+    let Err(error) = App::new(path_arg(&path), None, false).await else {
+        panic!("A broken config should not start the app");
+    };
+    let mut screen = StartupScreen::new(error);
+
+    // Selection stays within the choices
+    screen.handle_key_event(KeyEvent::from(KeyCode::Up)).await;
+    assert_eq!(screen.selected, 0);
+    for _ in 0..5 {
+        screen.handle_key_event(KeyEvent::from(KeyCode::Down)).await;
+    }
+    assert_eq!(screen.choices[screen.selected], StartupChoice::Exit);
+    assert!(matches!(screen.choose().await, Some(StartupOutcome::Exit)));
+
+    assert!(matches!(
+        screen.handle_key_event(KeyEvent::from(KeyCode::Esc)).await,
+        Some(StartupOutcome::Exit)
+    ));
+
+    // The broken file is left alone
+    assert_eq!(std::fs::read_to_string(&path)?, "not toml at all");
+
+    Ok(())
+}
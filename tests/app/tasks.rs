@@ -0,0 +1,111 @@
+use crate::helpers::app::setup_test_app;
+use crate::helpers::db::create_item;
+use anyhow::Result;
+use chrono::{Duration, Local};
+use crossterm::event::{KeyCode, KeyEvent};
+use judo::app::EventHandler;
+use judo::db::models::{NewTodoList, TodoList};
+use judo::ui::components::StatusMessage;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Widget;
+use std::time::Instant;
+
+#[tokio::test]
+async fn test_background_task_results_show_as_status() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    app.tasks
+        .spawn(async { Ok(Some("Imported 3 items".to_string())) });
+    let result = app.tasks.next().await;
+    app.handle_task_result(result);
+    let status = app.status.clone().unwrap();
+    assert_eq!(status.text, "Imported 3 items");
+    assert!(!status.is_error);
+
+    // Failures show as errors
+    app.tasks
+        .spawn(async { Err(anyhow::anyhow!("Failed to read import file")) });
+    let result = app.tasks.next().await;
+    app.handle_task_result(result);
+    let status = app.status.clone().unwrap();
+    assert_eq!(status.text, "Failed to read import file");
+    assert!(status.is_error);
+
+    // Nothing to say leaves the status alone
+    app.tasks.spawn(async { Ok(None) });
+    let result = app.tasks.next().await;
+    app.handle_task_result(result);
+    assert_eq!(app.status, Some(status));
+    assert!(app.tasks.try_next().is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_status_expires_on_tick() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    app.status = Some(StatusMessage::info("Saved"));
+    app.on_tick().await;
+    assert!(app.status.is_some());
+
+    // It shows at the bottom of the screen
+    let area = Rect::new(0, 0, 100, 30);
+    let mut buf = Buffer::empty(area);
+    (&mut app).render(area, &mut buf);
+    let bottom: String = (0..area.width)
+        .map(|x| buf[(x, area.height - 1)].symbol().to_string())
+        .collect();
+    assert!(bottom.contains("Saved"));
+
+    app.status.as_mut().unwrap().shown_until = Instant::now();
+    app.on_tick().await;
+    assert_eq!(app.status, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lists_reload_when_the_day_changes() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    app.refresh_if_changed().await?;
+    assert!(!app.refresh_if_changed().await?);
+
+    // Overdue and due-today markers depend on the date
+    app.today -= Duration::days(1);
+    assert!(app.refresh_if_changed().await?);
+    assert_eq!(app.today, Local::now().date_naive());
+    assert!(!app.refresh_if_changed().await?);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_key_handler_errors_show_as_status() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    let list = TodoList::create(
+        &app.pool,
+        NewTodoList {
+            name: "Chores".to_string(),
+        },
+    )
+    .await?;
+    create_item(&app.pool, list.id, "Dishes").await?;
+    app.lists_component.load_lists(&app.pool).await?;
+    app.lists_component.select_first();
+    app.lists_component
+        .get_selected_list_mut()
+        .unwrap()
+        .item_state
+        .select(Some(0));
+
+    app.pool.close().await;
+    EventHandler::handle_main_screen_key(&mut app, KeyEvent::from(KeyCode::Char('d'))).await;
+
+    let status = app.status.expect("the failure is reported");
+    assert!(status.is_error);
+    assert!(status.text.starts_with("Failed to delete item"));
+
+    Ok(())
+}
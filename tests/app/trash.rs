@@ -0,0 +1,114 @@
+use crate::helpers::app::setup_test_app;
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use judo::app::{App, EventHandler};
+use judo::db::config::{Config, DBConfig};
+use judo::db::connections::init_db;
+use judo::db::models::{NewTodoList, TodoList, TrashEntry};
+use judo::ui::components::trash::PendingPurge;
+
+async fn press(app: &mut App, key: KeyCode) {
+    EventHandler::handle_trash_screen_key(app, KeyEvent::new(key, KeyModifiers::NONE)).await;
+}
+
+async fn setup_trash(app: &mut App, names: &[&str]) -> Result<()> {
+    for name in names {
+        let list = TodoList::create(
+            &app.pool,
+            NewTodoList {
+                name: name.to_string(),
+            },
+        )
+        .await?;
+        list.delete(&app.pool).await?;
+    }
+    app.enter_trash_screen().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_purge_waits_for_confirmation() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    setup_trash(&mut app, &["Groceries", "Chores"]).await?;
+
+    // Anything but a yes leaves the trash alone
+    press(&mut app, KeyCode::Char('d')).await;
+    assert_eq!(
+        app.trash_component.pending_purge,
+        Some(PendingPurge::Selected)
+    );
+    press(&mut app, KeyCode::Char('n')).await;
+    assert_eq!(app.trash_component.pending_purge, None);
+    assert_eq!(TrashEntry::get_all(&app.pool).await?.len(), 2);
+
+    press(&mut app, KeyCode::Char('d')).await;
+    press(&mut app, KeyCode::Char('y')).await;
+    assert_eq!(app.trash_component.pending_purge, None);
+    assert_eq!(TrashEntry::get_all(&app.pool).await?.len(), 1);
+    assert_eq!(app.trash_component.entries.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_empty_waits_for_confirmation() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    setup_trash(&mut app, &["Groceries", "Chores"]).await?;
+
+    press(&mut app, KeyCode::Char('E')).await;
+    assert_eq!(app.trash_component.pending_purge, Some(PendingPurge::All));
+    press(&mut app, KeyCode::Esc).await;
+    assert_eq!(TrashEntry::get_all(&app.pool).await?.len(), 2);
+
+    press(&mut app, KeyCode::Char('E')).await;
+    press(&mut app, KeyCode::Char('y')).await;
+    assert!(TrashEntry::get_all(&app.pool).await?.is_empty());
+
+    // Nothing to confirm once the trash is empty
+    press(&mut app, KeyCode::Char('E')).await;
+    assert_eq!(app.trash_component.pending_purge, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_opening_the_app_leaves_expired_trash_alone() -> Result<()> {
+    let dir = setup_test_dir()?;
+    let db_config = DBConfig {
+        name: "dojo".to_string(),
+        connection_str: format!("sqlite:{}", dir.join("dojo.db").display()),
+        sqlite: Default::default(),
+    };
+    let pool = init_db(&db_config.connection_str).await?;
+    let list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Old".to_string(),
+        },
+    )
+    .await?;
+    let list_id = list.id;
+    list.delete(&pool).await?;
+    sqlx::query("UPDATE todo_lists SET deleted_at = ?1 WHERE id = ?2")
+        .bind(Utc::now() - Duration::days(45))
+        .bind(list_id)
+        .execute(&pool)
+        .await?;
+
+    // One-shot CLI commands open the app too, and purge only when they deal with the trash
+    let config = Config {
+        default: "dojo".to_string(),
+        dbs: vec![db_config],
+        ..Default::default()
+    };
+    let mut app = App::open(dir.join("judo.toml"), config).await?;
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    assert!(app.tasks.try_next().is_none());
+    assert_eq!(TrashEntry::get_all(&pool).await?.len(), 1);
+    assert!(app.change_watcher.is_none());
+
+    Ok(())
+}
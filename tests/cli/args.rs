@@ -1,5 +1,10 @@
+use chrono::NaiveDate;
 use clap::Parser;
-use judo::cli::args::{Cli, Commands, DbCommands, ItemCommands, ListCommands};
+use judo::cli::args::{
+    Cli, Commands, DbCommands, ItemCommands, ListCommands, SmartCommands, TrashCommands,
+};
+use judo::db::dates::start_of_day;
+use judo::db::models::{Priority, SortMode};
 
 #[test]
 fn test_cli_no_command() {
@@ -10,6 +15,80 @@ fn test_cli_no_command() {
     assert!(cli.command.is_none());
 }
 
+#[test]
+fn test_global_config_and_db_flags() {
+    // Test parsing the global --config and --db flags before a subcommand
+    let cli = Cli::try_parse_from([
+        "judo",
+        "--config",
+        "/tmp/judo.toml",
+        "--db",
+        "work",
+        "lists",
+        "show",
+    ])
+    .unwrap();
+    assert_eq!(cli.config, Some("/tmp/judo.toml".to_string()));
+    assert_eq!(cli.db, Some("work".to_string()));
+    assert!(matches!(cli.command, Some(Commands::Lists { .. })));
+}
+
+#[test]
+fn test_global_flags_without_subcommand() {
+    // Test parsing the global flags when launching the TUI
+    let cli = Cli::try_parse_from(["judo", "--db", "work"]).unwrap();
+    assert_eq!(cli.db, Some("work".to_string()));
+    assert!(cli.command.is_none());
+}
+
+#[test]
+fn test_tui_command() {
+    // Test parsing "tui" with a database and a list name
+    let cli = Cli::try_parse_from(["judo", "tui", "--db", "work", "--list", "sprint"]).unwrap();
+    match cli.command {
+        Some(Commands::Tui { db, list, list_id }) => {
+            assert_eq!(db, Some("work".to_string()));
+            assert_eq!(list, Some("sprint".to_string()));
+            assert!(list_id.is_none());
+        }
+        _ => panic!("Expected Commands::Tui"),
+    }
+
+    // Test parsing "tui" with a list ID
+    let cli = Cli::try_parse_from(["judo", "tui", "-i", "3"]).unwrap();
+    match cli.command {
+        Some(Commands::Tui { db, list, list_id }) => {
+            assert!(db.is_none());
+            assert!(list.is_none());
+            assert_eq!(list_id, Some(3));
+        }
+        _ => panic!("Expected Commands::Tui"),
+    }
+}
+
+#[test]
+fn test_tui_command_list_name_and_id_conflict() {
+    // Test that a list cannot be selected by both name and ID
+    let args = Cli::try_parse_from(["judo", "tui", "--list", "sprint", "--list-id", "3"]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn test_init_command() {
+    // Test parsing "init" with and without a name
+    let cli = Cli::try_parse_from(["judo", "init"]).unwrap();
+    match cli.command {
+        Some(Commands::Init { name }) => assert!(name.is_none()),
+        _ => panic!("Expected Commands::Init"),
+    }
+
+    let cli = Cli::try_parse_from(["judo", "init", "--name", "repo"]).unwrap();
+    match cli.command {
+        Some(Commands::Init { name }) => assert_eq!(name, Some("repo".to_string())),
+        _ => panic!("Expected Commands::Init"),
+    }
+}
+
 #[test]
 fn test_dbs_show_command() {
     // Test parsing "dbs show" command
@@ -37,8 +116,9 @@ fn test_dbs_add_command_with_name() {
 
     match cli.command {
         Some(Commands::Dbs { command }) => match command {
-            Some(DbCommands::Add { name }) => {
-                assert_eq!(name, "test_db");
+            Some(DbCommands::Add { name, path }) => {
+                assert_eq!(name, Some("test_db".to_string()));
+                assert!(path.is_none());
             }
             _ => panic!("Expected DbCommands::Add"),
         },
@@ -55,8 +135,9 @@ fn test_dbs_add_command_with_short_flag() {
 
     match cli.command {
         Some(Commands::Dbs { command }) => match command {
-            Some(DbCommands::Add { name }) => {
-                assert_eq!(name, "my_database");
+            Some(DbCommands::Add { name, path }) => {
+                assert_eq!(name, Some("my_database".to_string()));
+                assert!(path.is_none());
             }
             _ => panic!("Expected DbCommands::Add"),
         },
@@ -71,6 +152,44 @@ fn test_dbs_add_command_missing_name() {
     assert!(args.is_err());
 }
 
+#[test]
+fn test_dbs_add_command_with_path() {
+    // Test parsing "dbs add" with a path and no name
+    let args = Cli::try_parse_from(["judo", "dbs", "add", "--path", "./todo.db"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Dbs { command }) => match command {
+            Some(DbCommands::Add { name, path }) => {
+                assert!(name.is_none());
+                assert_eq!(path, Some("./todo.db".to_string()));
+            }
+            _ => panic!("Expected DbCommands::Add"),
+        },
+        _ => panic!("Expected Commands::Dbs"),
+    }
+}
+
+#[test]
+fn test_dbs_add_command_with_path_and_name() {
+    // Test parsing "dbs add" with both a path and a name
+    let args = Cli::try_parse_from(["judo", "dbs", "add", "-p", "~/todo.db", "-n", "project"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Dbs { command }) => match command {
+            Some(DbCommands::Add { name, path }) => {
+                assert_eq!(name, Some("project".to_string()));
+                assert_eq!(path, Some("~/todo.db".to_string()));
+            }
+            _ => panic!("Expected DbCommands::Add"),
+        },
+        _ => panic!("Expected Commands::Dbs"),
+    }
+}
+
 #[test]
 fn test_lists_show_command_no_filter() {
     // Test parsing "lists show" without filters
@@ -80,7 +199,8 @@ fn test_lists_show_command_no_filter() {
 
     match cli.command {
         Some(Commands::Lists { command }) => match command {
-            Some(ListCommands::Show { name }) => {
+            Some(ListCommands::Show { name, archived }) => {
+                assert!(!archived);
                 assert!(name.is_none());
             }
             _ => panic!("Expected ListCommands::Show"),
@@ -98,7 +218,8 @@ fn test_lists_show_command_with_name_filter() {
 
     match cli.command {
         Some(Commands::Lists { command }) => match command {
-            Some(ListCommands::Show { name }) => {
+            Some(ListCommands::Show { name, archived }) => {
+                assert!(!archived);
                 assert_eq!(name, Some("my_list".to_string()));
             }
             _ => panic!("Expected ListCommands::Show"),
@@ -205,6 +326,65 @@ fn test_lists_delete_with_db() {
     }
 }
 
+#[test]
+fn test_lists_show_archived() {
+    // Test parsing "lists show" with the archived flag
+    let args = Cli::try_parse_from(["judo", "lists", "show", "--archived"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Lists { command }) => match command {
+            Some(ListCommands::Show { name, archived }) => {
+                assert!(archived);
+                assert!(name.is_none());
+            }
+            _ => panic!("Expected ListCommands::Show"),
+        },
+        _ => panic!("Expected Commands::Lists"),
+    }
+}
+
+#[test]
+fn test_lists_archive_by_name() {
+    // Test parsing "lists archive" with name and db
+    let args = Cli::try_parse_from(["judo", "lists", "archive", "-n", "old", "-d", "test_db"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Lists { command }) => match command {
+            Some(ListCommands::Archive { name, id, db }) => {
+                assert_eq!(name, Some("old".to_string()));
+                assert!(id.is_none());
+                assert_eq!(db, Some("test_db".to_string()));
+            }
+            _ => panic!("Expected ListCommands::Archive"),
+        },
+        _ => panic!("Expected Commands::Lists"),
+    }
+}
+
+#[test]
+fn test_lists_unarchive_by_id() {
+    // Test parsing "lists unarchive" with ID
+    let args = Cli::try_parse_from(["judo", "lists", "unarchive", "--id", "7"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Lists { command }) => match command {
+            Some(ListCommands::Unarchive { name, id, db }) => {
+                assert!(name.is_none());
+                assert_eq!(id, Some(7));
+                assert!(db.is_none());
+            }
+            _ => panic!("Expected ListCommands::Unarchive"),
+        },
+        _ => panic!("Expected Commands::Lists"),
+    }
+}
+
 #[test]
 fn test_items_show_command() {
     // Test parsing "items show"
@@ -214,8 +394,8 @@ fn test_items_show_command() {
 
     match cli.command {
         Some(Commands::Items { command }) => match command {
-            Some(ItemCommands::Show) => {
-                // Success
+            Some(ItemCommands::Show { query }) => {
+                assert!(query.is_none());
             }
             _ => panic!("Expected ItemCommands::Show"),
         },
@@ -223,6 +403,37 @@ fn test_items_show_command() {
     }
 }
 
+#[test]
+fn test_items_clear_done() {
+    // Test parsing "items clear-done" with the --list alias
+    let args = Cli::try_parse_from(["judo", "items", "clear-done", "--list", "chores"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Items { command }) => match command {
+            Some(ItemCommands::ClearDone {
+                db,
+                list_name,
+                list_id,
+            }) => {
+                assert!(db.is_none());
+                assert_eq!(list_name, Some("chores".to_string()));
+                assert!(list_id.is_none());
+            }
+            _ => panic!("Expected ItemCommands::ClearDone"),
+        },
+        _ => panic!("Expected Commands::Items"),
+    }
+}
+
+#[test]
+fn test_items_clear_done_list_name_and_id_conflict() {
+    // Test that the list to clear cannot be given by both name and ID
+    let args = Cli::try_parse_from(["judo", "items", "clear-done", "-l", "chores", "-i", "3"]);
+    assert!(args.is_err());
+}
+
 #[test]
 fn test_items_add_minimal() {
     // Test parsing "items add" with minimal arguments
@@ -237,11 +448,13 @@ fn test_items_add_minimal() {
                 db,
                 list_name,
                 list_id,
+                due,
             }) => {
                 assert_eq!(name, "Buy milk");
                 assert!(db.is_none());
                 assert!(list_name.is_none());
                 assert!(list_id.is_none());
+                assert!(due.is_none());
             }
             _ => panic!("Expected ItemCommands::Add"),
         },
@@ -271,11 +484,13 @@ fn test_items_add_with_list_name() {
                 db,
                 list_name,
                 list_id,
+                due,
             }) => {
                 assert_eq!(name, "Task 1");
                 assert!(db.is_none());
                 assert_eq!(list_name, Some("shopping".to_string()));
                 assert!(list_id.is_none());
+                assert!(due.is_none());
             }
             _ => panic!("Expected ItemCommands::Add"),
         },
@@ -297,11 +512,13 @@ fn test_items_add_with_list_id() {
                 db,
                 list_name,
                 list_id,
+                due,
             }) => {
                 assert_eq!(name, "Task 2");
                 assert!(db.is_none());
                 assert!(list_name.is_none());
                 assert_eq!(list_id, Some(5));
+                assert!(due.is_none());
             }
             _ => panic!("Expected ItemCommands::Add"),
         },
@@ -333,11 +550,13 @@ fn test_items_add_with_db_and_list() {
                 db,
                 list_name,
                 list_id,
+                due,
             }) => {
                 assert_eq!(name, "Important task");
                 assert_eq!(db, Some("work_db".to_string()));
                 assert_eq!(list_name, Some("projects".to_string()));
                 assert!(list_id.is_none());
+                assert!(due.is_none());
             }
             _ => panic!("Expected ItemCommands::Add"),
         },
@@ -542,3 +761,339 @@ fn test_cli_with_zero_id() {
         _ => panic!("Expected Commands::Lists"),
     }
 }
+
+#[test]
+fn test_trash_show_with_db() {
+    // Test parsing "trash show" with db
+    let args = Cli::try_parse_from(["judo", "trash", "show", "-d", "test_db"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Trash { command }) => match command {
+            Some(TrashCommands::Show { db }) => {
+                assert_eq!(db, Some("test_db".to_string()));
+            }
+            _ => panic!("Expected TrashCommands::Show"),
+        },
+        _ => panic!("Expected Commands::Trash"),
+    }
+}
+
+#[test]
+fn test_trash_restore_item() {
+    // Test parsing "trash restore" with an item ID
+    let args = Cli::try_parse_from(["judo", "trash", "restore", "--item-id", "5"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Trash { command }) => match command {
+            Some(TrashCommands::Restore {
+                list_id,
+                item_id,
+                db,
+            }) => {
+                assert!(list_id.is_none());
+                assert_eq!(item_id, Some(5));
+                assert!(db.is_none());
+            }
+            _ => panic!("Expected TrashCommands::Restore"),
+        },
+        _ => panic!("Expected Commands::Trash"),
+    }
+}
+
+#[test]
+fn test_trash_restore_requires_one_id() {
+    // Test that "trash restore" needs exactly one of the list and item IDs
+    assert!(Cli::try_parse_from(["judo", "trash", "restore"]).is_err());
+    assert!(Cli::try_parse_from(["judo", "trash", "restore", "-l", "1", "-i", "2"]).is_err());
+}
+
+#[test]
+fn test_trash_empty() {
+    // Test parsing "trash empty"
+    let args = Cli::try_parse_from(["judo", "trash", "empty"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Trash { command }) => match command {
+            Some(TrashCommands::Empty { db }) => assert!(db.is_none()),
+            _ => panic!("Expected TrashCommands::Empty"),
+        },
+        _ => panic!("Expected Commands::Trash"),
+    }
+}
+
+#[test]
+fn test_lists_sort_command() {
+    // Test parsing "lists sort" with a mode and a list name
+    let args = Cli::try_parse_from(["judo", "lists", "sort", "due-date", "-n", "work"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Lists { command }) => match command {
+            Some(ListCommands::Sort { mode, name, id, db }) => {
+                assert_eq!(mode, SortMode::DueDate);
+                assert_eq!(name, Some("work".to_string()));
+                assert!(id.is_none());
+                assert!(db.is_none());
+            }
+            _ => panic!("Expected ListCommands::Sort"),
+        },
+        _ => panic!("Expected Commands::Lists"),
+    }
+}
+
+#[test]
+fn test_lists_sort_invalid_mode() {
+    // Test that unknown sort modes are rejected
+    let args = Cli::try_parse_from(["judo", "lists", "sort", "random", "-n", "work"]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn test_agenda_command() {
+    // Test parsing "agenda" for all databases
+    let args = Cli::try_parse_from(["judo", "agenda", "--all"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Agenda { db, all }) => {
+            assert!(db.is_none());
+            assert!(all);
+        }
+        _ => panic!("Expected Commands::Agenda"),
+    }
+
+    // A single database can't be combined with all of them
+    assert!(Cli::try_parse_from(["judo", "agenda", "--all", "-d", "work"]).is_err());
+}
+
+#[test]
+fn test_lists_columns_command() {
+    // Test parsing "lists columns" with a comma-separated list of columns
+    let args = Cli::try_parse_from(["judo", "lists", "columns", "todo,review,done", "-i", "3"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Lists { command }) => match command {
+            Some(ListCommands::Columns {
+                columns,
+                name,
+                id,
+                db,
+            }) => {
+                assert_eq!(columns, "todo,review,done");
+                assert!(name.is_none());
+                assert_eq!(id, Some(3));
+                assert!(db.is_none());
+            }
+            _ => panic!("Expected ListCommands::Columns"),
+        },
+        _ => panic!("Expected Commands::Lists"),
+    }
+}
+
+#[test]
+fn test_items_move_command() {
+    // Test parsing "items move" with a column and an item ID
+    let args = Cli::try_parse_from(["judo", "items", "move", "doing", "-i", "7", "-d", "work"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Items { command }) => match command {
+            Some(ItemCommands::Move { status, id, db }) => {
+                assert_eq!(status, "doing");
+                assert_eq!(id, 7);
+                assert_eq!(db, Some("work".to_string()));
+            }
+            _ => panic!("Expected ItemCommands::Move"),
+        },
+        _ => panic!("Expected Commands::Items"),
+    }
+}
+
+#[test]
+fn test_items_reorder_command() {
+    // Test parsing "items reorder" with an item ID and a position
+    let args = Cli::try_parse_from(["judo", "items", "reorder", "--id", "7", "--position", "1"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Items { command }) => match command {
+            Some(ItemCommands::Reorder { id, position, db }) => {
+                assert_eq!(id, 7);
+                assert_eq!(position, 1);
+                assert!(db.is_none());
+            }
+            _ => panic!("Expected ItemCommands::Reorder"),
+        },
+        _ => panic!("Expected Commands::Items"),
+    }
+
+    // The position is required
+    let args = Cli::try_parse_from(["judo", "items", "reorder", "--id", "7"]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn test_items_add_with_due_date() {
+    // Test parsing "items add" with a due date in plain words
+    let args = Cli::try_parse_from([
+        "judo",
+        "items",
+        "add",
+        "--name",
+        "Pay rent",
+        "--due",
+        "2026-10-20",
+    ]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Items { command }) => match command {
+            Some(ItemCommands::Add { due, .. }) => {
+                let expected = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+                assert_eq!(due, Some(start_of_day(expected)));
+            }
+            _ => panic!("Expected ItemCommands::Add"),
+        },
+        _ => panic!("Expected Commands::Items"),
+    }
+
+    // Dates that can't be read are rejected
+    let args = Cli::try_parse_from([
+        "judo", "items", "add", "--name", "Pay rent", "--due", "someday",
+    ]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn test_items_due_command() {
+    // Test parsing "items due" with a date and with --clear
+    let args = Cli::try_parse_from(["judo", "items", "due", "--due", "next fri", "-i", "7"]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Items { command }) => match command {
+            Some(ItemCommands::Due { due, clear, id, db }) => {
+                assert!(due.is_some());
+                assert!(!clear);
+                assert_eq!(id, 7);
+                assert!(db.is_none());
+            }
+            _ => panic!("Expected ItemCommands::Due"),
+        },
+        _ => panic!("Expected Commands::Items"),
+    }
+
+    let args = Cli::try_parse_from(["judo", "items", "due", "--clear", "-i", "7"]);
+    match args.unwrap().command {
+        Some(Commands::Items {
+            command: Some(ItemCommands::Due { due, clear, .. }),
+        }) => {
+            assert!(due.is_none());
+            assert!(clear);
+        }
+        _ => panic!("Expected ItemCommands::Due"),
+    }
+
+    // Either a date or --clear, not both or neither
+    assert!(Cli::try_parse_from(["judo", "items", "due", "-i", "7"]).is_err());
+    assert!(
+        Cli::try_parse_from([
+            "judo", "items", "due", "--due", "today", "--clear", "-i", "7"
+        ])
+        .is_err()
+    );
+}
+
+#[test]
+fn test_smart_add_command() {
+    // Test parsing "smart add" with several criteria
+    let args = Cli::try_parse_from([
+        "judo",
+        "smart",
+        "add",
+        "-n",
+        "Urgent",
+        "-p",
+        "high",
+        "--due-within",
+        "7",
+        "--pending",
+    ]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Smart { command }) => match command {
+            Some(SmartCommands::Add {
+                name,
+                priority,
+                due_within,
+                done,
+                pending,
+                tag,
+                text,
+                db,
+            }) => {
+                assert_eq!(name, "Urgent");
+                assert_eq!(priority, Some(Priority::High));
+                assert_eq!(due_within, Some(7));
+                assert!(!done);
+                assert!(pending);
+                assert!(tag.is_none());
+                assert!(text.is_none());
+                assert!(db.is_none());
+            }
+            _ => panic!("Expected SmartCommands::Add"),
+        },
+        _ => panic!("Expected Commands::Smart"),
+    }
+}
+
+#[test]
+fn test_smart_add_conflicting_done_flags() {
+    // Test that --done and --pending can't be used together
+    let args = Cli::try_parse_from(["judo", "smart", "add", "-n", "x", "--done", "--pending"]);
+    assert!(args.is_err());
+}
+
+#[test]
+fn test_items_show_with_query() {
+    // Test parsing "items show --query" into a typed query
+    let args = Cli::try_parse_from([
+        "judo",
+        "items",
+        "show",
+        "--query",
+        "priority:high due<7d !done",
+    ]);
+    assert!(args.is_ok());
+    let cli = args.unwrap();
+
+    match cli.command {
+        Some(Commands::Items { command }) => match command {
+            Some(ItemCommands::Show { query }) => {
+                assert_eq!(query.unwrap().terms.len(), 3);
+            }
+            _ => panic!("Expected ItemCommands::Show"),
+        },
+        _ => panic!("Expected Commands::Items"),
+    }
+
+    // Invalid queries are rejected while parsing the arguments
+    let args = Cli::try_parse_from(["judo", "items", "show", "-q", "priority:urgent"]);
+    assert!(args.is_err());
+}
@@ -1,39 +1,134 @@
-use crate::helpers::db::setup_test_db_shared;
+use crate::helpers::app::setup_test_app;
+use crate::helpers::fs::setup_test_dir;
 use anyhow::Result;
-use judo::app::App;
-use judo::cli::ops::{add_item, add_list, delete_item, delete_list, toggle_done_item};
+use judo::cli::InvalidInput;
+use judo::cli::ops::{
+    add_db, add_item, add_list, archive_list, clear_done_items, delete_item, delete_list,
+    empty_trash, list_lists, reorder_item, restore_from_trash, set_item_due_date, show_agenda,
+    toggle_done_item, unarchive_list,
+};
 use judo::db::config::{Config, DBConfig};
-use judo::db::models::{NewTodoList, TodoItem, TodoList};
-
-/// Build a test App backed by a named shared in-memory database.
-///
-/// Because the connection string uses `cache=shared`, the pools that
-/// CLI ops create internally (via `get_db_pool`) will hit the **same**
-/// in-memory database as the pool stored on the returned `App`.
-async fn setup_test_app() -> Result<App> {
-    let (pool, connection_str) = setup_test_db_shared().await?;
-
-    let test_db_config = DBConfig {
-        name: "test_db".to_string(),
-        connection_str,
-    };
-
-    let config = Config {
-        default: "test_db".to_string(),
-        dbs: vec![test_db_config.clone()],
-        colours: Default::default(),
-    };
-
-    Ok(App {
-        config,
-        current_db_config: test_db_config,
-        current_screen: judo::app::state::CurrentScreen::Main,
-        pool,
-        lists_component: judo::ui::components::ListsComponent::new(),
-        input_state: judo::ui::components::InputState::new(),
-        selected_db_index: 0,
-        exit: false,
-    })
+use judo::db::connections::{get_db_pool, init_db};
+use judo::db::dates::parse_due_date;
+use judo::db::models::{NewTodoList, Priority, SortMode, TodoItem, TodoList, TrashEntry};
+
+// ===== Database Operations Tests =====
+
+#[tokio::test]
+async fn test_operations_share_one_pool_per_database() -> Result<()> {
+    let mut app = setup_test_app().await?;
+
+    // A second database, not opened yet
+    let db_path = setup_test_dir()?.join("other.db");
+    app.config.dbs.push(DBConfig {
+        name: "other".to_string(),
+        connection_str: format!("sqlite:{}", db_path.display()),
+        sqlite: Default::default(),
+    });
+
+    add_list(&app, "Chores".to_string(), &None).await?;
+    add_item(
+        &app,
+        "Dishes".to_string(),
+        &None,
+        None,
+        Some("Chores".to_string()),
+        None,
+    )
+    .await?;
+    assert_eq!(app.pools.len(), 1);
+
+    // Listing goes through every database, opening and migrating the new one
+    list_lists(&app, None, false).await?;
+    assert_eq!(app.pools.len(), 2);
+    add_list(&app, "Errands".to_string(), &Some("other".to_string())).await?;
+    list_lists(&app, None, false).await?;
+    assert_eq!(app.pools.len(), 2);
+
+    app.pools.close_all().await;
+    assert!(app.pools.is_empty());
+    assert!(app.pool.is_closed());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_agenda_of_all_dbs_skips_unreadable_ones() -> Result<()> {
+    let mut app = setup_test_app().await?;
+    let missing_dir = setup_test_dir()?.join("missing");
+    app.config.dbs.push(DBConfig {
+        name: "broken".to_string(),
+        connection_str: format!("sqlite:{}", missing_dir.join("broken.db").display()),
+        sqlite: Default::default(),
+    });
+
+    // The readable ones are shown before the command fails
+    let error = show_agenda(&app, &None, true).await.unwrap_err();
+    assert!(format!("{:#}", error).contains("'broken'"));
+    // Asked for on its own, it still fails
+    assert!(
+        show_agenda(&app, &Some("broken".to_string()), false)
+            .await
+            .is_err()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_db_registers_existing_file() -> Result<()> {
+    let app = setup_test_app().await?;
+    let config_path = app.config_path.clone();
+
+    // Create a judo database outside of the data directory
+    let db_path = setup_test_dir()?.join("todo.db");
+    init_db(&format!("sqlite:{}", db_path.display())).await?;
+
+    add_db(app, None, Some(db_path.display().to_string())).await?;
+
+    let config = Config::read(&config_path)?;
+    let registered = config.get_db_by_name("todo".to_string())?;
+    assert_eq!(
+        registered.connection_str,
+        format!("sqlite:{}", db_path.display())
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_db_rejects_foreign_file() -> Result<()> {
+    let app = setup_test_app().await?;
+
+    let db_path = setup_test_dir()?.join("notes.db");
+    let pool = init_db(&format!("sqlite:{}", db_path.display())).await?;
+    sqlx::query("DROP TABLE todo_items").execute(&pool).await?;
+    pool.close().await;
+
+    assert!(
+        add_db(
+            app,
+            Some("notes".to_string()),
+            Some(db_path.display().to_string())
+        )
+        .await
+        .is_err()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_db_rejects_missing_file() -> Result<()> {
+    let app = setup_test_app().await?;
+    let db_path = setup_test_dir()?.join("missing.db");
+
+    assert!(
+        add_db(app, None, Some(db_path.display().to_string()))
+            .await
+            .is_err()
+    );
+
+    Ok(())
 }
 
 // ===== List Operations Tests =====
@@ -178,6 +273,7 @@ async fn test_add_item_to_list_by_name() -> Result<()> {
         &None,
         None,
         Some("Shopping".to_string()),
+        None,
     )
     .await?;
 
@@ -190,6 +286,116 @@ async fn test_add_item_to_list_by_name() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_add_item_with_quick_add_syntax() -> Result<()> {
+    let app = setup_test_app().await?;
+
+    add_list(&app, "Shopping".to_string(), &None).await?;
+    add_list(&app, "Chores".to_string(), &None).await?;
+
+    // ^list picks the list when no list option is given
+    add_item(
+        &app,
+        "Pay rent !high @tomorrow #home #bills ^Chores".to_string(),
+        &None,
+        None,
+        None,
+        None,
+    )
+    .await?;
+
+    let lists = TodoList::get_all(&app.pool).await?;
+    assert!(lists[0].get_all_items(&app.pool).await?.is_empty());
+    let items = lists[1].get_all_items(&app.pool).await?;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "Pay rent");
+    assert_eq!(items[0].priority, Some(Priority::High));
+    assert_eq!(items[0].tags(), vec!["home", "bills"]);
+    let tomorrow = chrono::Local::now().date_naive() + chrono::Duration::days(1);
+    assert_eq!(
+        items[0]
+            .due_date
+            .map(|due| due.with_timezone(&chrono::Local).date_naive()),
+        Some(tomorrow)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_item_and_set_due_date() -> Result<()> {
+    let app = setup_test_app().await?;
+    add_list(&app, "Chores".to_string(), &None).await?;
+
+    // --due wins over a date typed in the name
+    let due = parse_due_date("next fri").unwrap();
+    add_item(
+        &app,
+        "Pay rent @tomorrow".to_string(),
+        &None,
+        None,
+        Some("Chores".to_string()),
+        Some(due),
+    )
+    .await?;
+
+    let lists = TodoList::get_all(&app.pool).await?;
+    let item = lists[0].get_all_items(&app.pool).await?.remove(0);
+    assert_eq!(item.name, "Pay rent");
+    assert_eq!(item.due_date, Some(due));
+
+    let later = parse_due_date("oct 20 2030").unwrap();
+    set_item_due_date(&app, Some(later), item.id, &None).await?;
+    let item = TodoItem::get_by_id(&app.pool, item.id).await?.unwrap();
+    assert_eq!(item.due_date, Some(later));
+
+    set_item_due_date(&app, None, item.id, &None).await?;
+    let item = TodoItem::get_by_id(&app.pool, item.id).await?.unwrap();
+    assert_eq!(item.due_date, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reorder_item() -> Result<()> {
+    let app = setup_test_app().await?;
+    add_list(&app, "Chores".to_string(), &None).await?;
+    for name in ["Dishes", "Laundry", "Groceries"] {
+        add_item(
+            &app,
+            name.to_string(),
+            &None,
+            None,
+            Some("Chores".to_string()),
+            None,
+        )
+        .await?;
+    }
+    let mut list = TodoList::get_all(&app.pool).await?.remove(0);
+    let names =
+        |items: Vec<TodoItem>| -> Vec<String> { items.into_iter().map(|i| i.name).collect() };
+
+    let groceries = list.get_all_items(&app.pool).await?.remove(2);
+    reorder_item(&app, groceries.id, 1, &None).await?;
+    assert_eq!(
+        names(list.get_all_items(&app.pool).await?),
+        vec!["Groceries", "Dishes", "Laundry"]
+    );
+
+    // Positions count from what a sorted list shows, which becomes its manual order
+    list.set_sort_mode(&app.pool, SortMode::Alphabetical)
+        .await?;
+    reorder_item(&app, groceries.id, 3, &None).await?;
+    let list = TodoList::get_by_id(&app.pool, list.id).await?.unwrap();
+    assert_eq!(list.sort_mode, SortMode::Manual);
+    assert_eq!(
+        names(list.get_all_items(&app.pool).await?),
+        vec!["Dishes", "Laundry", "Groceries"]
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_add_item_to_list_by_id() -> Result<()> {
     let app = setup_test_app().await?;
@@ -208,6 +414,7 @@ async fn test_add_item_to_list_by_id() -> Result<()> {
         &None,
         Some(created.id),
         None,
+        None,
     )
     .await?;
 
@@ -231,6 +438,7 @@ async fn test_add_multiple_items_to_list() -> Result<()> {
             &None,
             None,
             Some("Work".to_string()),
+            None,
         )
         .await?;
     }
@@ -260,6 +468,7 @@ async fn test_add_item_with_special_characters() -> Result<()> {
         &None,
         None,
         Some("Test".to_string()),
+        None,
     )
     .await?;
 
@@ -283,6 +492,7 @@ async fn test_add_item_with_unicode() -> Result<()> {
         &None,
         None,
         Some("Test".to_string()),
+        None,
     )
     .await?;
 
@@ -304,6 +514,7 @@ async fn test_delete_item_by_id() -> Result<()> {
         &None,
         None,
         Some("Test".to_string()),
+        None,
     )
     .await?;
 
@@ -318,6 +529,37 @@ async fn test_delete_item_by_id() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_missing_targets_are_invalid_input() -> Result<()> {
+    let app = setup_test_app().await?;
+
+    // Returned rather than exiting, so that the pools get closed first
+    let is_invalid_input =
+        |result: Result<()>| result.is_err_and(|e| e.downcast_ref::<InvalidInput>().is_some());
+    assert!(is_invalid_input(delete_item(&app, 42, &None).await));
+    assert!(is_invalid_input(
+        add_item(
+            &app,
+            "Milk".to_string(),
+            &None,
+            None,
+            Some("Missing".to_string()),
+            None,
+        )
+        .await
+    ));
+    assert!(is_invalid_input(
+        add_list(&app, "Chores".to_string(), &Some("missing".to_string())).await
+    ));
+    assert!(is_invalid_input(reorder_item(&app, 42, 0, &None).await));
+
+    // The app can still be used afterwards
+    add_list(&app, "Chores".to_string(), &None).await?;
+    assert_eq!(TodoList::get_all(&app.pool).await?.len(), 1);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delete_item_keeps_others() -> Result<()> {
     let app = setup_test_app().await?;
@@ -330,6 +572,7 @@ async fn test_delete_item_keeps_others() -> Result<()> {
             &None,
             None,
             Some("Test".to_string()),
+            None,
         )
         .await?;
     }
@@ -362,6 +605,7 @@ async fn test_toggle_done_item() -> Result<()> {
         &None,
         None,
         Some("Test".to_string()),
+        None,
     )
     .await?;
 
@@ -394,6 +638,7 @@ async fn test_toggle_done_multiple_times() -> Result<()> {
         &None,
         None,
         Some("Test".to_string()),
+        None,
     )
     .await?;
 
@@ -418,7 +663,15 @@ async fn test_add_item_with_empty_name() -> Result<()> {
     let app = setup_test_app().await?;
 
     add_list(&app, "Test".to_string(), &None).await?;
-    add_item(&app, "".to_string(), &None, None, Some("Test".to_string())).await?;
+    add_item(
+        &app,
+        "".to_string(),
+        &None,
+        None,
+        Some("Test".to_string()),
+        None,
+    )
+    .await?;
 
     let lists = TodoList::get_all(&app.pool).await?;
     let items = lists[0].get_all_items(&app.pool).await?;
@@ -441,6 +694,7 @@ async fn test_add_item_with_very_long_name() -> Result<()> {
         &None,
         None,
         Some("Test".to_string()),
+        None,
     )
     .await?;
 
@@ -462,6 +716,7 @@ async fn test_delete_list_with_items() -> Result<()> {
         &None,
         None,
         Some("To Delete".to_string()),
+        None,
     )
     .await?;
     add_item(
@@ -470,6 +725,7 @@ async fn test_delete_list_with_items() -> Result<()> {
         &None,
         None,
         Some("To Delete".to_string()),
+        None,
     )
     .await?;
 
@@ -483,6 +739,35 @@ async fn test_delete_list_with_items() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_archive_and_unarchive_list() -> Result<()> {
+    let app = setup_test_app().await?;
+
+    add_list(&app, "Old Project".to_string(), &None).await?;
+    add_item(
+        &app,
+        "Item 1".to_string(),
+        &None,
+        None,
+        Some("Old Project".to_string()),
+        None,
+    )
+    .await?;
+
+    archive_list(&app, Some("Old Project".to_string()), None, &None).await?;
+    assert_eq!(TodoList::get_all(&app.pool).await?.len(), 0);
+    assert_eq!(TodoList::get_archived(&app.pool).await?.len(), 1);
+
+    // Archived lists are restored by name, with their items
+    unarchive_list(&app, Some("Old Project".to_string()), None, &None).await?;
+    let lists = TodoList::get_all(&app.pool).await?;
+    assert_eq!(lists.len(), 1);
+    assert_eq!(lists[0].get_all_items(&app.pool).await?.len(), 1);
+    assert!(TodoList::get_archived(&app.pool).await?.is_empty());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_multiple_lists_with_items() -> Result<()> {
     let app = setup_test_app().await?;
@@ -496,6 +781,7 @@ async fn test_multiple_lists_with_items() -> Result<()> {
         &None,
         None,
         Some("List 1".to_string()),
+        None,
     )
     .await?;
     add_item(
@@ -504,6 +790,7 @@ async fn test_multiple_lists_with_items() -> Result<()> {
         &None,
         None,
         Some("List 1".to_string()),
+        None,
     )
     .await?;
     add_item(
@@ -512,6 +799,7 @@ async fn test_multiple_lists_with_items() -> Result<()> {
         &None,
         None,
         Some("List 2".to_string()),
+        None,
     )
     .await?;
 
@@ -538,6 +826,7 @@ async fn test_list_isolation() -> Result<()> {
         &None,
         None,
         Some("List A".to_string()),
+        None,
     )
     .await?;
     add_item(
@@ -546,6 +835,7 @@ async fn test_list_isolation() -> Result<()> {
         &None,
         None,
         Some("List B".to_string()),
+        None,
     )
     .await?;
 
@@ -581,6 +871,7 @@ async fn test_complex_workflow() -> Result<()> {
             &None,
             None,
             Some("Shopping".to_string()),
+            None,
         )
         .await?;
     }
@@ -618,6 +909,7 @@ async fn test_multiple_operations_on_same_item() -> Result<()> {
         &None,
         None,
         Some("Test".to_string()),
+        None,
     )
     .await?;
 
@@ -648,3 +940,125 @@ async fn test_empty_database_operations() -> Result<()> {
     assert_eq!(TodoList::get_all(&app.pool).await?.len(), 0);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_clear_done_items() -> Result<()> {
+    let app = setup_test_app().await?;
+
+    add_list(&app, "Errands".to_string(), &None).await?;
+    add_list(&app, "Other".to_string(), &None).await?;
+    for (name, list) in [("Post", "Errands"), ("Bank", "Errands"), ("Gym", "Other")] {
+        add_item(
+            &app,
+            name.to_string(),
+            &None,
+            None,
+            Some(list.to_string()),
+            None,
+        )
+        .await?;
+    }
+
+    // Complete one item in each list
+    let lists = TodoList::get_all(&app.pool).await?;
+    let errands = lists[0].get_all_items(&app.pool).await?;
+    let other = lists[1].get_all_items(&app.pool).await?;
+    toggle_done_item(&app, errands[0].id, &None).await?;
+    toggle_done_item(&app, other[0].id, &None).await?;
+
+    clear_done_items(&app, &None, None, Some("Errands".to_string())).await?;
+
+    // Only the completed item of the targeted list is gone
+    let errands = lists[0].get_all_items(&app.pool).await?;
+    assert_eq!(errands.len(), 1);
+    assert_eq!(errands[0].name, "Bank");
+    assert_eq!(lists[1].get_all_items(&app.pool).await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_restore_and_empty_trash() -> Result<()> {
+    let app = setup_test_app().await?;
+
+    add_list(&app, "Groceries".to_string(), &None).await?;
+    add_item(
+        &app,
+        "Milk".to_string(),
+        &None,
+        None,
+        Some("Groceries".to_string()),
+        None,
+    )
+    .await?;
+    let list_id = TodoList::get_all(&app.pool).await?[0].id;
+    let item_id = TodoItem::get_by_list_id(&app.pool, list_id).await?[0].id;
+
+    // Deleting moves the item and the list to the trash
+    delete_item(&app, item_id, &None).await?;
+    delete_list(&app, None, Some(list_id), &None).await?;
+    assert!(TodoList::get_all(&app.pool).await?.is_empty());
+    assert_eq!(TrashEntry::get_all(&app.pool).await?.len(), 1);
+
+    restore_from_trash(&app, Some(list_id), None, &None).await?;
+    assert_eq!(TodoList::get_all(&app.pool).await?.len(), 1);
+    assert!(TodoItem::get_by_id(&app.pool, item_id).await?.is_none());
+
+    restore_from_trash(&app, None, Some(item_id), &None).await?;
+    assert!(TodoItem::get_by_id(&app.pool, item_id).await?.is_some());
+
+    // Emptying the trash makes deletions permanent
+    delete_list(&app, None, Some(list_id), &None).await?;
+    empty_trash(&app, &None).await?;
+    assert!(TrashEntry::get_all(&app.pool).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_add_db_rejects_empty_file() -> Result<()> {
+    let app = setup_test_app().await?;
+
+    // An SQLite file without any tables yet
+    let db_path = setup_test_dir()?.join("empty.db");
+    let pool = get_db_pool(&format!("sqlite:{}", db_path.display())).await?;
+    pool.close().await;
+
+    let error = add_db(app, None, Some(db_path.display().to_string()))
+        .await
+        .expect_err("empty files are not judo databases");
+    assert!(format!("{:#}", error).contains("Not a judo database"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_list_ids_follow_the_archived_flag() -> Result<()> {
+    let app = setup_test_app().await?;
+
+    add_list(&app, "Old Project".to_string(), &None).await?;
+    let list_id = TodoList::get_all(&app.pool).await?[0].id;
+
+    // An active list can't be unarchived by ID
+    let error = unarchive_list(&app, None, Some(list_id), &None)
+        .await
+        .unwrap_err();
+    assert!(error.downcast_ref::<InvalidInput>().is_some());
+
+    // And an archived one is out of reach of the other list commands
+    archive_list(&app, None, Some(list_id), &None).await?;
+    let error = add_item(&app, "Item".to_string(), &None, Some(list_id), None, None)
+        .await
+        .unwrap_err();
+    assert!(error.downcast_ref::<InvalidInput>().is_some());
+    assert!(
+        archive_list(&app, None, Some(list_id), &None)
+            .await
+            .is_err()
+    );
+
+    unarchive_list(&app, None, Some(list_id), &None).await?;
+    assert_eq!(TodoList::get_all(&app.pool).await?.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::helpers::db::setup_test_db;
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, Utc};
+use judo::db::models::{AgendaBucket, ItemWithList, NewTodoItem, NewTodoList, TodoItem, TodoList};
+
+#[test]
+fn test_agenda_bucket_for_date() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    let day = |offset: i64| today + Duration::days(offset);
+
+    assert_eq!(
+        AgendaBucket::for_date(day(-3), today),
+        AgendaBucket::Overdue
+    );
+    assert_eq!(
+        AgendaBucket::for_date(day(-1), today),
+        AgendaBucket::Overdue
+    );
+    assert_eq!(AgendaBucket::for_date(today, today), AgendaBucket::Today);
+    assert_eq!(
+        AgendaBucket::for_date(day(1), today),
+        AgendaBucket::Tomorrow
+    );
+    assert_eq!(
+        AgendaBucket::for_date(day(2), today),
+        AgendaBucket::ThisWeek
+    );
+    assert_eq!(
+        AgendaBucket::for_date(day(6), today),
+        AgendaBucket::ThisWeek
+    );
+    assert_eq!(AgendaBucket::for_date(day(7), today), AgendaBucket::Later);
+}
+
+#[tokio::test]
+async fn test_agenda_entries() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    let work = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+    let mut archived = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Old".to_string(),
+        },
+    )
+    .await?;
+
+    let specs = [
+        (work.id, "Report", Some(Utc::now() + Duration::days(10))),
+        (work.id, "Invoice", Some(Utc::now() - Duration::days(2))),
+        (work.id, "Someday", None),
+        (work.id, "Finished", Some(Utc::now())),
+        (archived.id, "Forgotten", Some(Utc::now())),
+    ];
+    let mut items = Vec::new();
+    for (list_id, name, due_date) in specs {
+        items.push(
+            TodoItem::create(
+                &pool,
+                NewTodoItem {
+                    list_id,
+                    name: name.to_string(),
+                    priority: None,
+                    due_date,
+                },
+            )
+            .await?,
+        );
+    }
+    items[3].toggle_done(&pool).await?;
+    archived.archive(&pool).await?;
+
+    // Only pending items with a due date in active lists, earliest first
+    let entries = ItemWithList::get_agenda(&pool).await?;
+    let names: Vec<_> = entries.iter().map(|e| e.item.name.as_str()).collect();
+    assert_eq!(names, ["Invoice", "Report"]);
+    assert!(entries.iter().all(|e| e.list_name == "Work"));
+    assert_eq!(entries[0].bucket(), AgendaBucket::Overdue);
+    assert_eq!(entries[1].bucket(), AgendaBucket::Later);
+
+    // Deleted items leave the agenda
+    items.remove(1).delete(&pool).await?;
+    assert_eq!(ItemWithList::get_agenda(&pool).await?.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use crate::helpers::db::create_item;
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use judo::db::config::{JournalMode, SqliteSettings, Synchronous};
+use judo::db::connections::{init_db, init_db_with};
+use judo::db::models::{NewSmartList, NewTodoList, SmartList, TodoItem, TodoList, UIList};
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+fn connection_str(file: &str) -> Result<String> {
+    Ok(format!("sqlite:{}", setup_test_dir()?.join(file).display()))
+}
+
+/// Hold the write lock of a database for a while, like a slow writer in another process
+async fn hold_write_lock(pool: SqlitePool, duration: Duration) -> Result<()> {
+    let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
+    tokio::time::sleep(duration).await;
+    sqlx::query("UPDATE todo_lists SET name = name")
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_connections_use_the_configured_settings() -> Result<()> {
+    let pool = init_db(&connection_str("defaults.db")?).await?;
+    let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+        .fetch_one(&pool)
+        .await?;
+    let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+        .fetch_one(&pool)
+        .await?;
+    let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+        .fetch_one(&pool)
+        .await?;
+    let synchronous: i64 = sqlx::query_scalar("PRAGMA synchronous")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(journal_mode, "wal");
+    assert_eq!(busy_timeout, 5000);
+    assert_eq!(foreign_keys, 1);
+    assert_eq!(synchronous, 1);
+
+    let settings = SqliteSettings {
+        journal_mode: Some(JournalMode::Delete),
+        busy_timeout_ms: Some(250),
+        foreign_keys: Some(false),
+        synchronous: Some(Synchronous::Full),
+    };
+    let pool = init_db_with(&connection_str("custom.db")?, &settings).await?;
+    let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+        .fetch_one(&pool)
+        .await?;
+    let busy_timeout: i64 = sqlx::query_scalar("PRAGMA busy_timeout")
+        .fetch_one(&pool)
+        .await?;
+    let foreign_keys: i64 = sqlx::query_scalar("PRAGMA foreign_keys")
+        .fetch_one(&pool)
+        .await?;
+    let synchronous: i64 = sqlx::query_scalar("PRAGMA synchronous")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(journal_mode, "delete");
+    assert_eq!(busy_timeout, 250);
+    assert_eq!(foreign_keys, 0);
+    assert_eq!(synchronous, 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_writers_wait_for_the_lock() -> Result<()> {
+    let connection_str = connection_str("locked.db")?;
+    let holder = init_db(&connection_str).await?;
+    let list = TodoList::create(
+        &holder,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+
+    // A writer waits for the lock to be released rather than failing
+    let writer = init_db(&connection_str).await?;
+    let held = tokio::spawn(hold_write_lock(holder.clone(), Duration::from_millis(300)));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    create_item(&writer, list.id, "Waited").await?;
+    held.await??;
+
+    // Without a busy timeout, the same write gives up straight away
+    let impatient = init_db_with(
+        &connection_str,
+        &SqliteSettings {
+            busy_timeout_ms: Some(0),
+            ..Default::default()
+        },
+    )
+    .await?;
+    let held = tokio::spawn(hold_write_lock(holder.clone(), Duration::from_millis(300)));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let error = create_item(&impatient, list.id, "Gave up")
+        .await
+        .unwrap_err();
+    assert!(format!("{:#}", error).contains("locked"));
+    held.await??;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_writers_and_readers_on_one_file() -> Result<()> {
+    // Separate pools on one file, like scripts running the CLI next to the TUI
+    let connection_str = connection_str("shared.db")?;
+    let pool = init_db(&connection_str).await?;
+    let list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+
+    let mut writers = Vec::new();
+    for writer in 0..4 {
+        let connection_str = connection_str.clone();
+        let list_id = list.id;
+        writers.push(tokio::spawn(async move {
+            let pool = init_db(&connection_str).await?;
+            for i in 0..15 {
+                let mut item = create_item(&pool, list_id, &format!("{}-{}", writer, i)).await?;
+                item.toggle_done(&pool).await?;
+                item.update_name(&pool, format!("{}-{} (renamed)", writer, i))
+                    .await?;
+                if i % 3 == 0 {
+                    item.delete(&pool).await?;
+                }
+            }
+            anyhow::Ok(())
+        }));
+    }
+
+    // Readers keep going while the writers work
+    let reader = {
+        let connection_str = connection_str.clone();
+        tokio::spawn(async move {
+            let pool = init_db(&connection_str).await?;
+            for _ in 0..20 {
+                UIList::get_all(&pool).await?;
+                TodoList::count_items(&pool).await?;
+            }
+            anyhow::Ok(())
+        })
+    };
+
+    for writer in writers {
+        writer.await??;
+    }
+    reader.await??;
+
+    let items = TodoItem::get_by_list_id(&pool, list.id).await?;
+    assert_eq!(items.len(), 4 * 10);
+    assert!(
+        items
+            .iter()
+            .all(|i| i.is_done && i.name.ends_with("(renamed)"))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_smart_list_creates_keep_orderings_unique() -> Result<()> {
+    let connection_str = connection_str("smart.db")?;
+    let pool = init_db(&connection_str).await?;
+
+    let mut writers = Vec::new();
+    for writer in 0..4 {
+        let connection_str = connection_str.clone();
+        writers.push(tokio::spawn(async move {
+            let pool = init_db(&connection_str).await?;
+            for i in 0..5 {
+                SmartList::create(
+                    &pool,
+                    NewSmartList {
+                        name: format!("{}-{}", writer, i),
+                        min_priority: None,
+                        due_within_days: None,
+                        is_done: None,
+                        tag: None,
+                        text: None,
+                    },
+                )
+                .await?;
+            }
+            anyhow::Ok(())
+        }));
+    }
+    for writer in writers {
+        writer.await??;
+    }
+
+    let mut orderings: Vec<i64> = SmartList::get_all(&pool)
+        .await?
+        .iter()
+        .map(|smart_list| smart_list.ordering)
+        .collect();
+    orderings.dedup();
+    assert_eq!(orderings, (1..=20).collect::<Vec<i64>>());
+
+    Ok(())
+}
@@ -0,0 +1,289 @@
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use judo::db::config::{Config, DBConfig, JournalMode, SqliteSettings, Synchronous, resolve_path};
+use judo::db::theme::NamedTheme;
+use ratatui::style::{Color, Modifier};
+use std::time::Duration;
+
+fn test_config() -> Config {
+    Config {
+        default: "dojo".to_string(),
+        dbs: vec![
+            DBConfig {
+                name: "dojo".to_string(),
+                connection_str: "sqlite:/tmp/dojo.db".to_string(),
+                sqlite: Default::default(),
+            },
+            DBConfig {
+                name: "work".to_string(),
+                connection_str: "sqlite:/tmp/work.db".to_string(),
+                sqlite: Default::default(),
+            },
+        ],
+        colours: Default::default(),
+        remember_session: true,
+        trash_retention_days: 30,
+        session: None,
+    }
+}
+
+#[test]
+fn test_resolve_absolute_path() -> Result<()> {
+    let path = resolve_path("/tmp/todo.db")?;
+    assert_eq!(path.to_str(), Some("/tmp/todo.db"));
+    Ok(())
+}
+
+#[test]
+fn test_resolve_relative_path() -> Result<()> {
+    let path = resolve_path("./todo.db")?;
+    assert!(path.is_absolute());
+    assert!(path.starts_with(std::env::current_dir()?));
+    assert!(path.ends_with("todo.db"));
+    Ok(())
+}
+
+#[test]
+fn test_resolve_home_path() -> Result<()> {
+    let home = dirs::home_dir().expect("Home directory should exist");
+    assert_eq!(resolve_path("~")?, home);
+    assert_eq!(resolve_path("~/todo.db")?, home.join("todo.db"));
+    Ok(())
+}
+
+#[test]
+fn test_read_creates_missing_config() -> Result<()> {
+    let path = setup_test_dir()?.join("nested").join("judo.toml");
+
+    let config = Config::read(&path)?;
+
+    assert!(path.is_file());
+    assert_eq!(config.default, "dojo");
+    Ok(())
+}
+
+#[test]
+fn test_use_db_overrides_default_for_session() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    let mut config = test_config();
+
+    config.use_db("work".to_string())?;
+    assert_eq!(config.get_default()?.name, "work");
+
+    // The override is not persisted
+    config.write(&path)?;
+    assert_eq!(Config::read(&path)?.default, "dojo");
+    Ok(())
+}
+
+#[test]
+fn test_use_unknown_db() {
+    let mut config = test_config();
+    assert!(config.use_db("missing".to_string()).is_err());
+    assert_eq!(config.default, "dojo");
+}
+
+#[test]
+fn test_set_default_during_session_is_persisted() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    let mut config = test_config();
+
+    config.use_db("work".to_string())?;
+    config.set_default("work".to_string());
+
+    config.write(&path)?;
+    assert_eq!(Config::read(&path)?.default, "work");
+    Ok(())
+}
+
+#[test]
+fn test_sqlite_settings_per_database() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(
+        &path,
+        r#"
+default = "dojo"
+
+[[dbs]]
+name = "dojo"
+connection_str = "sqlite:/tmp/dojo.db"
+
+[[dbs]]
+name = "work"
+connection_str = "sqlite:/tmp/work.db"
+journal_mode = "delete"
+busy_timeout_ms = 250
+foreign_keys = false
+synchronous = "full"
+"#,
+    )?;
+
+    let config = Config::read(&path)?;
+    let dojo = config.clone().get_db_by_name("dojo".to_string())?;
+    assert_eq!(dojo.sqlite, SqliteSettings::default());
+    assert_eq!(dojo.sqlite.journal_mode(), JournalMode::Wal);
+    assert_eq!(dojo.sqlite.busy_timeout(), Duration::from_millis(5000));
+    assert!(dojo.sqlite.foreign_keys());
+    assert_eq!(dojo.sqlite.synchronous(), Synchronous::Normal);
+
+    let work = config.clone().get_db_by_name("work".to_string())?;
+    assert_eq!(work.sqlite.journal_mode(), JournalMode::Delete);
+    assert_eq!(work.sqlite.busy_timeout(), Duration::from_millis(250));
+    assert!(!work.sqlite.foreign_keys());
+    assert_eq!(work.sqlite.synchronous(), Synchronous::Full);
+
+    // Settings survive a write, and unset ones are not written out
+    config.write(&path)?;
+    let written = std::fs::read_to_string(&path)?;
+    assert_eq!(written.matches("journal_mode").count(), 1);
+    let work = Config::read(&path)?.get_db_by_name("work".to_string())?;
+    assert_eq!(work.sqlite.busy_timeout_ms, Some(250));
+    Ok(())
+}
+
+#[test]
+fn test_recreate_keeps_a_backup() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(&path, "default = \"dojo\"\n[[dbs]\nname = ")?;
+
+    let config = Config::recreate(&path)?;
+
+    assert_eq!(config.default, "dojo");
+    assert_eq!(Config::read(&path)?.default, "dojo");
+    assert_eq!(
+        std::fs::read_to_string(path.with_extension("toml.bak"))?,
+        "default = \"dojo\"\n[[dbs]\nname = "
+    );
+    Ok(())
+}
+
+#[test]
+fn test_recreate_twice_keeps_both_backups() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(&path, "default = \"dojo\"\n[[dbs]\nname = ")?;
+
+    Config::recreate(&path)?;
+    let recreated = std::fs::read_to_string(&path)?;
+    Config::recreate(&path)?;
+
+    // The original config is still in the first backup
+    assert_eq!(
+        std::fs::read_to_string(path.with_extension("toml.bak"))?,
+        "default = \"dojo\"\n[[dbs]\nname = "
+    );
+    assert_eq!(
+        std::fs::read_to_string(path.with_extension("toml.bak.1"))?,
+        recreated
+    );
+    assert!(path.exists());
+    Ok(())
+}
+
+#[test]
+fn test_invalid_colour_is_rejected_on_read() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(
+        &path,
+        "default = \"dojo\"\ndbs = []\n\n[colours]\nforeground = \"blurple\"\n",
+    )?;
+
+    let error = format!("{:#}", Config::read(&path).err().unwrap());
+    assert!(error.contains("invalid colour 'blurple'"));
+    assert!(error.contains("foreground = \"blurple\""));
+    Ok(())
+}
+
+#[test]
+fn test_named_themes() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(
+        &path,
+        "default = \"dojo\"\ndbs = []\n\n[colours]\ntheme = \"nord\"\nhighlight = \"LightRed\"\n",
+    )?;
+
+    // Colours set alongside the theme take precedence over it
+    let config = Config::read(&path)?;
+    assert_eq!(config.background(), NamedTheme::Nord.background());
+    assert_eq!(config.foreground(), NamedTheme::Nord.foreground());
+    assert_eq!(config.highlight(), Color::LightRed);
+
+    // And are written back as they were
+    config.write(&path)?;
+    assert!(std::fs::read_to_string(&path)?.contains("highlight = \"LightRed\""));
+
+    // Unknown themes say which ones there are
+    std::fs::write(
+        &path,
+        "default = \"dojo\"\ndbs = []\n\n[colours]\ntheme = \"dracula\"\n",
+    )?;
+    let error = format!("{:#}", Config::read(&path).err().unwrap());
+    assert!(error.contains("dracula"));
+    assert!(error.contains("gruvbox"));
+    Ok(())
+}
+
+#[test]
+fn test_explicit_colours_keep_working() -> Result<()> {
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(
+        &path,
+        "default = \"dojo\"\ndbs = []\n\n[colours]\nbackground = \"#000000\"\nforeground = \"white\"\nhighlight = \"208\"\n",
+    )?;
+
+    let config = Config::read(&path)?;
+    assert_eq!(config.background(), Color::Rgb(0, 0, 0));
+    assert_eq!(config.foreground(), Color::White);
+    assert_eq!(config.highlight(), Color::Indexed(208));
+
+    // New configs name their theme
+    let path = setup_test_dir()?.join("judo.toml");
+    let config = Config::read(&path)?;
+    assert!(std::fs::read_to_string(&path)?.contains("theme = \"judo\""));
+    assert_eq!(config.background(), NamedTheme::Judo.background());
+    Ok(())
+}
+
+#[test]
+fn test_element_styles() -> Result<()> {
+    // Unset elements are derived from the base colours
+    let config: Config = toml::from_str(
+        "default = \"dojo\"\ndbs = []\n\n[colours]\nforeground = \"white\"\nbackground = \"black\"\n",
+    )?;
+    let theme = &config.colours;
+    assert_eq!(theme.border_style().fg, Some(Color::White));
+    assert_eq!(theme.selected_style().fg, Some(Color::Black));
+    assert_eq!(theme.selected_style().bg, Some(Color::White));
+    assert!(
+        theme
+            .done_style()
+            .add_modifier
+            .contains(Modifier::CROSSED_OUT)
+    );
+
+    // Set ones replace what they name and keep the rest
+    let config: Config = toml::from_str(
+        "default = \"dojo\"\ndbs = []\n\n[colours]\nforeground = \"white\"\nselected = { bg = \"yellow\", bold = true }\n\n[colours.title]\nfg = \"cyan\"\nunderlined = true\n",
+    )?;
+    let theme = &config.colours;
+    assert_eq!(theme.selected_style().bg, Some(Color::Yellow));
+    assert_eq!(theme.selected_style().fg, Some(theme.background()));
+    assert!(theme.selected_style().add_modifier.contains(Modifier::BOLD));
+    assert_eq!(theme.title_style().fg, Some(Color::Cyan));
+    assert!(
+        theme
+            .title_style()
+            .add_modifier
+            .contains(Modifier::UNDERLINED)
+    );
+    assert_eq!(theme.border_style().fg, Some(Color::White));
+
+    // Typos are caught on read
+    let path = setup_test_dir()?.join("judo.toml");
+    std::fs::write(
+        &path,
+        "default = \"dojo\"\ndbs = []\n\n[colours]\noverdue = { bolt = true }\n",
+    )?;
+    let error = format!("{:#}", Config::read(&path).err().unwrap());
+    assert!(error.contains("bolt"));
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+use judo::db::dates::{parse_date, parse_due_date, start_of_day};
+
+/// Sunday, October 18th 2026
+fn today() -> NaiveDate {
+    date(2026, 10, 18)
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+fn parsed(input: &str) -> NaiveDate {
+    parse_date(input, today()).unwrap_or_else(|e| panic!("{}", e))
+}
+
+#[test]
+fn test_parse_date_named_days() {
+    assert_eq!(parsed("today"), date(2026, 10, 18));
+    assert_eq!(parsed("tod"), date(2026, 10, 18));
+    assert_eq!(parsed("tomorrow"), date(2026, 10, 19));
+    assert_eq!(parsed("tmr"), date(2026, 10, 19));
+    assert_eq!(parsed("tmrw"), date(2026, 10, 19));
+    assert_eq!(parsed("yesterday"), date(2026, 10, 17));
+}
+
+#[test]
+fn test_parse_date_ignores_case_and_spaces() {
+    assert_eq!(parsed("  Tomorrow "), date(2026, 10, 19));
+    assert_eq!(parsed("NEXT   Fri"), date(2026, 10, 23));
+    assert_eq!(parsed("In 3 Days"), date(2026, 10, 21));
+    assert_eq!(parsed("OCT 20"), date(2026, 10, 20));
+}
+
+#[test]
+fn test_parse_date_offsets() {
+    assert_eq!(parsed("in 3 days"), date(2026, 10, 21));
+    assert_eq!(parsed("3 days"), date(2026, 10, 21));
+    assert_eq!(parsed("in 1 day"), date(2026, 10, 19));
+    assert_eq!(parsed("in 0 days"), date(2026, 10, 18));
+    assert_eq!(parsed("in a week"), date(2026, 10, 25));
+    assert_eq!(parsed("in one week"), date(2026, 10, 25));
+    assert_eq!(parsed("2 weeks"), date(2026, 11, 1));
+    assert_eq!(parsed("in 1 month"), date(2026, 11, 18));
+    assert_eq!(parsed("in a month"), date(2026, 11, 18));
+    assert_eq!(parsed("3 months"), date(2027, 1, 18));
+
+    // Compact forms
+    assert_eq!(parsed("3d"), date(2026, 10, 21));
+    assert_eq!(parsed("2w"), date(2026, 11, 1));
+    assert_eq!(parsed("1m"), date(2026, 11, 18));
+    assert_eq!(parsed("in 10d"), date(2026, 10, 28));
+    assert_eq!(parsed("0d"), date(2026, 10, 18));
+}
+
+#[test]
+fn test_parse_date_month_offsets_clamp_to_month_end() {
+    let january_31st = date(2026, 1, 31);
+    assert_eq!(
+        parse_date("in 1 month", january_31st),
+        Ok(date(2026, 2, 28))
+    );
+    assert_eq!(parse_date("1m", date(2028, 1, 31)), Ok(date(2028, 2, 29)));
+}
+
+#[test]
+fn test_parse_date_weekdays() {
+    // The next one after today, a week away for today's weekday
+    assert_eq!(parsed("mon"), date(2026, 10, 19));
+    assert_eq!(parsed("tue"), date(2026, 10, 20));
+    assert_eq!(parsed("tues"), date(2026, 10, 20));
+    assert_eq!(parsed("wednesday"), date(2026, 10, 21));
+    assert_eq!(parsed("thu"), date(2026, 10, 22));
+    assert_eq!(parsed("thur"), date(2026, 10, 22));
+    assert_eq!(parsed("thurs"), date(2026, 10, 22));
+    assert_eq!(parsed("fri"), date(2026, 10, 23));
+    assert_eq!(parsed("friday"), date(2026, 10, 23));
+    assert_eq!(parsed("this fri"), date(2026, 10, 23));
+    assert_eq!(parsed("sat"), date(2026, 10, 24));
+    assert_eq!(parsed("sun"), date(2026, 10, 25));
+
+    // From a Wednesday, earlier weekdays come next week
+    let wednesday = date(2026, 10, 21);
+    assert_eq!(parse_date("mon", wednesday), Ok(date(2026, 10, 26)));
+    assert_eq!(parse_date("wed", wednesday), Ok(date(2026, 10, 28)));
+    assert_eq!(parse_date("fri", wednesday), Ok(date(2026, 10, 23)));
+}
+
+#[test]
+fn test_parse_date_next_weekday() {
+    // Sunday ends the week, so next week starts tomorrow
+    assert_eq!(parsed("next mon"), date(2026, 10, 19));
+    assert_eq!(parsed("next fri"), date(2026, 10, 23));
+    assert_eq!(parsed("next sunday"), date(2026, 10, 25));
+
+    // From a Monday, next Friday is in the following week
+    let monday = date(2026, 10, 19);
+    assert_eq!(parse_date("fri", monday), Ok(date(2026, 10, 23)));
+    assert_eq!(parse_date("next fri", monday), Ok(date(2026, 10, 30)));
+    assert_eq!(parse_date("next mon", monday), Ok(date(2026, 10, 26)));
+}
+
+#[test]
+fn test_parse_date_periods() {
+    assert_eq!(parsed("eow"), date(2026, 10, 18));
+    assert_eq!(parsed("end of week"), date(2026, 10, 18));
+    assert_eq!(
+        parse_date("eow", date(2026, 10, 19)),
+        Ok(date(2026, 10, 25))
+    );
+    assert_eq!(parsed("eom"), date(2026, 10, 31));
+    assert_eq!(parsed("end of month"), date(2026, 10, 31));
+    assert_eq!(parse_date("eom", date(2028, 2, 3)), Ok(date(2028, 2, 29)));
+    assert_eq!(parse_date("eom", date(2026, 12, 5)), Ok(date(2026, 12, 31)));
+    assert_eq!(parsed("eoy"), date(2026, 12, 31));
+    assert_eq!(parsed("end of year"), date(2026, 12, 31));
+
+    assert_eq!(parsed("next week"), date(2026, 10, 19));
+    assert_eq!(
+        parse_date("next week", date(2026, 10, 21)),
+        Ok(date(2026, 10, 26))
+    );
+    assert_eq!(parsed("next month"), date(2026, 11, 1));
+    assert_eq!(
+        parse_date("next month", date(2026, 12, 31)),
+        Ok(date(2027, 1, 1))
+    );
+    assert_eq!(parsed("next year"), date(2027, 1, 1));
+}
+
+#[test]
+fn test_parse_date_month_and_day() {
+    assert_eq!(parsed("oct 20"), date(2026, 10, 20));
+    assert_eq!(parsed("20 oct"), date(2026, 10, 20));
+    assert_eq!(parsed("october 20"), date(2026, 10, 20));
+    assert_eq!(parsed("oct 20th"), date(2026, 10, 20));
+    assert_eq!(parsed("1st nov"), date(2026, 11, 1));
+    assert_eq!(parsed("nov 2nd"), date(2026, 11, 2));
+    assert_eq!(parsed("nov 3rd"), date(2026, 11, 3));
+    assert_eq!(parsed("sept 9"), date(2027, 9, 9));
+    assert_eq!(parsed("sep 9"), date(2027, 9, 9));
+
+    // Today counts, earlier days roll over to next year
+    assert_eq!(parsed("oct 18"), date(2026, 10, 18));
+    assert_eq!(parsed("oct 17"), date(2027, 10, 17));
+    assert_eq!(parsed("jan 1"), date(2027, 1, 1));
+
+    // February 29th waits for the next leap year
+    assert_eq!(parsed("feb 29"), date(2028, 2, 29));
+
+    // An explicit year
+    assert_eq!(parsed("oct 20 2027"), date(2027, 10, 20));
+    assert_eq!(parsed("20 oct 2025"), date(2025, 10, 20));
+}
+
+#[test]
+fn test_parse_date_iso() {
+    assert_eq!(parsed("2026-10-20"), date(2026, 10, 20));
+    assert_eq!(parsed("2025-01-01"), date(2025, 1, 1));
+}
+
+#[test]
+fn test_parse_date_errors() {
+    for input in [
+        "",
+        "someday",
+        "next",
+        "next someday",
+        "in",
+        "in days",
+        "in 3",
+        "3 fortnights",
+        "3x",
+        "d",
+        "ma 5",
+        "oct",
+        "oct 32",
+        "oct 0",
+        "feb 30 2026",
+        "oct nov",
+        "20 21",
+        "2026-13-01",
+        "2026-02-30",
+        "end of",
+        "in 3 days please",
+    ] {
+        let error = parse_date(input, today()).unwrap_err();
+        assert!(
+            error.starts_with(&format!("invalid date '{}'", input.trim())),
+            "{}: {}",
+            input,
+            error
+        );
+    }
+}
+
+#[test]
+fn test_start_of_day() {
+    let expected = Local
+        .with_ymd_and_hms(2026, 10, 20, 0, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc);
+    assert_eq!(start_of_day(date(2026, 10, 20)), expected);
+}
+
+#[test]
+fn test_parse_due_date() {
+    assert_eq!(
+        parse_due_date("2026-10-20"),
+        Ok(start_of_day(date(2026, 10, 20)))
+    );
+    let local_today = Local::now().date_naive();
+    assert_eq!(parse_due_date("today"), Ok(start_of_day(local_today)));
+    assert!(parse_due_date("someday").is_err());
+}
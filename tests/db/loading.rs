@@ -0,0 +1,111 @@
+use crate::helpers::db::setup_test_db;
+use anyhow::Result;
+use chrono::Utc;
+use judo::db::models::{NewTodoList, TodoItem, TodoList, UIList};
+use judo::db::query::Query;
+use judo::ui::components::ListsComponent;
+use sqlx::SqlitePool;
+
+const LISTS: i64 = 40;
+const ITEMS_PER_LIST: i64 = 250;
+
+/// Fill the database with lists of generated items, every tenth one done
+async fn generate(pool: &SqlitePool) -> Result<()> {
+    let now = Utc::now();
+    let mut tx = pool.begin().await?;
+    for l in 1..=LISTS {
+        sqlx::query(
+            "INSERT INTO todo_lists (id, name, ordering, created_at, updated_at) VALUES (?1, ?2, ?1, ?3, ?3)",
+        )
+        .bind(l)
+        .bind(format!("List {}", l))
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+        for i in 1..=ITEMS_PER_LIST {
+            sqlx::query(
+                "INSERT INTO todo_items (list_id, name, is_done, ordering, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?5)",
+            )
+            .bind(l)
+            .bind(format!("Item {}-{}", l, i))
+            .bind(i % 10 == 0)
+            .bind(ITEMS_PER_LIST - i + 1)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_lists_with_thousands_of_items() -> Result<()> {
+    let pool = setup_test_db().await?;
+    generate(&pool).await?;
+
+    // Leave out lists in the archive or the trash, and items in the trash
+    let mut archived = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Archived".to_string(),
+        },
+    )
+    .await?;
+    archived.archive(&pool).await?;
+    TodoItem::get_by_list_id(&pool, 1)
+        .await?
+        .remove(0)
+        .delete(&pool)
+        .await?;
+
+    let ui_lists = UIList::get_all(&pool).await?;
+
+    // The same as fetching each list on its own
+    for ui_list in &ui_lists {
+        let items = TodoItem::get_by_list_id(&pool, ui_list.list.id).await?;
+        let names: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        let loaded: Vec<&str> = ui_list.items.iter().map(|i| i.item.name.as_str()).collect();
+        assert_eq!(loaded, names);
+    }
+
+    assert_eq!(ui_lists.len(), LISTS as usize);
+    assert_eq!(ui_lists[0].items.len(), ITEMS_PER_LIST as usize - 1);
+    assert_eq!(ui_lists[1].items.len(), ITEMS_PER_LIST as usize);
+    assert_eq!(
+        ui_lists[1].items[0].item.name,
+        format!("Item 2-{}", ITEMS_PER_LIST)
+    );
+
+    // Counts come from a single query too
+    let counts = TodoList::count_items(&pool).await?;
+    assert_eq!(counts[&1], ITEMS_PER_LIST - 1);
+    assert_eq!(counts[&LISTS], ITEMS_PER_LIST);
+    assert!(!counts.contains_key(&archived.id));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_filter_lists_with_thousands_of_items() -> Result<()> {
+    let pool = setup_test_db().await?;
+    generate(&pool).await?;
+
+    let mut lists_component = ListsComponent::new();
+    lists_component.load_lists(&pool).await?;
+
+    let query: Query = "done".parse().map_err(anyhow::Error::msg)?;
+    lists_component.set_filter(Some(query), &pool).await?;
+
+    for ui_list in &lists_component.lists {
+        assert_eq!(ui_list.items.len(), (ITEMS_PER_LIST / 10) as usize);
+        assert!(ui_list.items.iter().all(|i| i.item.is_done));
+    }
+
+    // Clearing the filter brings every item back
+    lists_component.set_filter(None, &pool).await?;
+    let total: usize = lists_component.lists.iter().map(|l| l.items.len()).sum();
+    assert_eq!(total, (LISTS * ITEMS_PER_LIST) as usize);
+
+    Ok(())
+}
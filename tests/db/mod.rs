@@ -1,2 +1,13 @@
+pub mod agenda_ops;
+pub mod concurrency;
+pub mod config;
+pub mod dates;
+pub mod loading;
+pub mod ordering;
+pub mod project;
+pub mod query;
+pub mod quick_add;
+pub mod smart_list_ops;
 pub mod todo_item_ops;
 pub mod todo_list_ops;
+pub mod trash_ops;
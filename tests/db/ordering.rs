@@ -0,0 +1,327 @@
+use crate::helpers::db::{create_item, setup_test_db};
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use judo::db::connections::init_db;
+use judo::db::models::{NewTodoList, SortMode, TodoItem, TodoList};
+use sqlx::SqlitePool;
+
+async fn create_list(pool: &SqlitePool, name: &str) -> Result<TodoList> {
+    TodoList::create(
+        pool,
+        NewTodoList {
+            name: name.to_string(),
+        },
+    )
+    .await
+}
+
+/// Orderings of all items of a list, trash included, by id
+async fn item_orderings(pool: &SqlitePool, list_id: i64) -> Result<Vec<(i64, i64)>> {
+    Ok(
+        sqlx::query_as("SELECT id, ordering FROM todo_items WHERE list_id = ?1 ORDER BY id")
+            .bind(list_id)
+            .fetch_all(pool)
+            .await?,
+    )
+}
+
+async fn item_names(pool: &SqlitePool, list_id: i64) -> Result<Vec<String>> {
+    Ok(TodoItem::get_by_list_id(pool, list_id)
+        .await?
+        .into_iter()
+        .map(|item| item.name)
+        .collect())
+}
+
+#[tokio::test]
+async fn test_duplicate_orderings_are_rejected() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let list = create_list(&pool, "Work").await?;
+    let item = create_item(&pool, list.id, "First").await?;
+    let other_list = create_list(&pool, "Home").await?;
+
+    // Within a list, items can't share an ordering
+    let duplicate = sqlx::query(
+        "INSERT INTO todo_items (list_id, name, ordering, created_at, updated_at) VALUES (?1, 'Copy', ?2, '', '')",
+    )
+    .bind(list.id)
+    .bind(item.ordering)
+    .execute(&pool)
+    .await;
+    assert!(duplicate.is_err());
+
+    // Other lists are free to use it
+    create_item(&pool, other_list.id, "Elsewhere").await?;
+    assert_eq!(item_orderings(&pool, other_list.id).await?[0].1, 1);
+
+    // Lists can't share an ordering either
+    let duplicate = sqlx::query("UPDATE todo_lists SET ordering = ?1 WHERE id = ?2")
+        .bind(list.ordering)
+        .bind(other_list.id)
+        .execute(&pool)
+        .await;
+    assert!(duplicate.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_repair_renumbers_duplicate_orderings() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let work = create_list(&pool, "Work").await?;
+    let home = create_list(&pool, "Home").await?;
+    let mut ids = Vec::new();
+    for name in ["A", "B", "C", "D"] {
+        ids.push(create_item(&pool, work.id, name).await?.id);
+    }
+    let home_item = create_item(&pool, home.id, "E").await?;
+
+    // Recreate what racing writers used to leave behind
+    sqlx::raw_sql(
+        "DROP INDEX idx_todo_lists_ordering; DROP INDEX idx_todo_items_list_ordering;
+         CREATE INDEX idx_todo_lists_ordering ON todo_lists(ordering);
+         CREATE INDEX idx_todo_items_list_ordering ON todo_items(list_id, ordering);",
+    )
+    .execute(&pool)
+    .await?;
+    for (id, ordering) in ids.iter().zip([2, 2, 1, 7]) {
+        sqlx::query("UPDATE todo_items SET ordering = ?1 WHERE id = ?2")
+            .bind(ordering)
+            .bind(id)
+            .execute(&pool)
+            .await?;
+    }
+    sqlx::query("UPDATE todo_lists SET ordering = 5")
+        .execute(&pool)
+        .await?;
+
+    sqlx::raw_sql(include_str!(
+        "../../migrations/20261018000006_unique_orderings.sql"
+    ))
+    .execute(&pool)
+    .await?;
+
+    // Order kept, ties broken by id, each list numbered from 1
+    assert_eq!(
+        item_orderings(&pool, work.id).await?,
+        vec![(ids[0], 2), (ids[1], 3), (ids[2], 1), (ids[3], 4)]
+    );
+    assert_eq!(
+        item_orderings(&pool, home.id).await?,
+        vec![(home_item.id, 1)]
+    );
+    let lists = TodoList::get_all(&pool).await?;
+    assert_eq!(
+        lists.iter().map(|l| (l.id, l.ordering)).collect::<Vec<_>>(),
+        vec![(work.id, 1), (home.id, 2)]
+    );
+
+    // And the orderings are unique again
+    let duplicate = sqlx::query("UPDATE todo_items SET ordering = 1 WHERE id = ?1")
+        .bind(ids[0])
+        .execute(&pool)
+        .await;
+    assert!(duplicate.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_moves_use_the_stored_ordering() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let list = create_list(&pool, "Work").await?;
+    create_item(&pool, list.id, "A").await?;
+    create_item(&pool, list.id, "B").await?;
+    let mut stale = create_item(&pool, list.id, "C").await?;
+
+    // Someone else moves C up while this copy still has its old ordering
+    let mut fresh = TodoItem::get_by_id(&pool, stale.id).await?.unwrap();
+    fresh.move_up(&pool).await?;
+    assert_eq!(item_names(&pool, list.id).await?, vec!["A", "C", "B"]);
+
+    stale.move_up(&pool).await?;
+    assert_eq!(item_names(&pool, list.id).await?, vec!["C", "A", "B"]);
+    assert_eq!(stale.ordering, 1);
+
+    // Lists too
+    create_list(&pool, "First").await?;
+    let mut stale_list = create_list(&pool, "Second").await?;
+    let mut fresh_list = TodoList::get_by_id(&pool, stale_list.id).await?.unwrap();
+    fresh_list.move_up(&pool).await?;
+    stale_list.move_up(&pool).await?;
+    let names: Vec<String> = TodoList::get_all(&pool)
+        .await?
+        .into_iter()
+        .map(|l| l.name)
+        .collect();
+    assert_eq!(names, vec!["Second", "Work", "First"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rebaseline_with_items_in_trash() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let mut list = create_list(&pool, "Work").await?;
+    for name in ["Charlie", "Alpha", "Delta", "Bravo"] {
+        create_item(&pool, list.id, name).await?;
+    }
+    let trashed = TodoItem::get_by_list_id(&pool, list.id).await?.remove(2);
+    trashed.delete(&pool).await?;
+
+    list.set_sort_mode(&pool, SortMode::Alphabetical).await?;
+    list.rebaseline_ordering(&pool).await?;
+
+    assert_eq!(list.sort_mode, SortMode::Manual);
+    assert_eq!(
+        item_names(&pool, list.id).await?,
+        vec!["Alpha", "Bravo", "Charlie"]
+    );
+
+    // The item in the trash keeps its own ordering
+    let mut orderings: Vec<i64> = item_orderings(&pool, list.id)
+        .await?
+        .into_iter()
+        .map(|(_, ordering)| ordering)
+        .collect();
+    orderings.sort();
+    assert_eq!(orderings, vec![1, 2, 3, 4]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_item_to_position() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let list = create_list(&pool, "Work").await?;
+    let mut items = Vec::new();
+    for name in ["A", "B", "C", "D", "E"] {
+        items.push(create_item(&pool, list.id, name).await?);
+    }
+    let trashed = items.remove(3);
+    let (trashed_id, trashed_ordering) = (trashed.id, trashed.ordering);
+    trashed.delete(&pool).await?;
+
+    items[3].move_to_position(&pool, 0).await?;
+    assert_eq!(item_names(&pool, list.id).await?, vec!["E", "A", "B", "C"]);
+    assert_eq!(items[3].ordering, 1);
+
+    items[1].move_to_position(&pool, 2).await?;
+    assert_eq!(item_names(&pool, list.id).await?, vec!["E", "A", "B", "C"]);
+    items[0].move_to_position(&pool, 2).await?;
+    assert_eq!(item_names(&pool, list.id).await?, vec!["E", "B", "A", "C"]);
+
+    // Positions past the end move the item to the bottom
+    items[3].move_to_position(&pool, 99).await?;
+    assert_eq!(item_names(&pool, list.id).await?, vec!["B", "A", "C", "E"]);
+
+    // The item in the trash kept its ordering, and comes back where it was
+    let orderings = item_orderings(&pool, list.id).await?;
+    assert!(orderings.contains(&(trashed_id, trashed_ordering)));
+    sqlx::query("UPDATE todo_items SET deleted_at = NULL WHERE id = ?1")
+        .bind(trashed_id)
+        .execute(&pool)
+        .await?;
+    assert_eq!(
+        item_names(&pool, list.id).await?,
+        vec!["B", "A", "C", "D", "E"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_item_to_position_in_sorted_list() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let mut list = create_list(&pool, "Work").await?;
+    for name in ["Charlie", "Alpha", "Bravo"] {
+        create_item(&pool, list.id, name).await?;
+    }
+    list.set_sort_mode(&pool, SortMode::Alphabetical).await?;
+
+    // Positions follow the sorted order, which becomes the manual one
+    let mut charlie = TodoItem::get_by_list_id(&pool, list.id).await?.remove(0);
+    list.move_item_to_position(&pool, &mut charlie, 0).await?;
+    assert_eq!(list.sort_mode, SortMode::Manual);
+    assert_eq!(
+        item_names(&pool, list.id).await?,
+        vec!["Charlie", "Alpha", "Bravo"]
+    );
+    assert_eq!(charlie.ordering, 1);
+
+    // Already manual lists are moved in as they are
+    list.move_item_to_position(&pool, &mut charlie, 1).await?;
+    assert_eq!(
+        item_names(&pool, list.id).await?,
+        vec!["Alpha", "Charlie", "Bravo"]
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_list_to_position() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let mut lists = Vec::new();
+    for name in ["Work", "Home", "Archived", "Errands"] {
+        lists.push(create_list(&pool, name).await?);
+    }
+    let mut archived = lists.remove(2);
+    archived.archive(&pool).await?;
+    let archived_ordering = archived.ordering;
+
+    lists[2].move_to_position(&pool, 0).await?;
+    lists[0].move_to_position(&pool, usize::MAX).await?;
+    let names: Vec<String> = TodoList::get_all(&pool)
+        .await?
+        .into_iter()
+        .map(|l| l.name)
+        .collect();
+    assert_eq!(names, vec!["Errands", "Home", "Work"]);
+
+    // Archived lists are left where they were
+    let archived = TodoList::get_by_id(&pool, archived.id).await?.unwrap();
+    assert_eq!(archived.ordering, archived_ordering);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_writers_keep_orderings_unique() -> Result<()> {
+    // Separate pools on one file, like the CLI running next to the TUI
+    let path = setup_test_dir()?.join("race.db");
+    let connection_str = format!("sqlite:{}", path.display());
+    let pool = init_db(&connection_str).await?;
+    let list = create_list(&pool, "Work").await?;
+
+    let mut tasks = Vec::new();
+    for writer in 0..4 {
+        let connection_str = connection_str.clone();
+        let list_id = list.id;
+        tasks.push(tokio::spawn(async move {
+            let pool = init_db(&connection_str).await?;
+            for i in 0..10 {
+                let mut item = create_item(&pool, list_id, &format!("{}-{}", writer, i)).await?;
+                if i % 2 == 0 {
+                    item.move_up(&pool).await?;
+                } else {
+                    item.move_down(&pool).await?;
+                }
+            }
+            anyhow::Ok(())
+        }));
+    }
+    for task in tasks {
+        task.await??;
+    }
+
+    let mut orderings: Vec<i64> = item_orderings(&pool, list.id)
+        .await?
+        .into_iter()
+        .map(|(_, ordering)| ordering)
+        .collect();
+    orderings.sort();
+    assert_eq!(orderings, (1..=40).collect::<Vec<_>>());
+
+    Ok(())
+}
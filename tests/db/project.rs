@@ -0,0 +1,151 @@
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use judo::db::config::{Config, DBConfig};
+use judo::db::project::{PROJECT_DIR, PROJECT_FILE, discover_project_db, init_project};
+
+fn test_config() -> Config {
+    Config {
+        default: "dojo".to_string(),
+        dbs: vec![DBConfig {
+            name: "dojo".to_string(),
+            connection_str: "sqlite:/tmp/dojo.db".to_string(),
+            sqlite: Default::default(),
+        }],
+        colours: Default::default(),
+        remember_session: true,
+        trash_retention_days: 30,
+        session: None,
+    }
+}
+
+#[test]
+fn test_discover_without_project() -> Result<()> {
+    let root = setup_test_dir()?;
+
+    // The temp directory may itself live below a project, so only check the
+    // discovered database (if any) is not inside our test directory
+    if let Some(db) = discover_project_db(&root)? {
+        assert!(!db.connection_str.contains(root.to_str().unwrap()));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_discover_judo_dir_from_subdirectory() -> Result<()> {
+    let root = setup_test_dir()?;
+    std::fs::create_dir_all(root.join(PROJECT_DIR))?;
+    let nested = root.join("src").join("deeply").join("nested");
+    std::fs::create_dir_all(&nested)?;
+
+    let db = discover_project_db(&nested)?.expect("Project should be found");
+
+    assert_eq!(db.name, root.file_name().unwrap().to_string_lossy());
+    assert_eq!(
+        db.connection_str,
+        format!("sqlite:{}", root.join(".judo").join("judo.db").display())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_discover_judo_toml_with_custom_path() -> Result<()> {
+    let root = setup_test_dir()?;
+    std::fs::write(
+        root.join(PROJECT_FILE),
+        "name = \"website\"\npath = \"todo.db\"\n",
+    )?;
+
+    let db = discover_project_db(&root)?.expect("Project should be found");
+
+    assert_eq!(db.name, "website");
+    assert_eq!(
+        db.connection_str,
+        format!("sqlite:{}", root.join("todo.db").display())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_discover_invalid_judo_toml() -> Result<()> {
+    let root = setup_test_dir()?;
+    std::fs::write(root.join(PROJECT_FILE), "name = ")?;
+
+    assert!(discover_project_db(&root).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_init_project() -> Result<()> {
+    let root = setup_test_dir()?;
+
+    let db = init_project(&root, Some("repo".to_string()))?;
+    assert_eq!(db.name, "repo");
+    assert!(root.join(PROJECT_DIR).is_dir());
+    assert!(root.join(PROJECT_FILE).is_file());
+
+    // A second init in the same place must fail
+    assert!(init_project(&root, None).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_project_db_overrides_default_but_is_not_written() -> Result<()> {
+    let root = setup_test_dir()?;
+    let mut config = test_config();
+
+    config.use_project_db(DBConfig {
+        name: "repo".to_string(),
+        connection_str: "sqlite:/tmp/repo.db".to_string(),
+        sqlite: Default::default(),
+    });
+
+    assert_eq!(config.default, "repo");
+    assert_eq!(config.get_default()?.connection_str, "sqlite:/tmp/repo.db");
+    assert_eq!(config.dbs.len(), 2);
+
+    // Writing the config keeps judo.toml free of the project database
+    let path = root.join("judo.toml");
+    config.write(&path)?;
+    let on_disk: Config = toml::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(on_disk.default, "dojo");
+    assert_eq!(on_disk.dbs.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_project_db_reuses_configured_entry() -> Result<()> {
+    let mut config = test_config();
+
+    config.use_project_db(DBConfig {
+        name: "repo".to_string(),
+        connection_str: "sqlite:/tmp/dojo.db".to_string(),
+        sqlite: Default::default(),
+    });
+
+    assert_eq!(config.default, "dojo");
+    assert_eq!(config.dbs.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_project_db_name_clash() -> Result<()> {
+    let mut config = test_config();
+
+    config.use_project_db(DBConfig {
+        name: "dojo".to_string(),
+        connection_str: "sqlite:/tmp/other.db".to_string(),
+        sqlite: Default::default(),
+    });
+
+    assert_eq!(config.default, "dojo (project)");
+    assert_eq!(config.get_default()?.connection_str, "sqlite:/tmp/other.db");
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+use crate::helpers::db::setup_test_db;
+use anyhow::Result;
+use chrono::{Duration, Local, NaiveDate, TimeZone, Utc};
+use judo::db::models::{NewTodoItem, NewTodoList, Priority, TodoItem, TodoList};
+use judo::db::query::{Comparison, DueDate, Filter, Query, Term};
+
+fn term(negated: bool, filter: Filter) -> Term {
+    Term { negated, filter }
+}
+
+#[test]
+fn test_query_parsing() {
+    let query: Query = r#"priority:high due<7d !done list:work "big report" #home"#
+        .parse()
+        .unwrap();
+    assert_eq!(
+        query.terms,
+        vec![
+            term(false, Filter::Priority(Comparison::Eq, Priority::High)),
+            term(false, Filter::Due(Comparison::Lt, DueDate::Relative(7))),
+            term(true, Filter::Done),
+            term(false, Filter::List("work".to_string())),
+            term(false, Filter::Text("big report".to_string())),
+            term(false, Filter::Tag("home".to_string())),
+        ]
+    );
+
+    // Short keys, other comparisons and dates
+    let query: Query =
+        "p>=medium d>=2026-10-20 pending t:errands s:doing list:\"side projects\" due:none milk"
+            .parse()
+            .unwrap();
+    assert_eq!(
+        query.terms,
+        vec![
+            term(false, Filter::Priority(Comparison::Ge, Priority::Medium)),
+            term(
+                false,
+                Filter::Due(
+                    Comparison::Ge,
+                    DueDate::On(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap())
+                )
+            ),
+            term(true, Filter::Done),
+            term(false, Filter::Tag("errands".to_string())),
+            term(false, Filter::Status("doing".to_string())),
+            term(false, Filter::List("side projects".to_string())),
+            term(false, Filter::NoDue),
+            term(false, Filter::Text("milk".to_string())),
+        ]
+    );
+
+    // Relative dates
+    for (value, days) in [
+        ("today", 0),
+        ("tomorrow", 1),
+        ("yesterday", -1),
+        ("3d", 3),
+        ("2w", 14),
+        ("-2d", -2),
+    ] {
+        assert_eq!(value.parse::<DueDate>(), Ok(DueDate::Relative(days)));
+    }
+
+    // Dates in words are read again on each day
+    let due: DueDate = "EOW".parse().unwrap();
+    assert_eq!(due, DueDate::Words("eow".to_string()));
+    assert_eq!(due.to_string(), "eow");
+    let sunday = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    assert_eq!(due.resolve(sunday), sunday);
+    assert_eq!(
+        due.resolve(sunday + Duration::days(1)),
+        sunday + Duration::days(7)
+    );
+    let query: Query = "due<eow due:fri".parse().unwrap();
+    assert_eq!(query.to_string(), "due<eow due:fri");
+
+    // Empty queries match everything
+    assert!("   ".parse::<Query>().unwrap().is_empty());
+}
+
+#[test]
+fn test_query_display_round_trip() {
+    let source =
+        r#"priority>=medium due<=2w !#home list:"side projects" pending "big report" due:none"#;
+    let query: Query = source.parse().unwrap();
+    assert_eq!(query.to_string(), source);
+    assert_eq!(query.to_string().parse::<Query>().unwrap(), query);
+}
+
+#[test]
+fn test_query_parse_errors() {
+    for (source, message) in [
+        ("priority:urgent", "unknown priority"),
+        ("due<soon", "invalid due date"),
+        ("colour:red", "unknown field"),
+        ("list<work", "can only be compared with ':'"),
+        ("\"unfinished", "unterminated quote"),
+        ("priority:", "missing value"),
+        ("is:maybe", "unknown state"),
+        ("#", "must be followed by a tag"),
+        ("! done", "must be followed by a term"),
+    ] {
+        let error = source.parse::<Query>().unwrap_err();
+        assert!(
+            error.contains(message),
+            "'{}' gave '{}', expected '{}'",
+            source,
+            error,
+            message
+        );
+    }
+}
+
+#[test]
+fn test_query_sql_is_parameterised() {
+    let query: Query = r#"list:work "'; DROP TABLE todo_items; --""#.parse().unwrap();
+    let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    let builder = query.to_sql(Some(1), today);
+    let sql = builder.sql();
+
+    // Values are bound, never pasted into the SQL
+    assert!(!sql.contains("DROP"));
+    assert!(!sql.contains("work"));
+    assert_eq!(sql.matches('?').count(), 3);
+}
+
+#[tokio::test]
+async fn test_query_matches() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    let work = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+    let home = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Home".to_string(),
+        },
+    )
+    .await?;
+
+    // Due dates at local noon, to stay clear of day boundaries
+    let today = Local::now().date_naive();
+    let noon = |days: i64| {
+        Local
+            .from_local_datetime(
+                &(today + Duration::days(days))
+                    .and_hms_opt(12, 0, 0)
+                    .unwrap(),
+            )
+            .earliest()
+            .unwrap()
+            .with_timezone(&Utc)
+    };
+    let specs = [
+        (work.id, "Ship release", Some(Priority::High), Some(noon(2))),
+        (
+            work.id,
+            "Write report",
+            Some(Priority::Medium),
+            Some(noon(10)),
+        ),
+        (home.id, "Pay rent", Some(Priority::High), Some(noon(-1))),
+        (home.id, "Water plants", Some(Priority::Low), Some(noon(0))),
+        (home.id, "Read book", None, None),
+    ];
+    let mut items = Vec::new();
+    for (list_id, name, priority, due_date) in specs {
+        items.push(
+            TodoItem::create(
+                &pool,
+                NewTodoItem {
+                    list_id,
+                    name: name.to_string(),
+                    priority,
+                    due_date,
+                },
+            )
+            .await?,
+        );
+    }
+    items[2].toggle_done(&pool).await?;
+    items[3].set_tags(&pool, vec!["garden".to_string()]).await?;
+    let statuses = work.statuses();
+    items[0].move_to_column(&pool, &statuses, 1).await?;
+
+    let names = |source: &'static str| {
+        let pool = pool.clone();
+        async move {
+            let query: Query = source.parse().unwrap();
+            query
+                .get_matches(&pool, None)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|query_match| query_match.item.name)
+                .collect::<Vec<_>>()
+        }
+    };
+
+    assert_eq!(
+        names("priority:high").await,
+        vec!["Ship release", "Pay rent"]
+    );
+    assert_eq!(
+        names("priority>=medium !done").await,
+        vec!["Ship release", "Write report"]
+    );
+    assert_eq!(
+        names("due<7d").await,
+        vec!["Ship release", "Pay rent", "Water plants"]
+    );
+    assert_eq!(names("due:today").await, vec!["Water plants"]);
+    assert_eq!(
+        names("due>=tomorrow").await,
+        vec!["Ship release", "Write report"]
+    );
+    assert_eq!(names("!due<7d").await, vec!["Write report", "Read book"]);
+    assert_eq!(names("due:none").await, vec!["Read book"]);
+    assert_eq!(
+        names("list:home pending").await,
+        vec!["Water plants", "Read book"]
+    );
+    assert_eq!(names("#GARDEN").await, vec!["Water plants"]);
+    assert_eq!(names("status:doing").await, vec!["Ship release"]);
+    assert_eq!(
+        names("\"re\" !list:work").await,
+        vec!["Pay rent", "Read book"]
+    );
+    assert_eq!(names("").await.len(), 5);
+
+    // Matches can be limited to a single list
+    let query: Query = "priority:high".parse().unwrap();
+    let matches = query.get_matches(&pool, Some(home.id)).await?;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].list_name, "Home");
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+use judo::db::models::Priority;
+use judo::db::query::DueDate;
+use judo::db::quick_add::QuickAdd;
+
+#[test]
+fn test_quick_add_parsing() {
+    let quick_add = QuickAdd::parse("Pay rent !high @tomorrow #home #bills ^Chores");
+    assert_eq!(
+        quick_add,
+        QuickAdd {
+            name: "Pay rent".to_string(),
+            priority: Some(Priority::High),
+            due: Some(DueDate::Relative(1)),
+            tags: vec!["home".to_string(), "bills".to_string()],
+            list: Some("Chores".to_string()),
+        }
+    );
+
+    // Tokens can sit anywhere among the words of the name
+    let quick_add = QuickAdd::parse("#work Send @2026-10-20 the report !LOW");
+    assert_eq!(quick_add.name, "Send the report");
+    assert_eq!(quick_add.priority, Some(Priority::Low));
+    assert_eq!(
+        quick_add.due,
+        Some(DueDate::On(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap()))
+    );
+    assert_eq!(quick_add.tags, vec!["work".to_string()]);
+
+    // Plain names are left alone
+    let quick_add = QuickAdd::parse("  Buy   milk ");
+    assert_eq!(
+        quick_add,
+        QuickAdd {
+            name: "Buy milk".to_string(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_quick_add_literal_words() {
+    // Escaped words, lone sigils and sigils inside words belong to the name
+    let quick_add = QuickAdd::parse(r"Fix \#12 ! now me@example.com x^2");
+    assert_eq!(quick_add.name, "Fix #12 ! now me@example.com x^2");
+    assert_eq!(quick_add.priority, None);
+    assert!(quick_add.tags.is_empty());
+}
+
+#[test]
+fn test_quick_add_repeated_fields() {
+    // Later values win, repeated tags are kept once
+    let quick_add = QuickAdd::parse("Plan !low !high @today @3d ^a ^b #x #x");
+    assert_eq!(quick_add.priority, Some(Priority::High));
+    assert_eq!(quick_add.due, Some(DueDate::Relative(3)));
+    assert_eq!(quick_add.list, Some("b".to_string()));
+    assert_eq!(quick_add.tags, vec!["x".to_string()]);
+}
+
+#[test]
+fn test_quick_add_without_name() {
+    // Only the TUI insists on a name, the CLI takes empty ones too
+    let quick_add = QuickAdd::parse("!high #home");
+    assert_eq!(quick_add.name, "");
+    assert_eq!(quick_add.priority, Some(Priority::High));
+    assert_eq!(QuickAdd::parse(""), QuickAdd::default());
+}
+
+#[test]
+fn test_quick_add_invalid_tokens() {
+    // Words that only look like tokens stay in the name
+    let quick_add = QuickAdd::parse("Pay rent !urgent @someday #a,b Special! @#$%");
+    assert_eq!(
+        quick_add,
+        QuickAdd {
+            name: "Pay rent !urgent @someday #a,b Special! @#$%".to_string(),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_quick_add_dates_in_words() {
+    // Due dates take the same words as --due
+    let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    for (word, expected) in [("@fri", 23), ("@eow", 18), ("@tmr", 19), ("@EOM", 31)] {
+        let quick_add = QuickAdd::parse(&format!("Pay rent {}", word));
+        assert_eq!(quick_add.name, "Pay rent", "{}", word);
+        let due = quick_add.due.expect(word).resolve(today);
+        assert_eq!(due, NaiveDate::from_ymd_opt(2026, 10, expected).unwrap());
+    }
+}
+
+#[test]
+fn test_quick_add_new_item() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+    let quick_add = QuickAdd::parse("Pay rent !medium @tomorrow");
+
+    let new_item = quick_add.new_item(7, today);
+    assert_eq!(new_item.list_id, 7);
+    assert_eq!(new_item.name, "Pay rent");
+    assert_eq!(new_item.priority, Some(Priority::Medium));
+
+    // Due at the start of the local day
+    let expected = Local
+        .with_ymd_and_hms(2026, 10, 19, 0, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc);
+    assert_eq!(new_item.due_date, Some(expected));
+
+    let new_item = QuickAdd::parse("Buy milk").new_item(7, today);
+    assert_eq!(new_item.priority, None);
+    assert_eq!(new_item.due_date, None);
+}
+
+#[test]
+fn test_quick_add_describe() {
+    let today = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+
+    let quick_add = QuickAdd::parse("Pay rent !high @1d #home #bills ^Chores");
+    assert_eq!(
+        quick_add.describe(today),
+        "priority high · due Mon 2026-10-19 · #home #bills · in Chores"
+    );
+    assert_eq!(QuickAdd::parse("Buy milk").describe(today), "");
+}
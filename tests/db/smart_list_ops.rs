@@ -0,0 +1,233 @@
+use crate::helpers::db::setup_test_db;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use judo::db::models::{
+    NewSmartList, NewTodoItem, NewTodoList, Priority, SmartList, TodoItem, TodoList, UIList,
+};
+
+#[tokio::test]
+async fn test_smart_list_criteria() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    let work = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+    let home = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Home".to_string(),
+        },
+    )
+    .await?;
+
+    let specs = [
+        (work.id, "Ship release", Some(Priority::High), Some(3)),
+        (work.id, "Write report", Some(Priority::Medium), Some(20)),
+        (home.id, "Pay rent", Some(Priority::High), Some(-1)),
+        (home.id, "Water plants", Some(Priority::Low), Some(1)),
+        (home.id, "Read book", None, None),
+    ];
+    let mut items = Vec::new();
+    for (list_id, name, priority, days) in specs {
+        items.push(
+            TodoItem::create(
+                &pool,
+                NewTodoItem {
+                    list_id,
+                    name: name.to_string(),
+                    priority,
+                    due_date: days.map(|days| Utc::now() + Duration::days(days)),
+                },
+            )
+            .await?,
+        );
+    }
+    items[0]
+        .set_tags(&pool, vec!["#release".to_string()])
+        .await?;
+    items[3]
+        .set_tags(&pool, vec!["garden".to_string(), " chores ".to_string()])
+        .await?;
+    assert_eq!(items[3].tags(), vec!["garden", "chores"]);
+
+    // High-priority items due this week, across lists, soonest first
+    let urgent = SmartList::create(
+        &pool,
+        NewSmartList {
+            name: "Urgent".to_string(),
+            min_priority: Some(Priority::High),
+            due_within_days: Some(7),
+            ..Default::default()
+        },
+    )
+    .await?;
+    let names: Vec<_> = urgent
+        .get_items(&pool)
+        .await?
+        .into_iter()
+        .map(|item| item.name)
+        .collect();
+    assert_eq!(names, vec!["Pay rent", "Ship release"]);
+
+    let this_week = SmartList::create(
+        &pool,
+        NewSmartList {
+            name: "This week".to_string(),
+            due_within_days: Some(7),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(this_week.get_items(&pool).await?.len(), 3);
+
+    // Completed items drop out of smart lists asking for pending ones
+    let pending = SmartList::create(
+        &pool,
+        NewSmartList {
+            name: "Pending".to_string(),
+            min_priority: Some(Priority::Medium),
+            is_done: Some(false),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(pending.get_items(&pool).await?.len(), 3);
+    items[1].toggle_done(&pool).await?;
+    assert_eq!(pending.get_items(&pool).await?.len(), 2);
+
+    // Tags and text match whole tags and parts of names, ignoring case
+    let tagged = SmartList::create(
+        &pool,
+        NewSmartList {
+            name: "Chores".to_string(),
+            tag: Some("Chores".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    let tagged_items = tagged.get_items(&pool).await?;
+    assert_eq!(tagged_items.len(), 1);
+    assert_eq!(tagged_items[0].id, items[3].id);
+
+    let text = SmartList::create(
+        &pool,
+        NewSmartList {
+            name: "Reading".to_string(),
+            text: Some("READ".to_string()),
+            ..Default::default()
+        },
+    )
+    .await?;
+    assert_eq!(text.get_items(&pool).await?[0].name, "Read book");
+    assert_eq!(text.describe(), "\"READ\"");
+    assert_eq!(urgent.describe(), "priority >= high, due within 7 days");
+
+    // Deleted items and items of archived lists are left out
+    items[2].clone().delete(&pool).await?;
+    let mut work = work;
+    work.archive(&pool).await?;
+    assert!(urgent.get_items(&pool).await?.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_smart_lists_as_ui_lists() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    let list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+    let mut item = TodoItem::create(
+        &pool,
+        NewTodoItem {
+            list_id: list.id,
+            name: "Task".to_string(),
+            priority: Some(Priority::Low),
+            due_date: None,
+        },
+    )
+    .await?;
+
+    let mut smart_list = SmartList::create(
+        &pool,
+        NewSmartList {
+            name: "Pending".to_string(),
+            is_done: Some(false),
+            ..Default::default()
+        },
+    )
+    .await?;
+    smart_list.update_name(&pool, "To do".to_string()).await?;
+
+    let mut ui_lists = UIList::get_smart_lists(&pool).await?;
+    assert_eq!(ui_lists.len(), 1);
+    assert!(ui_lists[0].is_smart());
+    assert_eq!(ui_lists[0].list.name, "To do");
+    assert_eq!(ui_lists[0].items.len(), 1);
+
+    // Items are re-computed by query when refreshed
+    item.toggle_done(&pool).await?;
+    ui_lists[0].update_items(&pool).await?;
+    assert!(ui_lists[0].items.is_empty());
+
+    // Deleting the smart list leaves the items alone
+    smart_list.delete(&pool).await?;
+    assert!(SmartList::get_all(&pool).await?.is_empty());
+    assert!(TodoItem::get_by_id(&pool, item.id).await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_item_with_tags() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Home".to_string(),
+        },
+    )
+    .await?;
+    let new_item = |name: &str| NewTodoItem {
+        list_id: list.id,
+        name: name.to_string(),
+        priority: None,
+        due_date: None,
+    };
+
+    let item = TodoItem::create_with_tags(
+        &pool,
+        new_item("Water plants"),
+        vec![
+            "#garden".to_string(),
+            " chores ".to_string(),
+            "garden".to_string(),
+        ],
+    )
+    .await?;
+    assert_eq!(item.tags(), vec!["garden", "chores"]);
+
+    // A bad tag leaves no untagged item behind
+    assert!(
+        TodoItem::create_with_tags(&pool, new_item("Mow"), vec!["a,b".to_string()])
+            .await
+            .is_err()
+    );
+    let names: Vec<_> = TodoItem::get_by_list_id(&pool, list.id)
+        .await?
+        .into_iter()
+        .map(|item| item.name)
+        .collect();
+    assert_eq!(names, vec!["Water plants"]);
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use crate::helpers::db::setup_test_db;
 use anyhow::Result;
 use chrono::{Duration, Utc};
@@ -674,3 +676,77 @@ async fn test_todo_item_ordering_by_creation_date() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_todo_item_board_columns() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    let mut list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Board".to_string(),
+        },
+    )
+    .await?;
+    let mut item = TodoItem::create(
+        &pool,
+        NewTodoItem {
+            list_id: list.id,
+            name: "Card".to_string(),
+            priority: None,
+            due_date: None,
+        },
+    )
+    .await?;
+
+    // New items start in the first column
+    let statuses = list.statuses();
+    assert_eq!(statuses, vec!["todo", "doing", "done"]);
+    assert_eq!(item.status, "todo");
+    assert_eq!(item.board_column(&statuses), 0);
+
+    // Moving to the last column completes the item, moving back reopens it
+    item.move_to_column(&pool, &statuses, 2).await?;
+    assert!(item.is_done);
+    let board = list.get_board(&pool).await?;
+    assert_eq!(board[2].len(), 1);
+    item.move_to_column(&pool, &statuses, 1).await?;
+    assert!(!item.is_done);
+    assert_eq!(item.status, "doing");
+    assert!(item.move_to_column(&pool, &statuses, 3).await.is_err());
+
+    // Toggling done keeps the item in the last column
+    item.toggle_done(&pool).await?;
+    assert_eq!(item.board_column(&statuses), 2);
+    item.toggle_done(&pool).await?;
+    assert_eq!(item.board_column(&statuses), 1);
+
+    // Items whose column is removed fall back to the first one
+    list.set_statuses(
+        &pool,
+        vec![
+            "backlog".to_string(),
+            "review".to_string(),
+            "shipped".to_string(),
+        ],
+    )
+    .await?;
+    let board = list.get_board(&pool).await?;
+    assert_eq!(board.len(), 3);
+    assert_eq!(board[0][0].id, item.id);
+
+    // Invalid columns are rejected
+    assert!(
+        list.set_statuses(&pool, vec!["only".to_string()])
+            .await
+            .is_err()
+    );
+    assert!(
+        list.set_statuses(&pool, vec!["a".to_string(), "a".to_string()])
+            .await
+            .is_err()
+    );
+    assert_eq!(list.statuses(), vec!["backlog", "review", "shipped"]);
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
+#![allow(clippy::clone_on_copy)]
+
 use crate::helpers::db::setup_test_db;
 use anyhow::Result;
 use chrono::{Duration, Utc};
-use judo::db::models::{NewTodoItem, NewTodoList, Priority, TodoItem, TodoList, UIList};
+use judo::db::models::{NewTodoItem, NewTodoList, Priority, SortMode, TodoItem, TodoList, UIList};
 
 #[tokio::test]
 async fn test_todo_list_crud_operations() -> Result<()> {
@@ -265,8 +267,8 @@ async fn test_todo_list_cascade_deletion() -> Result<()> {
     let items_before = TodoItem::get_by_list_id(&pool, test_list_id.clone()).await?;
     assert_eq!(items_before.len(), 3);
 
-    // Delete the list
-    test_list.delete(&pool).await?;
+    // Permanently delete the list
+    test_list.purge(&pool).await?;
 
     // Verify list is deleted
     let deleted_list = TodoList::get_by_id(&pool, test_list_id.clone()).await?;
@@ -632,3 +634,216 @@ async fn test_multiple_lists_with_mixed_items() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_todo_list_hide_done_and_clear_done() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    let mut test_list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Chores".to_string(),
+        },
+    )
+    .await?;
+    assert!(!test_list.hide_done);
+
+    // Create three items and complete two of them
+    let mut items = Vec::new();
+    for name in ["Laundry", "Dishes", "Vacuum"] {
+        items.push(
+            TodoItem::create(
+                &pool,
+                NewTodoItem {
+                    list_id: test_list.id,
+                    name: name.to_string(),
+                    priority: None,
+                    due_date: None,
+                },
+            )
+            .await?,
+        );
+    }
+    items[0].toggle_done(&pool).await?;
+    items[2].toggle_done(&pool).await?;
+
+    // All items are visible by default
+    let mut ui_lists = UIList::get_all(&pool).await?;
+    assert_eq!(ui_lists[0].items.len(), 3);
+
+    // Hiding done items is persisted and filters the UI list
+    test_list.set_hide_done(&pool, true).await?;
+    let fetched = TodoList::get_by_id(&pool, test_list.id)
+        .await?
+        .expect("List should exist");
+    assert!(fetched.hide_done);
+
+    ui_lists[0].list = fetched;
+    ui_lists[0].item_state.select(Some(2));
+    ui_lists[0].update_items(&pool).await?;
+    assert_eq!(ui_lists[0].items.len(), 1);
+    assert_eq!(ui_lists[0].items[0].item.name, "Dishes");
+    assert_eq!(ui_lists[0].item_state.selected(), Some(0));
+
+    let ui_lists = UIList::get_all(&pool).await?;
+    assert_eq!(ui_lists[0].items.len(), 1);
+
+    // Clearing done items deletes them for good
+    let deleted = test_list.clear_done(&pool).await?;
+    assert_eq!(deleted, 2);
+
+    let remaining = TodoItem::get_by_list_id(&pool, test_list.id).await?;
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].name, "Dishes");
+
+    // Nothing left to clear
+    assert_eq!(test_list.clear_done(&pool).await?, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_todo_list_archive_and_unarchive() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    let mut lists = Vec::new();
+    for name in ["First", "Second", "Third"] {
+        lists.push(
+            TodoList::create(
+                &pool,
+                NewTodoList {
+                    name: name.to_string(),
+                },
+            )
+            .await?,
+        );
+    }
+
+    TodoItem::create(
+        &pool,
+        NewTodoItem {
+            list_id: lists[1].id,
+            name: "Keep me".to_string(),
+            priority: None,
+            due_date: None,
+        },
+    )
+    .await?;
+
+    // Archived lists are hidden from the active lists
+    lists[1].archive(&pool).await?;
+    assert!(lists[1].archived_at.is_some());
+
+    let active = TodoList::get_all(&pool).await?;
+    assert_eq!(active.len(), 2);
+    assert!(active.iter().all(|list| list.id != lists[1].id));
+
+    let ui_lists = UIList::get_all(&pool).await?;
+    assert_eq!(ui_lists.len(), 2);
+
+    let archived = TodoList::get_archived(&pool).await?;
+    assert_eq!(archived.len(), 1);
+    assert_eq!(archived[0].name, "Second");
+
+    // Moving skips over the archived list
+    lists[2].move_up(&pool).await?;
+    let active = TodoList::get_all(&pool).await?;
+    assert_eq!(active[0].name, "Third");
+    assert_eq!(active[1].name, "First");
+
+    // Restoring keeps the items of the list
+    lists[1].unarchive(&pool).await?;
+    assert!(lists[1].archived_at.is_none());
+    assert!(TodoList::get_archived(&pool).await?.is_empty());
+    assert_eq!(TodoList::get_all(&pool).await?.len(), 3);
+
+    let items = TodoItem::get_by_list_id(&pool, lists[1].id).await?;
+    assert_eq!(items.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_todo_list_sort_modes() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    let mut test_list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Sorted".to_string(),
+        },
+    )
+    .await?;
+    assert_eq!(test_list.sort_mode, SortMode::Manual);
+
+    // Manual order: banana, Apple, cherry, date
+    let specs = [
+        (
+            "banana",
+            Some(Priority::Low),
+            Some(Utc::now() + Duration::days(3)),
+        ),
+        ("Apple", None, Some(Utc::now() + Duration::days(1))),
+        ("cherry", Some(Priority::High), None),
+        (
+            "date",
+            Some(Priority::Medium),
+            Some(Utc::now() + Duration::days(2)),
+        ),
+    ];
+    let mut items = Vec::new();
+    for (name, priority, due_date) in specs {
+        items.push(
+            TodoItem::create(
+                &pool,
+                NewTodoItem {
+                    list_id: test_list.id,
+                    name: name.to_string(),
+                    priority,
+                    due_date,
+                },
+            )
+            .await?,
+        );
+    }
+    items[0].toggle_done(&pool).await?;
+
+    let mut ui_list = UIList::get_all(&pool).await?.remove(0);
+    let names = |ui_list: &UIList| -> Vec<String> {
+        ui_list.items.iter().map(|i| i.item.name.clone()).collect()
+    };
+    assert_eq!(names(&ui_list), ["banana", "Apple", "cherry", "date"]);
+
+    let expected = [
+        (SortMode::Priority, ["cherry", "date", "banana", "Apple"]),
+        (SortMode::DueDate, ["Apple", "date", "banana", "cherry"]),
+        (SortMode::Created, ["banana", "Apple", "cherry", "date"]),
+        (
+            SortMode::Alphabetical,
+            ["Apple", "banana", "cherry", "date"],
+        ),
+        (SortMode::DoneLast, ["Apple", "cherry", "date", "banana"]),
+    ];
+    for (mode, order) in expected {
+        test_list.set_sort_mode(&pool, mode).await?;
+        ui_list.list = test_list.clone();
+        ui_list.update_items(&pool).await?;
+        assert_eq!(names(&ui_list), order, "Unexpected order for {}", mode);
+    }
+
+    // The sort mode is persisted
+    let fetched = TodoList::get_by_id(&pool, test_list.id)
+        .await?
+        .expect("List should exist");
+    assert_eq!(fetched.sort_mode, SortMode::DoneLast);
+
+    // Rebaselining keeps the order on screen but makes it the manual one
+    test_list.set_sort_mode(&pool, SortMode::Priority).await?;
+    test_list.rebaseline_ordering(&pool).await?;
+    assert_eq!(test_list.sort_mode, SortMode::Manual);
+    ui_list.list = test_list.clone();
+    ui_list.update_items(&pool).await?;
+    assert_eq!(names(&ui_list), ["cherry", "date", "banana", "Apple"]);
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use crate::helpers::db::setup_test_db;
+use anyhow::Result;
+use judo::db::models::{NewTodoItem, NewTodoList, TodoItem, TodoList, TrashEntry, UIList};
+
+async fn create_list_with_items(
+    pool: &sqlx::SqlitePool,
+    name: &str,
+    items: &[&str],
+) -> Result<(TodoList, Vec<TodoItem>)> {
+    let list = TodoList::create(
+        pool,
+        NewTodoList {
+            name: name.to_string(),
+        },
+    )
+    .await?;
+
+    let mut created = Vec::new();
+    for item in items {
+        created.push(
+            TodoItem::create(
+                pool,
+                NewTodoItem {
+                    list_id: list.id,
+                    name: item.to_string(),
+                    priority: None,
+                    due_date: None,
+                },
+            )
+            .await?,
+        );
+    }
+
+    Ok((list, created))
+}
+
+#[tokio::test]
+async fn test_deleted_list_goes_to_trash_and_back() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let (list, items) = create_list_with_items(&pool, "Groceries", &["Milk", "Eggs"]).await?;
+    let list_id = list.id;
+
+    list.delete(&pool).await?;
+
+    // The list and its items are hidden everywhere
+    assert!(TodoList::get_all(&pool).await?.is_empty());
+    assert!(TodoList::get_by_id(&pool, list_id).await?.is_none());
+    assert!(TodoItem::get_by_id(&pool, items[0].id).await?.is_none());
+    assert!(UIList::get_all(&pool).await?.is_empty());
+
+    // Only the list shows up in the trash, not its items
+    let trash = TrashEntry::get_all(&pool).await?;
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].name(), "Groceries");
+    assert!(trash[0].deleted_at().is_some());
+
+    // Restoring brings the items back with the list
+    trash[0].restore(&pool).await?;
+    assert!(TrashEntry::get_all(&pool).await?.is_empty());
+    let restored = TodoList::get_by_id(&pool, list_id)
+        .await?
+        .expect("List should be restored");
+    assert_eq!(restored.get_all_items(&pool).await?.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deleted_item_goes_to_trash_and_back() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let (list, mut items) = create_list_with_items(&pool, "Work", &["Email", "Report"]).await?;
+    let item = items.remove(0);
+    let item_id = item.id;
+
+    item.delete(&pool).await?;
+    assert_eq!(list.get_all_items(&pool).await?.len(), 1);
+
+    let entry = TrashEntry::get_item(&pool, item_id)
+        .await?
+        .expect("Item should be in the trash");
+    match &entry {
+        TrashEntry::Item { item, list_name } => {
+            assert_eq!(item.name, "Email");
+            assert_eq!(list_name, "Work");
+        }
+        TrashEntry::List(_) => panic!("Expected a deleted item"),
+    }
+    assert!(TrashEntry::get_list(&pool, list.id).await?.is_none());
+
+    entry.restore(&pool).await?;
+    assert_eq!(list.get_all_items(&pool).await?.len(), 2);
+    assert!(TodoItem::get_by_id(&pool, item_id).await?.is_some());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_purge_and_empty_trash() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let (first, _) = create_list_with_items(&pool, "First", &["A"]).await?;
+    let (_, mut items) = create_list_with_items(&pool, "Second", &["B", "C"]).await?;
+    let first_id = first.id;
+
+    first.delete(&pool).await?;
+    items.remove(0).delete(&pool).await?;
+    items.remove(0).delete(&pool).await?;
+    assert_eq!(TrashEntry::get_all(&pool).await?.len(), 3);
+
+    // Purging a single entry removes it for good
+    let entry = TrashEntry::get_list(&pool, first_id)
+        .await?
+        .expect("List should be in the trash");
+    entry.purge(&pool).await?;
+    assert_eq!(TrashEntry::get_all(&pool).await?.len(), 2);
+
+    // Emptying removes the rest
+    assert_eq!(TrashEntry::empty(&pool).await?, 2);
+    assert!(TrashEntry::get_all(&pool).await?.is_empty());
+    assert_eq!(TodoList::get_all(&pool).await?.len(), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_purge_expired_trash() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let (old, _) = create_list_with_items(&pool, "Old", &["A"]).await?;
+    let (recent, _) = create_list_with_items(&pool, "Recent", &[]).await?;
+    let old_id = old.id;
+
+    old.delete(&pool).await?;
+    recent.delete(&pool).await?;
+
+    // Pretend the first list was deleted long ago
+    sqlx::query("UPDATE todo_lists SET deleted_at = ?1 WHERE id = ?2")
+        .bind(chrono::Utc::now() - chrono::Duration::days(45))
+        .bind(old_id)
+        .execute(&pool)
+        .await?;
+
+    // A retention of 0 days never purges
+    assert_eq!(TrashEntry::purge_expired(&pool, 0).await?, 0);
+
+    assert_eq!(TrashEntry::purge_expired(&pool, 30).await?, 1);
+    let trash = TrashEntry::get_all(&pool).await?;
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].name(), "Recent");
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use crate::helpers::db::setup_test_db_shared;
+use crate::helpers::fs::setup_test_dir;
+use anyhow::Result;
+use judo::app::App;
+use judo::db::config::{Config, DBConfig};
+use judo::db::connections::PoolRegistry;
+
+/// Build a test App backed by a named shared in-memory database.
+///
+/// The pool is registered under the database name, so CLI ops reuse it.
+/// Because the connection string uses `cache=shared`, any other pool
+/// opened on it still hits the **same** in-memory database.
+pub async fn setup_test_app() -> Result<App> {
+    let (pool, connection_str) = setup_test_db_shared().await?;
+
+    let test_db_config = DBConfig {
+        name: "test_db".to_string(),
+        connection_str,
+        sqlite: Default::default(),
+    };
+
+    let config = Config {
+        default: "test_db".to_string(),
+        dbs: vec![test_db_config.clone()],
+        colours: Default::default(),
+        remember_session: true,
+        trash_retention_days: 30,
+        session: None,
+    };
+
+    let pools = PoolRegistry::new();
+    pools.insert(&test_db_config, pool.clone());
+
+    Ok(App {
+        config,
+        config_path: setup_test_dir()?.join("judo.toml"),
+        current_db_config: test_db_config,
+        current_screen: judo::app::state::CurrentScreen::Main,
+        pool,
+        change_watcher: None,
+        pools,
+        lists_component: judo::ui::components::ListsComponent::new(),
+        archive_component: judo::ui::components::ArchiveComponent::new(),
+        trash_component: judo::ui::components::TrashComponent::new(),
+        agenda_component: judo::ui::components::AgendaComponent::new(),
+        board_component: judo::ui::components::BoardComponent::new(),
+        input_state: judo::ui::components::InputState::new(),
+        selected_db_index: 0,
+        session_state: Default::default(),
+        session_path: Some(setup_test_dir()?.join("state.toml")),
+        tasks: judo::app::tasks::BackgroundTasks::new(),
+        status: None,
+        today: chrono::Local::now().date_naive(),
+        exit: false,
+    })
+}
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use judo::db::models::{NewTodoItem, TodoItem};
 use sqlx::migrate::Migrator;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
 use std::str::FromStr;
@@ -48,3 +49,17 @@ pub async fn setup_test_db_shared() -> Result<(SqlitePool, String)> {
 
     Ok((pool, connection_str))
 }
+
+/// Create an item with only a name in the given list
+pub async fn create_item(pool: &SqlitePool, list_id: i64, name: &str) -> Result<TodoItem> {
+    TodoItem::create(
+        pool,
+        NewTodoItem {
+            list_id,
+            name: name.to_string(),
+            priority: None,
+            due_date: None,
+        },
+    )
+    .await
+}
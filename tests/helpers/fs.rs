@@ -0,0 +1,20 @@
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static TEST_DIR_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Create a fresh, empty directory under the system temp directory.
+///
+/// Each call produces a unique path so tests never interfere.
+pub fn setup_test_dir() -> Result<PathBuf> {
+    let id = TEST_DIR_COUNTER.fetch_add(1, Ordering::SeqCst);
+    let dir = std::env::temp_dir().join(format!("judo_test_{}_{}", std::process::id(), id));
+
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).with_context(|| "Failed to clean test directory")?;
+    }
+    std::fs::create_dir_all(&dir).with_context(|| "Failed to create test directory")?;
+
+    Ok(dir)
+}
@@ -1,3 +1,5 @@
+mod app;
 mod cli;
 mod db;
 mod helpers;
+mod ui;
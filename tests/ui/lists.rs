@@ -0,0 +1,81 @@
+use crate::helpers::db::setup_test_db;
+use anyhow::Result;
+use judo::db::models::{NewSmartList, NewTodoList, Priority, SmartList, TodoList};
+use judo::ui::components::ListsComponent;
+
+#[tokio::test]
+async fn test_select_list_by_id_and_name() -> Result<()> {
+    let pool = setup_test_db().await?;
+
+    TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Home".to_string(),
+        },
+    )
+    .await?;
+    let work = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Work".to_string(),
+        },
+    )
+    .await?;
+
+    let mut lists_component = ListsComponent::new();
+    lists_component.load_lists(&pool).await?;
+    assert_eq!(lists_component.selected(), None);
+
+    // Select by ID
+    assert!(lists_component.select_list_by_id(work.id));
+    assert_eq!(lists_component.selected(), Some(1));
+
+    // Select by name
+    assert!(lists_component.select_list_by_name("Home"));
+    assert_eq!(lists_component.selected(), Some(0));
+
+    // Unknown lists leave the selection untouched
+    assert!(!lists_component.select_list_by_id(work.id + 100));
+    assert!(!lists_component.select_list_by_name("Missing"));
+    assert_eq!(lists_component.selected(), Some(0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_move_last_list_down_keeps_it_selected() -> Result<()> {
+    let pool = setup_test_db().await?;
+    for name in ["Home", "Work"] {
+        TodoList::create(
+            &pool,
+            NewTodoList {
+                name: name.to_string(),
+            },
+        )
+        .await?;
+    }
+    SmartList::create(
+        &pool,
+        NewSmartList {
+            name: "Urgent".to_string(),
+            min_priority: Some(Priority::High),
+            due_within_days: None,
+            is_done: None,
+            tag: None,
+            text: None,
+        },
+    )
+    .await?;
+
+    let mut lists_component = ListsComponent::new();
+    lists_component.load_lists(&pool).await?;
+    assert!(lists_component.select_list_by_name("Work"));
+
+    // Nothing moves past the smart lists, and neither does the selection
+    ListsComponent::move_selected_list_down(&mut lists_component, &pool).await?;
+    let selected = lists_component.get_selected_list().unwrap();
+    assert!(!selected.is_smart());
+    assert_eq!(selected.list.name, "Work");
+
+    Ok(())
+}
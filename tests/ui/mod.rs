@@ -0,0 +1,2 @@
+pub mod lists;
+pub mod theme;
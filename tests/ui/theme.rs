@@ -0,0 +1,135 @@
+use crate::helpers::db::setup_test_db;
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use judo::db::config::Config;
+use judo::db::models::{NewTodoItem, NewTodoList, Priority, TodoItem, TodoList};
+use judo::ui::components::{AddItemPopUp, InputState, ItemsComponent, ListsComponent};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+
+/// Style of the first cell of the row showing `text`
+fn style_of(buf: &Buffer, text: &str) -> Style {
+    let area = buf.area;
+    for y in 0..area.height {
+        let row: String = (0..area.width)
+            .map(|x| buf[(x, y)].symbol().to_string())
+            .collect();
+        if let Some(start) = row.find(text) {
+            let x = row[..start].chars().count() as u16;
+            return buf[(x, y)].style();
+        }
+    }
+    panic!("'{}' is not on screen", text);
+}
+
+#[tokio::test]
+async fn test_items_are_styled_by_theme() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let list = TodoList::create(
+        &pool,
+        NewTodoList {
+            name: "Chores".to_string(),
+        },
+    )
+    .await?;
+    for (name, priority, due_date) in [
+        ("Urgent", Some(Priority::High), None),
+        ("Someday", Some(Priority::Low), None),
+        ("Late", None, Some(Utc::now() - Duration::days(3))),
+        ("Finished", Some(Priority::High), None),
+    ] {
+        let mut item = TodoItem::create(
+            &pool,
+            NewTodoItem {
+                list_id: list.id,
+                name: name.to_string(),
+                priority,
+                due_date,
+            },
+        )
+        .await?;
+        if name == "Finished" {
+            item.toggle_done(&pool).await?;
+        }
+    }
+
+    let mut lists_component = ListsComponent::new();
+    lists_component.load_lists(&pool).await?;
+    lists_component.select_first();
+
+    let config: Config = toml::from_str(
+        r#"
+        default = "dojo"
+        dbs = []
+
+        [colours]
+        theme = "nord"
+        overdue = { fg = "red", italic = true }
+        done = { crossed_out = false, dim = true }
+        "#,
+    )?;
+    let area = Rect::new(0, 0, 80, 20);
+    let mut buf = Buffer::empty(area);
+    ItemsComponent::render(
+        lists_component.get_selected_list_mut(),
+        false,
+        area,
+        &mut buf,
+        config.clone(),
+    );
+
+    // Derived from the theme's colours
+    let urgent = style_of(&buf, "Urgent");
+    assert_eq!(urgent.fg, Some(config.highlight()));
+    assert!(
+        style_of(&buf, "Someday")
+            .add_modifier
+            .contains(Modifier::DIM)
+    );
+    assert_eq!(style_of(&buf, "I T E M S").fg, Some(config.foreground()));
+
+    // Set in the config
+    let late = style_of(&buf, "Late");
+    assert_eq!(late.fg, Some(Color::Red));
+    assert!(
+        late.add_modifier
+            .contains(Modifier::ITALIC | Modifier::BOLD)
+    );
+    let finished = style_of(&buf, "Finished");
+    assert!(finished.add_modifier.contains(Modifier::DIM));
+    assert!(!finished.add_modifier.contains(Modifier::CROSSED_OUT));
+
+    Ok(())
+}
+
+#[test]
+fn test_add_item_popup_is_styled_by_theme() -> Result<()> {
+    let config: Config = toml::from_str(
+        r#"
+        default = "dojo"
+        dbs = []
+
+        [colours]
+        status_error = { fg = "magenta" }
+        "#,
+    )?;
+    let area = Rect::new(0, 0, 80, 20);
+
+    let mut buf = Buffer::empty(area);
+    AddItemPopUp::render(config.clone(), &InputState::new(), area, &mut buf);
+    let hint = style_of(&buf, "!high @tomorrow");
+    assert_eq!(hint.fg, Some(config.foreground()));
+    assert!(hint.add_modifier.contains(Modifier::DIM));
+
+    let mut buf = Buffer::empty(area);
+    let state = InputState {
+        current_input: "!high".to_string(),
+        cursor_pos: 5,
+        is_modifying: false,
+    };
+    AddItemPopUp::render(config, &state, area, &mut buf);
+    assert_eq!(style_of(&buf, "missing item name").fg, Some(Color::Magenta));
+
+    Ok(())
+}